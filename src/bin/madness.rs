@@ -0,0 +1,15 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match madness_rs::cli::run(&args) {
+        Ok(report) => {
+            print!("{}", report);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("madness: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt;
+
+use crate::distribution::FinishDistribution;
+use crate::error::MadnessError;
+use crate::packed::PackedPool;
+use crate::{Bracket, Tournament, COMPLETE_MASK};
+
+/// A compact alternative to the JSON forms, for snapshots and for payloads
+/// sent to WASM clients. Whole numbers are little-endian, or LEB128 varints
+/// where they're usually small.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The bytes ran out at this offset.
+    Truncated(usize),
+    /// Bytes left over from this offset on.
+    Trailing(usize),
+    /// Read to the end, but not a value of the type.
+    Shape(&'static str),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::Truncated(offset) => write!(f, "bytes end early at {}", offset),
+            BinaryError::Trailing(offset) => write!(f, "unexpected bytes from {}", offset),
+            BinaryError::Shape(what) => write!(f, "unexpected bytes: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+fn write_varint(bytes: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        bytes.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes, at: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], BinaryError> {
+        let end = self
+            .at
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(BinaryError::Truncated(self.bytes.len()))?;
+        let taken = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(taken)
+    }
+
+    fn u64(&mut self) -> Result<u64, BinaryError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("eight bytes")))
+    }
+
+    fn varint(&mut self) -> Result<u64, BinaryError> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            n |= u64::from(byte & 0x7F)
+                .checked_shl(shift)
+                .filter(|&bits| bits >> shift == u64::from(byte & 0x7F))
+                .ok_or(BinaryError::Shape("a varint overflows 64 bits"))?;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(BinaryError::Shape("a varint overflows 64 bits"))
+    }
+
+    fn len(&mut self) -> Result<usize, BinaryError> {
+        let n = self.varint()?;
+        // Every counted item takes at least a byte.
+        usize::try_from(n)
+            .ok()
+            .filter(|&n| n <= self.bytes.len() - self.at)
+            .ok_or(BinaryError::Truncated(self.bytes.len()))
+    }
+
+    fn finish(&self) -> Result<(), BinaryError> {
+        if self.at == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(BinaryError::Trailing(self.at))
+        }
+    }
+}
+
+impl Tournament {
+    /// The results in 24 bytes: the decisions, mask and vacated bits.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.decisions, self.mask, self.vacated]
+            .iter()
+            .flat_map(|bits| bits.to_le_bytes())
+            .collect()
+    }
+
+    /// Reads the results back from [`Tournament::to_bytes`]'s form, checking
+    /// them as [`Tournament::try_new`] does.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Tournament, MadnessError> {
+        let mut reader = Reader::new(bytes);
+        let tournament = Tournament {
+            decisions: reader.u64()?,
+            mask: reader.u64()?,
+            vacated: reader.u64()?,
+        };
+        reader.finish()?;
+        tournament
+            .validate_strict()
+            .map_err(MadnessError::InvalidTournament)?;
+        Ok(tournament)
+    }
+}
+
+impl Bracket {
+    /// The picks in 8 bytes.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.decisions.to_le_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Bracket, MadnessError> {
+        let mut reader = Reader::new(bytes);
+        let decisions = reader.u64()?;
+        reader.finish()?;
+        if decisions & !COMPLETE_MASK != 0 {
+            return Err(MadnessError::ReservedBit);
+        }
+        Ok(Bracket { decisions })
+    }
+}
+
+impl PackedPool {
+    /// The entries: their count, then per entry its picks, its adjustment
+    /// and its owner's name, prefixed with its length.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        write_varint(&mut bytes, self.len() as u64);
+        for entry in 0..self.len() {
+            bytes.extend(self.bracket(entry).to_bytes());
+            // Zigzag, so small losses are as short as small gains.
+            let adjustment = self.adjustment(entry);
+            write_varint(&mut bytes, ((adjustment << 1) ^ (adjustment >> 63)) as u64);
+            let owner = self.owner(entry);
+            write_varint(&mut bytes, owner.len() as u64);
+            bytes.extend(owner.as_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<PackedPool, MadnessError> {
+        let mut reader = Reader::new(bytes);
+        let entries = reader.len()?;
+        let mut pool = PackedPool::with_capacity(entries, 0);
+        for _ in 0..entries {
+            let bracket = Bracket::from_bytes(reader.take(8)?)?;
+            let zigzag = reader.varint()?;
+            let adjustment = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+            let len = reader.len()?;
+            let owner = std::str::from_utf8(reader.take(len)?)
+                .map_err(|_| BinaryError::Shape("an owner's name isn't UTF-8"))?;
+            pool.push(owner, bracket, adjustment);
+        }
+        reader.finish()?;
+        Ok(pool)
+    }
+}
+
+impl FinishDistribution {
+    /// The scenario count, the number of rows and of places in each, then
+    /// every count row by row. Counts are varints, so the many zeros of a
+    /// big pool take a byte each.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        write_varint(&mut bytes, self.scenarios);
+        write_varint(&mut bytes, self.counts.len() as u64);
+        let places = self.counts.first().map_or(0, Vec::len);
+        write_varint(&mut bytes, places as u64);
+        for &count in self.counts.iter().flatten() {
+            write_varint(&mut bytes, count);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<FinishDistribution, MadnessError> {
+        let mut reader = Reader::new(bytes);
+        let scenarios = reader.varint()?;
+        let entries = reader.len()?;
+        let places = reader.len()?;
+        if places > entries || (entries > 0 && places == 0) {
+            return Err(BinaryError::Shape("a distribution has 1 to one place per entry").into());
+        }
+        let counts = (0..entries)
+            .map(|_| (0..places).map(|_| reader.varint()).collect())
+            .collect::<Result<_, _>>()?;
+        reader.finish()?;
+        Ok(FinishDistribution { counts, scenarios })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::Pool;
+    use crate::scoring::ScoringSystem;
+    use crate::testing::{random_brackets, random_tournament};
+    use crate::view::TournamentView;
+
+    #[test]
+    fn round_trips() {
+        for seed in 0..5 {
+            let tournament = random_tournament(seed, 40);
+            assert_eq!(
+                Tournament::from_bytes(&tournament.to_bytes()),
+                Ok(tournament)
+            );
+        }
+        let mut vacated = Tournament::new(0, crate::round_slot_mask(1));
+        vacated.record_no_contest(17).unwrap();
+        assert_eq!(Tournament::from_bytes(&vacated.to_bytes()), Ok(vacated));
+
+        let brackets = random_brackets(4, 5);
+        for bracket in &brackets {
+            assert_eq!(Bracket::from_bytes(&bracket.to_bytes()), Ok(*bracket));
+        }
+
+        let mut pool = Pool::new(Tournament::new(0, 0), ScoringSystem::standard());
+        for (i, &bracket) in brackets.iter().enumerate() {
+            pool.insert(&format!("Sébastien {}", i), bracket);
+        }
+        pool.adjust("Sébastien 1", -3, "late swap").unwrap();
+        pool.adjust("Sébastien 2", i32::MAX as i64, "bonus")
+            .unwrap();
+        let packed = PackedPool::from_pool(&pool);
+        assert_eq!(PackedPool::from_bytes(&packed.to_bytes()), Ok(packed));
+
+        let view = TournamentView::new(&Tournament::new(0, crate::COMPLETE_MASK & !0b1110));
+        let distribution = view.finish_distribution(&brackets).unwrap();
+        let bytes = distribution.to_bytes();
+        // JSON would spend at least two bytes a count.
+        assert!(bytes.len() < 2 * brackets.len() * brackets.len());
+        assert_eq!(FinishDistribution::from_bytes(&bytes), Ok(distribution));
+    }
+
+    #[test]
+    fn rejects_what_it_did_not_write() {
+        let bytes = Bracket::from_decisions(0).to_bytes();
+        assert_eq!(
+            Bracket::from_bytes(&bytes[..7]),
+            Err(MadnessError::Binary(BinaryError::Truncated(7)))
+        );
+        let mut long = bytes.to_vec();
+        long.push(0);
+        assert_eq!(
+            Bracket::from_bytes(&long),
+            Err(MadnessError::Binary(BinaryError::Trailing(8)))
+        );
+        assert_eq!(
+            Bracket::from_bytes(&1u64.to_le_bytes()),
+            Err(MadnessError::ReservedBit)
+        );
+        // The title game decided before its semifinals.
+        let early = Tournament {
+            decisions: 0,
+            mask: 1 << 1,
+            vacated: 0,
+        };
+        assert!(matches!(
+            Tournament::from_bytes(&early.to_bytes()),
+            Err(MadnessError::InvalidTournament(_))
+        ));
+        // Claims more entries than there are bytes.
+        assert_eq!(
+            PackedPool::from_bytes(&[5]),
+            Err(MadnessError::Binary(BinaryError::Truncated(1)))
+        );
+        assert!(matches!(
+            FinishDistribution::from_bytes(&[0, 1, 2, 0, 0]),
+            Err(MadnessError::Binary(BinaryError::Shape(_)))
+        ));
+        assert!(matches!(
+            FinishDistribution::from_bytes(&[0xFF; 11]),
+            Err(MadnessError::Binary(BinaryError::Shape(_)))
+        ));
+    }
+}
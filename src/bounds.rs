@@ -0,0 +1,273 @@
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::{Bracket, Score};
+
+/// Limits on a bracket's chance of finishing in the top places that hold
+/// however the scenarios not yet looked at go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FinishBound {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// An enumeration of top-`places` finish chances under a scoring system,
+/// weighted by a model, that runs a bounded number of scenarios per call and
+/// can be asked for sound bounds on every chance in between. The bounds meet
+/// once it's done.
+#[derive(Debug)]
+pub struct FinishBoundsJob<M> {
+    deltas: ScoreDeltaTable,
+    model: M,
+    places: usize,
+    team_slots: Vec<Option<u8>>,
+    undecided: Vec<u8>,
+    // Side chosen for each undecided game of the current scenario.
+    choices: Vec<u8>,
+    // Brackets whose finish is the same in every scenario, found from score
+    // ranges up front: `Some(true)` always in the top places, `Some(false)`
+    // never.
+    settled: Vec<Option<bool>>,
+    // Per bracket, the chance of the scenarios so far where it finished in
+    // the top places.
+    reached: Vec<f64>,
+    explored: f64,
+    scores: Vec<Score>,
+    scenarios_done: u64,
+    done: bool,
+}
+
+impl<M: WinProbability> FinishBoundsJob<M> {
+    pub fn new(
+        brackets: &[Bracket],
+        view: &TournamentView,
+        scoring: &ScoringSystem,
+        places: usize,
+        model: M,
+    ) -> FinishBoundsJob<M> {
+        let ranges: Vec<_> = brackets
+            .iter()
+            .map(|b| b.score_range(view, scoring))
+            .collect();
+        // Ranks count the brackets strictly ahead, so a bracket is safe while
+        // fewer than `places` others can pass its minimum, and out once that
+        // many are sure to beat its maximum.
+        let settled = ranges
+            .iter()
+            .enumerate()
+            .map(|(i, range)| {
+                let others = || ranges.iter().enumerate().filter(move |(j, _)| *j != i);
+                let can_pass = others().filter(|(_, r)| r.maximum > range.minimum).count();
+                let sure_to_beat = others().filter(|(_, r)| r.minimum > range.maximum).count();
+                if can_pass < places {
+                    Some(true)
+                } else if sure_to_beat >= places {
+                    Some(false)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut job = FinishBoundsJob {
+            deltas: ScoreDeltaTable::for_view_with(view, brackets, scoring),
+            model,
+            places,
+            team_slots: view.team_slots().to_vec(),
+            undecided: view.undecided().to_vec(),
+            choices: vec![0; view.undecided().len()],
+            settled,
+            reached: vec![0.0; brackets.len()],
+            explored: 0.0,
+            scores: Vec::with_capacity(brackets.len()),
+            scenarios_done: 0,
+            done: false,
+        };
+        job.fill_from(0);
+        job
+    }
+
+    /// Evaluates up to `max_scenarios` more scenarios, in the order of
+    /// [`TournamentView::for_each_scenario`]. Returns true once every
+    /// scenario has been evaluated.
+    pub fn step(&mut self, max_scenarios: u64) -> bool {
+        let mut budget = max_scenarios;
+        while !self.done && budget > 0 {
+            self.record();
+            self.scenarios_done += 1;
+            budget -= 1;
+            self.advance();
+        }
+        self.done
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn scenarios_done(&self) -> u64 {
+        self.scenarios_done
+    }
+
+    /// Per bracket in the order given: the chance so far is the lower
+    /// bound, and the upper adds every scenario still to come.
+    pub fn bounds(&self) -> Vec<FinishBound> {
+        let unexplored = if self.done {
+            0.0
+        } else {
+            (1.0 - self.explored).max(0.0)
+        };
+        self.reached
+            .iter()
+            .zip(&self.settled)
+            .map(|(&reached, settled)| match settled {
+                Some(true) => FinishBound {
+                    lower: 1.0,
+                    upper: 1.0,
+                },
+                Some(false) => FinishBound {
+                    lower: 0.0,
+                    upper: 0.0,
+                },
+                None => FinishBound {
+                    lower: reached,
+                    upper: (reached + unexplored).min(1.0),
+                },
+            })
+            .collect()
+    }
+
+    fn record(&mut self) {
+        let probability = self.probability();
+        self.explored += probability;
+        if probability == 0.0 || self.settled.iter().all(Option::is_some) {
+            return;
+        }
+        let ranks = self.deltas.ranks(&self.team_slots, &mut self.scores);
+        for (i, rank) in ranks.into_iter().enumerate() {
+            if rank < self.places {
+                self.reached[i] += probability;
+            }
+        }
+    }
+
+    /// The model's chance of the current scenario. Walkovers are certain.
+    fn probability(&self) -> f64 {
+        self.undecided
+            .iter()
+            .zip(&self.choices)
+            .fold(1.0, |acc, (&slot, &side)| {
+                let slot = slot as usize;
+                match (self.team_slots[slot * 2], self.team_slots[slot * 2 + 1]) {
+                    (Some(a), Some(b)) => {
+                        let p = self.model.win_probability(a, b).clamp(0.0, 1.0);
+                        acc * if side == 0 { p } else { 1.0 - p }
+                    }
+                    _ => acc,
+                }
+            })
+    }
+
+    /// The sides game `k` of the undecided list can be won from, given the
+    /// teams currently filled in below it. Mirrors the walkover rule in
+    /// `FinishContext::fill`.
+    fn sides(&self, k: usize) -> [bool; 2] {
+        let slot = self.undecided[k] as usize;
+        let (decision_0, decision_1) = (self.team_slots[slot * 2], self.team_slots[slot * 2 + 1]);
+        [
+            decision_0.is_some() || decision_1.is_none(),
+            decision_1.is_some(),
+        ]
+    }
+
+    fn set_choice(&mut self, k: usize, side: u8) {
+        let slot = self.undecided[k] as usize;
+        self.choices[k] = side;
+        self.team_slots[slot] = self.team_slots[slot * 2 + side as usize];
+    }
+
+    /// Fills games `k..` with their first possible side.
+    fn fill_from(&mut self, k: usize) {
+        for k in k..self.undecided.len() {
+            let side = if self.sides(k)[0] { 0 } else { 1 };
+            self.set_choice(k, side);
+        }
+    }
+
+    /// Moves to the next scenario, or marks the job done after the last one.
+    fn advance(&mut self) {
+        for k in (0..self.undecided.len()).rev() {
+            if self.choices[k] == 0 && self.sides(k)[1] {
+                self.set_choice(k, 1);
+                self.fill_from(k + 1);
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn bounds_close_in_on_the_exact_chances() {
+        // The Elite Eight and on are left: 7 games.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0xFE));
+        let brackets: Vec<Bracket> = (1..9u64)
+            .map(|i| Bracket::from_decisions(i.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+            .collect();
+        let model = |a: u8, b: u8| if a < b { 0.7 } else { 0.3 };
+        let scoring = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+
+        // The exact chances, from every scenario with its weight.
+        let deltas = ScoreDeltaTable::for_view_with(&view, &brackets, &scoring);
+        let mut exact = vec![0.0; brackets.len()];
+        view.for_each_scenario(None, |team_slots| {
+            let probability = (1..8usize).fold(1.0, |acc, slot| {
+                let (a, b) = (
+                    team_slots[slot * 2].unwrap(),
+                    team_slots[slot * 2 + 1].unwrap(),
+                );
+                let p = model(a, b);
+                acc * if team_slots[slot] == Some(a) {
+                    p
+                } else {
+                    1.0 - p
+                }
+            });
+            let ranks = deltas.ranks(team_slots, &mut Vec::new());
+            for (i, rank) in ranks.into_iter().enumerate() {
+                if rank < 2 {
+                    exact[i] += probability;
+                }
+            }
+        });
+
+        let mut job = FinishBoundsJob::new(&brackets, &view, &scoring, 2, model);
+        let mut previous = job.bounds();
+        loop {
+            let done = job.step(10);
+            let bounds = job.bounds();
+            for ((bound, before), chance) in bounds.iter().zip(&previous).zip(&exact) {
+                assert!(bound.lower >= before.lower - 1e-12);
+                assert!(bound.upper <= before.upper + 1e-12);
+                assert!(bound.lower <= chance + 1e-12 && *chance <= bound.upper + 1e-12);
+            }
+            previous = bounds;
+            if done {
+                break;
+            }
+        }
+        assert_eq!(job.scenarios_done(), 128);
+        for (bound, chance) in previous.iter().zip(&exact) {
+            assert!((bound.lower - chance).abs() < 1e-12);
+            assert!((bound.upper - chance).abs() < 1e-12);
+        }
+    }
+}
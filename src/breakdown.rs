@@ -0,0 +1,113 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Score, Tournament};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickResult {
+    Correct,
+    /// Someone else won, or the game was vacated.
+    Incorrect,
+    /// The game hasn't been decided.
+    Pending,
+}
+
+/// One pick and what it earned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PickScore {
+    pub slot: u8,
+    pub team: u8,
+    pub result: PickResult,
+    pub points: Score,
+}
+
+/// A bracket's score, by round and by pick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    /// Indexed by round number (1..=6).
+    pub rounds: [Score; 7],
+    /// Every pick, round by round and in slot order within a round.
+    pub picks: Vec<PickScore>,
+}
+
+impl ScoreBreakdown {
+    /// The same total [`Bracket::score_with`] gives.
+    pub fn total(&self) -> Score {
+        self.rounds.iter().fold(0, |acc, &p| acc.saturating_add(p))
+    }
+}
+
+impl Bracket {
+    /// Where the bracket's points under `scoring` came from, for
+    /// leaderboards showing points per round and audits of single picks.
+    pub fn score_breakdown(
+        &self,
+        tournament: &Tournament,
+        scoring: &ScoringSystem,
+    ) -> ScoreBreakdown {
+        let view = TournamentView::new(tournament);
+        let winners = view.team_slots();
+        let bracket_team_slots = self.decision_team_slots();
+        let mut rounds: [Score; 7] = [0; 7];
+        let mut picks = vec![];
+
+        for round in 1..=6u8 {
+            let first = 1u8 << (6 - round);
+            for slot in first..first * 2 {
+                let Some(team) = bracket_team_slots[slot as usize] else {
+                    continue;
+                };
+                let (result, points) = if !view.is_decided(slot) {
+                    (PickResult::Pending, 0)
+                } else if winners[slot as usize] == Some(team) {
+                    (PickResult::Correct, scoring.points_in(slot, team, winners))
+                } else {
+                    (PickResult::Incorrect, 0)
+                };
+                rounds[round as usize] = rounds[round as usize].saturating_add(points);
+                picks.push(PickScore {
+                    slot,
+                    team,
+                    result,
+                    points,
+                });
+            }
+        }
+        ScoreBreakdown { rounds, picks }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::points_for;
+    use crate::testing::{random_brackets, random_through};
+
+    #[test]
+    fn adds_up_to_the_score() {
+        let tournament = random_through(4, 3);
+        let scoring = ScoringSystem::standard();
+        for bracket in random_brackets(8, 5) {
+            let breakdown = bracket.score_breakdown(&tournament, &scoring);
+            assert_eq!(breakdown.total(), bracket.score_with(&tournament, &scoring));
+            assert_eq!(breakdown.picks.len(), 63);
+            assert_eq!(
+                breakdown.rounds,
+                TournamentView::new(&tournament).round_points(&bracket)
+            );
+        }
+
+        let chalk = Bracket::from_decisions(0);
+        let breakdown = chalk.score_breakdown(&Tournament::new(0, 1 << 32), &scoring);
+        assert_eq!(
+            breakdown.picks[0],
+            PickScore {
+                slot: 32,
+                team: 64,
+                result: PickResult::Correct,
+                points: points_for(1, 1)
+            }
+        );
+        assert_eq!(breakdown.picks[1].result, PickResult::Pending);
+        assert_eq!(breakdown.picks[62].slot, 1);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use crate::view::TournamentView;
+use crate::{points_for_pick, Bracket, Decisions, Score};
+
+/// First-round games that are byes, as bits of their game slots. The team in
+/// the even slot (`slot * 2`) advances without playing and the odd slot has
+/// no team. A bye is laid out as a decided game won from side 0, so
+/// enumeration never branches on it, and it's worth no points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Byes {
+    games: u64,
+}
+
+/// `slot` isn't a first-round game (32..=63), so it can't be a bye.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotFirstRoundGame {
+    pub slot: u8,
+}
+
+impl fmt::Display for NotFirstRoundGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "game {} isn't in the first round", self.slot)
+    }
+}
+
+impl std::error::Error for NotFirstRoundGame {}
+
+impl Byes {
+    pub fn new(slots: &[u8]) -> Result<Byes, NotFirstRoundGame> {
+        let mut games = 0;
+        for &slot in slots {
+            if !(32..=63).contains(&slot) {
+                return Err(NotFirstRoundGame { slot });
+            }
+            games |= 1 << slot;
+        }
+        Ok(Byes { games })
+    }
+
+    /// The byes as bits of their game slots.
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    pub fn is_bye(&self, slot: u8) -> bool {
+        slot < 64 && self.games & (1 << slot) != 0
+    }
+
+    /// Decides every bye for the team that has it. Call this on the
+    /// tournament before building a view, and on brackets before scoring, so
+    /// every entry "picks" the bye the same way.
+    pub fn apply<D: Decisions + ?Sized>(&self, decisions: &mut D) {
+        for slot in 32..=63u8 {
+            if self.is_bye(slot) {
+                decisions.set_decision(slot, 0);
+            }
+        }
+    }
+
+    /// `bracket`'s score without the byes. Every entry gets the same points
+    /// for them, so leaving them in changes no standings, only totals.
+    pub fn score(&self, view: &TournamentView, bracket: &Bracket) -> Score {
+        let picks = bracket.decision_team_slots();
+        let bye_points = (32..=63u8)
+            .filter(|&slot| self.is_bye(slot))
+            .filter(|&slot| {
+                picks[slot as usize].is_some_and(|t| view.team_slots()[slot as usize] == Some(t))
+            })
+            .map(|slot| points_for_pick(slot, slot * 2))
+            .fold(0, Score::saturating_add);
+        view.score(bracket).saturating_sub(bye_points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tournament;
+
+    #[test]
+    fn byes_are_decided_and_scoreless() {
+        let byes = Byes::new(&[32, 63]).unwrap();
+        assert_eq!(Byes::new(&[31]), Err(NotFirstRoundGame { slot: 31 }));
+
+        let mut tournament = Tournament {
+            decisions: 0,
+            mask: 0,
+            vacated: 0,
+        };
+        byes.apply(&mut tournament);
+        assert_eq!(tournament.mask(), byes.games());
+        let view = TournamentView::new(&tournament);
+        assert!(!view.undecided().contains(&32));
+        assert_eq!(view.team_slots()[32], Some(64));
+
+        // A bracket picking the empty side has it corrected.
+        let mut bracket = Bracket { decisions: 1 << 63 };
+        byes.apply(&mut bracket);
+        assert_eq!(bracket.decisions & (1 << 63), 0);
+        assert_eq!(byes.score(&view, &bracket), 0);
+        assert!(view.score(&bracket) > 0);
+    }
+}
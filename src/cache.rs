@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+/// Storage for expensive derived results, keyed by
+/// [`crate::Tournament::cache_key`] (or anything else that changes whenever
+/// the result would).
+pub trait ResultCache<V> {
+    fn get(&mut self, key: u64) -> Option<V>;
+
+    fn put(&mut self, key: u64, value: V);
+
+    /// The cached value for `key`, computing and storing it on a miss.
+    fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: u64, compute: F) -> V
+    where
+        V: Clone,
+    {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+        let value = compute();
+        self.put(key, value.clone());
+        value
+    }
+}
+
+/// An in-memory cache holding at most `capacity` results, dropping the least
+/// recently used one to make room.
+#[derive(Debug, Clone)]
+pub struct LruCache<V> {
+    capacity: usize,
+    // Each value with the tick it was last used at.
+    entries: HashMap<u64, (V, u64)>,
+    tick: u64,
+}
+
+impl<V> LruCache<V> {
+    pub fn new(capacity: usize) -> LruCache<V> {
+        LruCache {
+            capacity,
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<V: Clone> ResultCache<V> for LruCache<V> {
+    fn get(&mut self, key: u64) -> Option<V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(&key)?;
+        *used = self.tick;
+        Some(value.clone())
+    }
+
+    fn put(&mut self, key: u64, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            // A scan is fine at the sizes a pool server keeps around.
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(&key, _)| key)
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (value, self.tick));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_the_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.get(1), Some("one"));
+        cache.put(3, "three");
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(2), None);
+        assert_eq!(cache.get(1), Some("one"));
+
+        let mut computed = 0;
+        let mut compute = || {
+            computed += 1;
+            "three again"
+        };
+        assert_eq!(cache.get_or_insert_with(3, &mut compute), "three");
+        assert_eq!(cache.get_or_insert_with(4, &mut compute), "three again");
+        assert_eq!(computed, 1);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::Tournament;
+
+/// Games whose favorite the model gave between `low` and `high`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationBucket {
+    pub low: f64,
+    pub high: f64,
+    pub games: usize,
+    /// The favorites' mean predicted chance.
+    pub predicted: f64,
+    /// The share of them that won.
+    pub actual: f64,
+}
+
+/// How well a model's chances matched what happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// From the closest calls (favorites near 0.5) to the surest.
+    pub buckets: Vec<CalibrationBucket>,
+    /// Mean squared error of the chances given, from 0.0 (always certain
+    /// and right) up; always saying 0.5 scores 0.25.
+    pub brier: f64,
+    pub games: usize,
+}
+
+/// Scores `model` on every decided game of `tournaments` that two teams
+/// played, walkovers left out. Each game counts once, from the side the
+/// model favored, in one of `buckets` equal slices of 0.5..=1.0.
+pub fn calibrate<M: WinProbability + ?Sized>(
+    model: &M,
+    tournaments: &[Tournament],
+    buckets: usize,
+) -> Calibration {
+    let buckets = buckets.max(1);
+    let width = 0.5 / buckets as f64;
+    // Per bucket: games, summed predictions and favorites that won.
+    let mut totals = vec![(0usize, 0.0, 0usize); buckets];
+    let mut squared_error = 0.0;
+    let mut games = 0;
+
+    for tournament in tournaments {
+        let view = TournamentView::new(tournament);
+        let winners = view.team_slots();
+        for slot in (1..64usize).filter(|&slot| view.is_decided(slot as u8)) {
+            let (Some(a), Some(b), Some(winner)) =
+                (winners[slot * 2], winners[slot * 2 + 1], winners[slot])
+            else {
+                continue;
+            };
+            let p = model.win_probability(a, b);
+            let a_won = winner == a;
+            squared_error += (p - f64::from(u8::from(a_won))).powi(2);
+            games += 1;
+
+            let (favorite, favorite_won) = if p >= 0.5 {
+                (p, a_won)
+            } else {
+                (1.0 - p, !a_won)
+            };
+            let bucket = (((favorite - 0.5) / width) as usize).min(buckets - 1);
+            let (count, predicted, won) = &mut totals[bucket];
+            *count += 1;
+            *predicted += favorite;
+            *won += favorite_won as usize;
+        }
+    }
+
+    let buckets = totals
+        .into_iter()
+        .enumerate()
+        .map(|(i, (count, predicted, won))| {
+            let mean = |total: f64| {
+                if count == 0 {
+                    0.0
+                } else {
+                    total / count as f64
+                }
+            };
+            CalibrationBucket {
+                low: 0.5 + i as f64 * width,
+                high: 0.5 + (i + 1) as f64 * width,
+                games: count,
+                predicted: mean(predicted),
+                actual: mean(won as f64),
+            }
+        })
+        .collect();
+    Calibration {
+        buckets,
+        brier: if games == 0 {
+            0.0
+        } else {
+            squared_error / games as f64
+        },
+        games,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{seed_for_slot, COMPLETE_MASK};
+
+    #[test]
+    fn buckets_favorites_and_scores_the_model() {
+        // Chalk all the way and its mirror, where the other side always won.
+        let tournaments = [
+            Tournament::new(0, COMPLETE_MASK),
+            Tournament::new(COMPLETE_MASK, COMPLETE_MASK),
+        ];
+        let coin_flip = calibrate(&|_, _| 0.5, &tournaments, 5);
+        assert_eq!(coin_flip.games, 126);
+        assert_eq!(coin_flip.brier, 0.25);
+        assert_eq!(coin_flip.buckets[0].games, 126);
+        assert_eq!(coin_flip.buckets[0].actual, 0.5);
+
+        // Certain the better seed wins, but the top team won every game
+        // whatever its seed, so the sure bucket is off.
+        let seeds = |a: u8, b: u8| match seed_for_slot(a).cmp(&seed_for_slot(b)) {
+            std::cmp::Ordering::Less => 1.0,
+            std::cmp::Ordering::Greater => 0.0,
+            std::cmp::Ordering::Equal => 0.5,
+        };
+        let report = calibrate(&seeds, &tournaments[..1], 5);
+        let sure = &report.buckets[4];
+        assert_eq!(sure.predicted, 1.0);
+        assert!(sure.actual < 1.0);
+        assert!(report.brier > 0.0);
+        assert_eq!((sure.low, sure.high), (0.9, 1.0));
+    }
+}
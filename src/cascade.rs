@@ -0,0 +1,111 @@
+use crate::partial::PartialBracket;
+use crate::{Bracket, Decisions, COMPLETE_MASK};
+
+/// What happens to later-round picks of the team a changed pick replaces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Cascade {
+    /// The new team takes over every later pick the old team had.
+    Repoint,
+    /// Later picks of the old team are withdrawn, leaving them missing.
+    Invalidate,
+}
+
+/// A bracket after a pick change, and the later-round slots whose pick was
+/// re-pointed or withdrawn because of it (latest round last).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickChange {
+    pub picks: PartialBracket,
+    pub cascaded: Vec<u8>,
+}
+
+impl Bracket {
+    /// Picks team `winner` (64..=127) to win the game in `slot`, also picking
+    /// it through every earlier game on its way there. Returns `None` when
+    /// `winner` doesn't play in that part of the bracket. Use
+    /// [`PartialBracket::to_bracket`] on the result of a [`Cascade::Repoint`],
+    /// which always stays complete.
+    pub fn with_pick_changed(&self, slot: u8, winner: u8, cascade: Cascade) -> Option<PickChange> {
+        if !(1..=63).contains(&slot) || winner < 64 {
+            return None;
+        }
+        let depth = (1..=6).find(|&k| winner >> k == slot)?;
+
+        let old_team_slots = self.decision_team_slots();
+        let old_team = old_team_slots[slot as usize];
+
+        let mut picks = PartialBracket {
+            decisions: self.decisions,
+            mask: COMPLETE_MASK,
+        };
+        for k in 1..=depth {
+            let game = winner >> k;
+            let from = winner >> (k - 1);
+            picks.set_decision(game, from - game * 2);
+        }
+
+        let mut cascaded = vec![];
+        let mut game = slot / 2;
+        while game >= 1 && old_team_slots[game as usize] == old_team && old_team != Some(winner) {
+            cascaded.push(game);
+            game /= 2;
+        }
+        if cascade == Cascade::Invalidate {
+            for &game in &cascaded {
+                picks.mask &= !(1u64 << game);
+                picks.decisions &= !(1u64 << game);
+            }
+        }
+
+        Some(PickChange { picks, cascaded })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The all-zero bracket picks team 64 to win it all.
+    const CHALK: Bracket = Bracket { decisions: 0 };
+
+    #[test]
+    fn repoint_carries_the_new_team_forward() {
+        // Team 65 takes 64's place in its first-round game.
+        let change = CHALK.with_pick_changed(32, 65, Cascade::Repoint).unwrap();
+        let bracket = change.picks.to_bracket().unwrap();
+
+        assert_eq!(change.cascaded, vec![16, 8, 4, 2, 1]);
+        assert_eq!(bracket.decision_team_slots()[1], Some(65));
+        assert_eq!(bracket.decision(32), Some(1));
+    }
+
+    #[test]
+    fn picking_a_team_deep_sets_its_whole_path() {
+        // Team 127 is at the bottom of the bracket, under slot 3.
+        let change = CHALK
+            .with_pick_changed(3, 127, Cascade::Invalidate)
+            .unwrap();
+
+        assert!(change.cascaded.is_empty());
+        let bracket = change.picks.to_bracket().unwrap();
+        let team_slots = bracket.decision_team_slots();
+        for game in [63, 31, 15, 7, 3] {
+            assert_eq!(team_slots[game], Some(127));
+        }
+        assert_eq!(team_slots[1], Some(64));
+    }
+
+    #[test]
+    fn invalidate_withdraws_later_picks() {
+        let change = CHALK.with_pick_changed(4, 72, Cascade::Invalidate).unwrap();
+
+        assert_eq!(change.cascaded, vec![2, 1]);
+        assert_eq!(change.picks.missing(), vec![2, 1]);
+        assert_eq!(change.picks.decision_team_slots()[4], Some(72));
+    }
+
+    #[test]
+    fn rejects_teams_outside_the_game() {
+        assert_eq!(CHALK.with_pick_changed(3, 64, Cascade::Repoint), None);
+        assert_eq!(CHALK.with_pick_changed(32, 66, Cascade::Repoint), None);
+    }
+}
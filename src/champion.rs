@@ -0,0 +1,133 @@
+use crate::paths::PathsToVictory;
+use crate::rng::Rng;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::{Bracket, Score};
+
+/// The pool if one team wins it all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChampionRow {
+    pub champion: u8,
+    /// The share of samples the team won the title in.
+    pub chance: f64,
+    /// Per bracket, the chance of finishing first (alone or tied) in those
+    /// samples.
+    pub wins: Vec<f64>,
+}
+
+impl TournamentView {
+    /// "If 64 wins it all, Dave wins the pool": for every team that takes
+    /// the title in at least one of `samples` play-outs drawn from `model`,
+    /// each bracket's chance of finishing first with that champion. Most
+    /// likely champion first; the same `seed` gives the same table.
+    pub fn champion_table<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+        samples: u64,
+        seed: u64,
+        scoring: &ScoringSystem,
+    ) -> Vec<ChampionRow> {
+        let deltas = ScoreDeltaTable::for_view_with(self, brackets, scoring);
+        let mut rng = Rng::new(seed);
+        let mut team_slots = self.team_slots().to_vec();
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+        // Per team slot: samples it was champion in, and first places per
+        // bracket in them.
+        let mut titles = [0u64; 64];
+        let mut firsts = vec![vec![0u64; brackets.len()]; 64];
+        for _ in 0..samples {
+            self.play_out(model, &mut rng, &mut team_slots);
+            // A vacated title game crowns no one.
+            let Some(champion) = team_slots[1] else {
+                continue;
+            };
+            let team = champion as usize - 64;
+            titles[team] += 1;
+            let ranks = deltas.ranks(&team_slots, &mut scores);
+            for (count, rank) in firsts[team].iter_mut().zip(ranks) {
+                *count += (rank == 0) as u64;
+            }
+        }
+
+        let mut table: Vec<ChampionRow> = titles
+            .iter()
+            .zip(firsts)
+            .enumerate()
+            .filter(|(_, (&won, _))| won > 0)
+            .map(|(team, (&won, counts))| ChampionRow {
+                champion: 64 + team as u8,
+                chance: PathsToVictory::share(won, samples),
+                wins: counts
+                    .into_iter()
+                    .map(|c| PathsToVictory::share(c, won))
+                    .collect(),
+            })
+            .collect();
+        table.sort_by(|a, b| b.chance.total_cmp(&a.chance));
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn conditions_the_pool_on_each_champion() {
+        // The Final Four is left: 64 v 80 in slot 2, 96 v 112 in slot 3.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        // Chalk, chalk but 96 for the title, and 80 over 64 to win it all.
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+        // The lower team slot wins seven times in ten.
+        let model = |a: u8, b: u8| if a < b { 0.7 } else { 0.3 };
+        let scoring = ScoringSystem::standard();
+        let table = view.champion_table(&brackets, &model, 40_000, 3, &scoring);
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[0].champion, 64);
+        assert!((table.iter().map(|r| r.chance).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(
+            table,
+            view.champion_table(&brackets, &model, 40_000, 3, &scoring)
+        );
+
+        // The exact table, from every scenario with its weight.
+        let deltas = ScoreDeltaTable::for_view_with(&view, &brackets, &scoring);
+        let mut weight = [0.0; 64];
+        let mut firsts = [[0.0; 3]; 64];
+        view.for_each_scenario(None, |team_slots| {
+            let probability = (1..4usize).fold(1.0, |acc, slot| {
+                let a = team_slots[slot * 2].unwrap();
+                let b = team_slots[slot * 2 + 1].unwrap();
+                acc * if team_slots[slot] == Some(a) {
+                    model(a, b)
+                } else {
+                    1.0 - model(a, b)
+                }
+            });
+            let team = team_slots[1].unwrap() as usize - 64;
+            weight[team] += probability;
+            let ranks = deltas.ranks(team_slots, &mut Vec::new());
+            for (first, rank) in firsts[team].iter_mut().zip(ranks) {
+                *first += probability * (rank == 0) as u8 as f64;
+            }
+        });
+        for row in &table {
+            let team = row.champion as usize - 64;
+            assert!((row.chance - weight[team]).abs() < 0.01);
+            for (&chance, &first) in row.wins.iter().zip(&firsts[team]) {
+                assert!((chance - first / weight[team]).abs() < 0.02);
+            }
+        }
+        // With 80 champion only the third entry called it.
+        let team_80 = table.iter().find(|r| r.champion == 80).unwrap();
+        assert_eq!(team_80.wins, vec![0.0, 0.0, 1.0]);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{BestFinishes, Bracket, FinishContext};
+
+/// A best-finish enumeration that runs a bounded number of scenarios per
+/// call, so it can be driven from an event loop (a browser's, in the WASM
+/// build) without blocking it for the whole computation.
+///
+/// The job scores every scenario on its own, in the order of
+/// [`TournamentView::for_each_scenario`], where
+/// [`BestFinishes::for_view_with`] merges region outcomes that score the
+/// same; it does more work in total but finds the same finishes.
+#[derive(Debug)]
+pub struct BestFinishesJob {
+    brackets: Vec<Bracket>,
+    deltas: ScoreDeltaTable,
+    context: FinishContext,
+    team_slots: Vec<Option<u8>>,
+    undecided: Vec<u8>,
+    // Side chosen for each undecided game of the current scenario.
+    choices: Vec<u8>,
+    scenarios_done: u64,
+    done: bool,
+    best_finishes: BestFinishes,
+}
+
+impl BestFinishesJob {
+    /// A job for each bracket's best finish in the top `places` under
+    /// `scoring`.
+    pub fn new(
+        brackets: &[Bracket],
+        view: &TournamentView,
+        scoring: &ScoringSystem,
+        places: usize,
+    ) -> BestFinishesJob {
+        let mut job = BestFinishesJob {
+            brackets: brackets.to_vec(),
+            deltas: ScoreDeltaTable::for_view_with(view, brackets, scoring),
+            context: FinishContext::new(),
+            team_slots: view.team_slots().to_vec(),
+            undecided: view.undecided().to_vec(),
+            choices: vec![0; view.undecided().len()],
+            scenarios_done: 0,
+            done: false,
+            best_finishes: BestFinishes::new(places),
+        };
+        job.fill_from(0);
+        job
+    }
+
+    /// Evaluates up to `max_scenarios` more scenarios. Returns true once every
+    /// scenario has been evaluated.
+    pub fn step(&mut self, max_scenarios: u64) -> bool {
+        let mut budget = max_scenarios;
+        while !self.done && budget > 0 {
+            self.context.record_leaf(
+                &self.brackets,
+                &self.deltas,
+                &self.team_slots,
+                &mut self.best_finishes,
+            );
+            self.scenarios_done += 1;
+            budget -= 1;
+            self.advance();
+        }
+        self.done
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn scenarios_done(&self) -> u64 {
+        self.scenarios_done
+    }
+
+    /// The best finishes over the scenarios evaluated so far; final once
+    /// [`BestFinishesJob::is_done`].
+    pub fn best_finishes(&self) -> &BestFinishes {
+        &self.best_finishes
+    }
+
+    pub fn into_best_finishes(self) -> BestFinishes {
+        self.best_finishes
+    }
+
+    /// The sides game `k` of the undecided list can be won from, given the
+    /// teams currently filled in below it. Mirrors the walkover rule in
+    /// `FinishContext::fill`.
+    fn sides(&self, k: usize) -> [bool; 2] {
+        let slot = self.undecided[k] as usize;
+        let (decision_0, decision_1) = (self.team_slots[slot * 2], self.team_slots[slot * 2 + 1]);
+        [
+            decision_0.is_some() || decision_1.is_none(),
+            decision_1.is_some(),
+        ]
+    }
+
+    fn set_choice(&mut self, k: usize, side: u8) {
+        let slot = self.undecided[k] as usize;
+        self.choices[k] = side;
+        self.team_slots[slot] = self.team_slots[slot * 2 + side as usize];
+    }
+
+    /// Fills games `k..` with their first possible side.
+    fn fill_from(&mut self, k: usize) {
+        for k in k..self.undecided.len() {
+            let side = if self.sides(k)[0] { 0 } else { 1 };
+            self.set_choice(k, side);
+        }
+    }
+
+    /// Moves to the next scenario, or marks the job done after the last one.
+    fn advance(&mut self) {
+        for k in (0..self.undecided.len()).rev() {
+            if self.choices[k] == 0 && self.sides(k)[1] {
+                self.set_choice(k, 1);
+                self.fill_from(k + 1);
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn chunks_add_up_to_the_full_enumeration() {
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0xFE,
+            vacated: 1 << 12,
+        });
+        let brackets: Vec<Bracket> = (1..9u64)
+            .map(|i| Bracket {
+                decisions: i.wrapping_mul(0x9E37_79B9_7F4A_7C15) & COMPLETE_MASK,
+            })
+            .collect();
+
+        let scoring = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+
+        let mut job = BestFinishesJob::new(&brackets, &view, &scoring, 3);
+        let mut calls = 0;
+        while !job.step(10) {
+            calls += 1;
+        }
+
+        let mut expected = 0;
+        view.for_each_scenario(None, |_| expected += 1);
+        assert_eq!(job.scenarios_done(), expected);
+        assert!(calls > 1);
+        assert_eq!(
+            job.into_best_finishes().possible_finishes,
+            BestFinishes::for_view_with(&brackets, &view, &scoring, 3).possible_finishes
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! The `madness` command: standings for a pool kept as plain files.
+//!
+//! ```text
+//! madness <field> <results> <entries> [--paid N]
+//! ```
+//!
+//! The field file lists the teams in bracket order, sixteen to a region: a
+//! line naming the region, then "seed name" lines. The results file lists
+//! the winners so far as [`Field::parse_results`] reads them. Each file in
+//! the entries directory is one bracket, either printable or a bracket code,
+//! and its name without the extension is the owner's. Results and entries
+//! can name teams by the usual short names, such as "UConn", through
+//! [`crate::names::AliasTable::standard`].
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::MadnessError;
+use crate::field::{Field, FieldBuilder, FieldError};
+use crate::pool::Pool;
+use crate::scoring::ScoringSystem;
+use crate::side::{RoundScore, SideContest, UpsetsCalled};
+use crate::status::{PoolAnalysis, Status};
+use crate::view::TournamentView;
+use crate::{ordinal, Bracket, DEFAULT_PLACES, ENUMERATION_LIMIT};
+
+const USAGE: &str = "usage: madness <field> <results> <entries> [--paid N]";
+const DEFAULT_PAID: usize = 3;
+// Past `ENUMERATION_LIMIT` scenarios best finishes are searched for
+// within this, and statuses aren't shown.
+const SEARCH_BUDGET: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage,
+    Io { path: PathBuf, error: io::Error },
+    Input { path: PathBuf, error: MadnessError },
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage => write!(f, "{}", USAGE),
+            CliError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            CliError::Input { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for CliError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CliError::Usage => None,
+            CliError::Io { error, .. } => Some(error),
+            CliError::Input { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Runs the command on `args` (without the program name), returning the
+/// report to print.
+pub fn run(args: &[String]) -> Result<String, CliError> {
+    let mut paths = vec![];
+    let mut paid = DEFAULT_PAID;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--paid" {
+            paid = args
+                .next()
+                .and_then(|n| n.parse().ok())
+                .ok_or(CliError::Usage)?;
+        } else {
+            paths.push(Path::new(arg));
+        }
+    }
+    let [field_path, results_path, entries_path] = paths[..] else {
+        return Err(CliError::Usage);
+    };
+
+    let field = parse_field(&read(field_path)?).map_err(|e| input(field_path, e))?;
+    let tournament = field
+        .parse_results(&read(results_path)?)
+        .map_err(|e| input(results_path, e))?;
+    let mut pool = Pool::new(tournament, ScoringSystem::standard());
+    for (owner, path) in entry_files(entries_path)? {
+        let bracket = parse_entry(&field, &read(&path)?).map_err(|e| input(&path, e))?;
+        pool.insert(&owner, bracket);
+    }
+    Ok(report(&pool, paid))
+}
+
+/// Reads a field file: a region's name on a line of its own, then its
+/// sixteen teams as "seed name". Blank lines and lines starting with `#`
+/// are skipped.
+pub fn parse_field(text: &str) -> Result<Field, FieldError> {
+    let mut builder = FieldBuilder::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let team = line
+            .split_once(char::is_whitespace)
+            .and_then(|(seed, name)| Some((seed.parse().ok()?, name.trim())));
+        match team {
+            Some((seed, name)) => builder.push(name, seed),
+            None => builder.region(line),
+        }
+    }
+    builder.build()
+}
+
+/// A bracket code or a printable bracket.
+fn parse_entry(field: &Field, text: &str) -> Result<Bracket, MadnessError> {
+    let text = text.trim();
+    if !text.contains(char::is_whitespace) {
+        return Ok(Bracket::from_code(text)?);
+    }
+    Ok(field.parse_printable(text)?)
+}
+
+/// `(owner, path)` for each file in `dir`, by file name.
+fn entry_files(dir: &Path) -> Result<Vec<(String, PathBuf)>, CliError> {
+    let io_error = |error| CliError::Io {
+        path: dir.to_path_buf(),
+        error,
+    };
+    let mut files = vec![];
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(owner) = path.file_stem().and_then(|s| s.to_str()) {
+            files.push((owner.to_string(), path.clone()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn report(pool: &Pool, paid: usize) -> String {
+    let width = pool
+        .entries()
+        .iter()
+        .map(|e| e.owner.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut text = String::from("Leaderboard\n");
+    for standing in pool.leaderboard() {
+        text.push_str(&format!(
+            "{:>3}. {:<width$}  {:>4}  (max {})\n",
+            standing.rank + 1,
+            standing.owner,
+            standing.score,
+            standing.max_possible,
+        ));
+    }
+
+    text.push_str("\nSide contests\n");
+    let first_round = RoundScore::new(1).expect("round 1 is a round");
+    let contests: [&dyn SideContest; 2] = [&UpsetsCalled, &first_round];
+    for contest in contests {
+        let standings = pool.side_standings(contest);
+        let leaders: Vec<&str> = standings
+            .iter()
+            .take_while(|s| s.rank == 0)
+            .map(|s| s.owner.as_str())
+            .collect();
+        if let Some(best) = standings.first() {
+            text.push_str(&format!(
+                "  {}: {} ({})\n",
+                contest.name(),
+                leaders.join(", "),
+                best.mark
+            ));
+        }
+    }
+
+    text.push_str("\nBest finishes\n");
+    let enumerable = pool.tournament().is_enumerable(ENUMERATION_LIMIT);
+    let finishes: Vec<(&str, Option<String>)> =
+        if let Some(finishes) = pool.best_finishes(DEFAULT_PLACES) {
+            finishes
+                .into_iter()
+                .map(|(owner, finish)| (owner, finish.map(ordinal)))
+                .collect()
+        } else {
+            let brackets: Vec<Bracket> = pool.active_entries().iter().map(|e| e.bracket).collect();
+            let view = TournamentView::new(pool.tournament());
+            let found = view.best_finishes_within(&brackets, DEFAULT_PLACES, SEARCH_BUDGET, 0);
+            pool.active_entries()
+                .iter()
+                .map(|e| {
+                    let finish = found.finishes.get(&e.bracket).map(|f| {
+                        let place = ordinal(f.rank);
+                        if f.exact {
+                            place
+                        } else {
+                            format!("{} or better", place)
+                        }
+                    });
+                    (e.owner.as_str(), finish)
+                })
+                .collect()
+        };
+    for (owner, finish) in finishes {
+        let finish = finish.unwrap_or_else(|| format!("out of the top {}", DEFAULT_PLACES));
+        text.push_str(&format!("  {:<width$}  {}\n", owner, finish));
+    }
+
+    text.push_str(&format!("\nStatus (top {} paid)\n", paid));
+    if !enumerable {
+        text.push_str("  too many games left to tell\n");
+        return text;
+    }
+    let brackets: Vec<Bracket> = pool.active_entries().iter().map(|e| e.bracket).collect();
+    let analysis = PoolAnalysis::new(
+        &TournamentView::new(pool.tournament()),
+        &brackets,
+        pool.scoring(),
+        paid,
+    );
+    for entry in pool.active_entries() {
+        let status = match analysis.status(&entry.bracket) {
+            Some(Status::Clinched(place)) => format!("clinched {} or better", ordinal(place)),
+            Some(Status::Alive) => "alive".to_string(),
+            Some(Status::Eliminated) | None => "eliminated".to_string(),
+        };
+        text.push_str(&format!("  {:<width$}  {}\n", entry.owner, status));
+    }
+    text
+}
+
+fn read(path: &Path) -> Result<String, CliError> {
+    fs::read_to_string(path).map_err(|error| CliError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn input<E: Into<MadnessError>>(path: &Path, error: E) -> CliError {
+    CliError::Input {
+        path: path.to_path_buf(),
+        error: error.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::chalk_bracket;
+    use crate::{Decisions, SEED_ORDER};
+
+    fn field_text() -> String {
+        let mut text = String::from("# 2021 field\n");
+        for region in ["East", "West", "South", "Midwest"] {
+            text.push_str(&format!("\n{}\n", region));
+            for seed in SEED_ORDER {
+                text.push_str(&format!("{} {} {}\n", seed, region, seed));
+            }
+        }
+        text.replace("1 East 1\n", "1 Connecticut\n")
+    }
+
+    #[test]
+    fn reports_a_pool_from_files() {
+        let dir = std::env::temp_dir().join(format!("madness-cli-{}", std::process::id()));
+        let entries = dir.join("entries");
+        fs::create_dir_all(&entries).unwrap();
+
+        let field = parse_field(&field_text()).unwrap();
+        assert_eq!(field.team(127).name, "Midwest 15");
+        // Chalk through the Final Four: Connecticut against South 1 for the
+        // title.
+        let chalk = field.printable(&chalk_bracket());
+        let results: Vec<&str> = chalk.lines().collect();
+        fs::write(dir.join("field.txt"), field_text()).unwrap();
+        fs::write(
+            dir.join("results.txt"),
+            results[..results.len() - 2].join("\n"),
+        )
+        .unwrap();
+        // Entries can use a short name for the East's top seed.
+        fs::write(
+            entries.join("dave.txt"),
+            chalk.replace("Connecticut", "UConn"),
+        )
+        .unwrap();
+        let mut underdog = chalk_bracket();
+        underdog.set_decision(1, 1 - underdog.decision(1).unwrap());
+        fs::write(entries.join("sarah.code"), underdog.to_code()).unwrap();
+
+        let args: Vec<String> = ["field.txt", "results.txt", "entries"]
+            .iter()
+            .map(|p| dir.join(p).display().to_string())
+            .chain(["--paid".to_string(), "1".to_string()])
+            .collect();
+        let report = run(&args).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(report.starts_with("Leaderboard\n  1. dave "));
+        // Tied until the title game, which each of them picked differently.
+        assert!(report.contains("  1. sarah "));
+        assert!(report.contains("Side contests\n  Upsets called: dave, sarah (0)\n"));
+        assert!(report.contains("Best finishes\n  dave   1st\n  sarah  1st\n"));
+        assert!(report.ends_with("Status (top 1 paid)\n  dave   alive\n  sarah  alive\n"));
+
+        assert!(matches!(run(&args[..2]), Err(CliError::Usage)));
+    }
+
+    #[test]
+    fn numbers_places() {
+        let places: Vec<String> = [0, 1, 2, 3, 10, 11, 20, 111].map(ordinal).to_vec();
+        assert_eq!(
+            places,
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "21st", "112th"]
+        );
+    }
+}
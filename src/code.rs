@@ -0,0 +1,128 @@
+use std::fmt;
+
+use crate::provenance::fnv1a;
+use crate::{Bracket, Decisions};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+// 8 bytes of decisions and a check byte, at 6 bits a character.
+const CODE_LEN: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeError {
+    /// Codes are 12 characters.
+    Length(usize),
+    /// Not a URL-safe base64 character.
+    Character(char),
+    /// The check byte doesn't match, so the code was mistyped or cut.
+    Checksum,
+    /// Bit 0 is reserved and never set in a code.
+    ReservedBit,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::Length(len) => {
+                write!(f, "code is {} characters, not {}", len, CODE_LEN)
+            }
+            CodeError::Character(c) => write!(f, "{:?} can't appear in a code", c),
+            CodeError::Checksum => write!(f, "code doesn't check out; it may be mistyped"),
+            CodeError::ReservedBit => write!(f, "code sets the reserved bit"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+fn check_byte(decisions: u64) -> u8 {
+    fnv1a(&[decisions]) as u8
+}
+
+impl Bracket {
+    /// A short code for the bracket that's safe in URLs and chat messages:
+    /// URL-safe base64 of the decision bits and a check byte.
+    pub fn to_code(&self) -> String {
+        let mut bytes = self.decisions().to_be_bytes().to_vec();
+        bytes.push(check_byte(self.decisions()));
+        bytes
+            .chunks(3)
+            .flat_map(|chunk| {
+                let group = (chunk[0] as u32) << 16 | (chunk[1] as u32) << 8 | chunk[2] as u32;
+                (0..4)
+                    .rev()
+                    .map(move |i| ALPHABET[(group >> (i * 6)) as usize & 63] as char)
+            })
+            .collect()
+    }
+
+    /// Reads a code made by [`Bracket::to_code`], ignoring surrounding
+    /// whitespace.
+    pub fn from_code(code: &str) -> Result<Bracket, CodeError> {
+        let code = code.trim();
+        let len = code.chars().count();
+        if len != CODE_LEN {
+            return Err(CodeError::Length(len));
+        }
+        let mut bytes = Vec::with_capacity(9);
+        let chars: Vec<char> = code.chars().collect();
+        for group in chars.chunks(4) {
+            let mut bits = 0u32;
+            for &c in group {
+                let value = ALPHABET
+                    .iter()
+                    .position(|&a| a as char == c)
+                    .ok_or(CodeError::Character(c))?;
+                bits = bits << 6 | value as u32;
+            }
+            bytes.extend_from_slice(&bits.to_be_bytes()[1..]);
+        }
+
+        let decisions = u64::from_be_bytes(bytes[..8].try_into().unwrap());
+        if bytes[8] != check_byte(decisions) {
+            return Err(CodeError::Checksum);
+        }
+        if decisions & 1 != 0 {
+            return Err(CodeError::ReservedBit);
+        }
+        Ok(Bracket::from_decisions(decisions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_brackets;
+
+    #[test]
+    fn codes_round_trip() {
+        for bracket in random_brackets(6, 20) {
+            let code = bracket.to_code();
+            assert_eq!(code.len(), CODE_LEN);
+            assert!(code
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(Bracket::from_code(&code), Ok(bracket));
+        }
+        let chalk = Bracket::from_decisions(0);
+        assert_eq!(
+            Bracket::from_code(&format!(" {}\n", chalk.to_code())),
+            Ok(chalk)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_codes() {
+        let code = Bracket::from_decisions(0x1234_5678_9ABC_DEF0).to_code();
+        assert_eq!(Bracket::from_code(&code[1..]), Err(CodeError::Length(11)));
+        assert_eq!(
+            Bracket::from_code(&code.replacen(&code[..1], "+", 1)),
+            Err(CodeError::Character('+'))
+        );
+
+        let flipped = if &code[..1] == "A" { "B" } else { "A" };
+        assert_eq!(
+            Bracket::from_code(&format!("{}{}", flipped, &code[1..])),
+            Err(CodeError::Checksum)
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::cmp::Reverse;
+
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Score, Tournament};
+
+/// An entry in a pool that runs the men's and women's tournaments together.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CombinedEntry {
+    pub mens: Bracket,
+    pub womens: Bracket,
+    /// A guess at the total points scored in the men's title game, used to
+    /// break ties.
+    pub tiebreaker: Option<u32>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CombinedScore {
+    pub mens: Score,
+    pub womens: Score,
+}
+
+impl CombinedScore {
+    pub fn total(&self) -> Score {
+        self.mens.saturating_add(self.womens)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CombinedStanding {
+    /// Index into the entries the standings were computed from.
+    pub entry: usize,
+    pub score: CombinedScore,
+    /// How far the entry's tiebreaker guess was from the title game's
+    /// total, once there is one.
+    pub tiebreaker_miss: Option<u32>,
+    /// Zero-based; entries with the same total and tiebreaker miss share a
+    /// rank.
+    pub rank: usize,
+}
+
+impl CombinedStanding {
+    /// Best first: the higher total, then the closer tiebreaker guess, with
+    /// no guess last.
+    fn key(&self) -> (Reverse<Score>, u64) {
+        let miss = self.tiebreaker_miss.map_or(u64::MAX, u64::from);
+        (Reverse(self.score.total()), miss)
+    }
+}
+
+/// Combined standings, best total first, keeping the per-tournament scores so
+/// reports can break the total down. Each tournament is scored under its
+/// own system. Equal totals are split by the tiebreaker guess closest to
+/// `title_points`, the men's title game total, once it's known.
+pub fn combined_standings(
+    (mens, mens_scoring): (&Tournament, &ScoringSystem),
+    (womens, womens_scoring): (&Tournament, &ScoringSystem),
+    entries: &[CombinedEntry],
+    title_points: Option<u32>,
+) -> Vec<CombinedStanding> {
+    let mens = TournamentView::new(mens);
+    let womens = TournamentView::new(womens);
+
+    let mut standings: Vec<CombinedStanding> = entries
+        .iter()
+        .enumerate()
+        .map(|(entry, e)| CombinedStanding {
+            entry,
+            score: CombinedScore {
+                mens: mens.score_with(&e.mens, mens_scoring),
+                womens: womens.score_with(&e.womens, womens_scoring),
+            },
+            tiebreaker_miss: title_points
+                .zip(e.tiebreaker)
+                .map(|(actual, guess)| guess.abs_diff(actual)),
+            rank: 0,
+        })
+        .collect();
+
+    standings.sort_by_key(CombinedStanding::key);
+
+    let mut rank = 0;
+    for i in 0..standings.len() {
+        if i > 0 && standings[i - 1].key() != standings[i].key() {
+            rank = i;
+        }
+        standings[i].rank = rank;
+    }
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn ranks_by_total_with_breakdown() {
+        let mens = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK,
+            vacated: 0,
+        };
+        let womens = Tournament {
+            decisions: COMPLETE_MASK,
+            mask: COMPLETE_MASK,
+            vacated: 0,
+        };
+        let chalk = Bracket { decisions: 0 };
+        let upsets = Bracket {
+            decisions: COMPLETE_MASK,
+        };
+        let entries = [
+            CombinedEntry {
+                mens: chalk,
+                womens: chalk,
+                tiebreaker: None,
+            },
+            CombinedEntry {
+                mens: chalk,
+                womens: upsets,
+                tiebreaker: None,
+            },
+            CombinedEntry {
+                mens: upsets,
+                womens: chalk,
+                tiebreaker: None,
+            },
+        ];
+        let standard = ScoringSystem::standard();
+
+        let standings =
+            combined_standings((&mens, &standard), (&womens, &standard), &entries, None);
+
+        assert_eq!(standings[0].entry, 1);
+        assert_eq!(standings[0].rank, 0);
+        assert_eq!(
+            standings[0].score.mens,
+            TournamentView::new(&mens).score(&chalk)
+        );
+        assert_eq!(standings[1].rank, 1);
+        assert_eq!(standings[2].entry, 2);
+        assert_eq!(standings[2].score.total(), 0);
+
+        let doubling = ScoringSystem::doubling();
+        let standings =
+            combined_standings((&mens, &standard), (&womens, &doubling), &entries, None);
+        assert_eq!(
+            standings[0].score.womens,
+            TournamentView::new(&womens).score_with(&upsets, &doubling)
+        );
+    }
+
+    #[test]
+    fn tiebreaker_splits_equal_totals() {
+        let tournament = Tournament::new(0, COMPLETE_MASK);
+        let chalk = Bracket { decisions: 0 };
+        let entry = |tiebreaker| CombinedEntry {
+            mens: chalk,
+            womens: chalk,
+            tiebreaker,
+        };
+        let entries = [
+            entry(None),
+            entry(Some(150)),
+            entry(Some(131)),
+            entry(Some(129)),
+        ];
+        let standard = ScoringSystem::standard();
+        let standings = |title_points| {
+            combined_standings(
+                (&tournament, &standard),
+                (&tournament, &standard),
+                &entries,
+                title_points,
+            )
+            .iter()
+            .map(|s| (s.entry, s.rank))
+            .collect::<Vec<_>>()
+        };
+
+        // Guesses of 131 and 129 both miss 130 by one.
+        assert_eq!(standings(Some(130)), vec![(2, 0), (3, 0), (1, 2), (0, 3)]);
+        // Until the title game is played, everyone is tied.
+        assert_eq!(standings(None), vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn total_saturates() {
+        let score = CombinedScore {
+            mens: Score::MAX,
+            womens: 1,
+        };
+        assert_eq!(score.total(), Score::MAX);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{standings_ranks, Bracket, Score, ROUNDS};
+
+/// The standings if `teams` win the games of one round, counting the games
+/// already decided plus every game those teams have to win to get there.
+/// Other games still to be played aren't counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionalLeaderboard {
+    /// Winner of each game in the round, in slot order. `None` where a
+    /// vacated game leaves no one to advance.
+    pub teams: Vec<Option<u8>>,
+    pub scores: Vec<Score>,
+    /// Zero-based, ties sharing the better position.
+    pub ranks: Vec<usize>,
+}
+
+impl TournamentView {
+    /// One leaderboard for every way the games of `round` can still turn
+    /// out: `round` 4 gives each possible Final Four, 5 each possible title
+    /// matchup.
+    pub fn conditional_leaderboards(
+        &self,
+        brackets: &[Bracket],
+        round: u8,
+    ) -> Vec<ConditionalLeaderboard> {
+        self.conditional_leaderboards_with(brackets, round, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::conditional_leaderboards`] under `scoring`.
+    pub fn conditional_leaderboards_with(
+        &self,
+        brackets: &[Bracket],
+        round: u8,
+        scoring: &ScoringSystem,
+    ) -> Vec<ConditionalLeaderboard> {
+        let first_slot = 1u8 << (ROUNDS - round);
+        let candidates: Vec<Vec<Option<u8>>> = (first_slot..first_slot * 2)
+            .map(|slot| {
+                let teams = self.contenders(slot);
+                if teams.is_empty() {
+                    vec![None]
+                } else {
+                    teams.into_iter().map(Some).collect()
+                }
+            })
+            .collect();
+
+        let mut boards = vec![];
+        let mut choice = vec![0; candidates.len()];
+        loop {
+            let teams: Vec<Option<u8>> = candidates
+                .iter()
+                .zip(&choice)
+                .map(|(teams, &i)| teams[i])
+                .collect();
+            boards.push(self.leaderboard_for(brackets, first_slot, teams, scoring));
+
+            // Odometer over the candidates, last game fastest.
+            let Some(game) = (0..choice.len())
+                .rev()
+                .find(|&game| choice[game] + 1 < candidates[game].len())
+            else {
+                break;
+            };
+            choice[game] += 1;
+            choice[game + 1..].iter_mut().for_each(|i| *i = 0);
+        }
+        boards
+    }
+
+    fn leaderboard_for(
+        &self,
+        brackets: &[Bracket],
+        first_slot: u8,
+        teams: Vec<Option<u8>>,
+        scoring: &ScoringSystem,
+    ) -> ConditionalLeaderboard {
+        let mut team_slots = [None; 128];
+        team_slots.copy_from_slice(self.team_slots());
+        for (slot, team) in (first_slot..).zip(&teams) {
+            let Some(team) = *team else {
+                continue;
+            };
+            let mut game = team / 2;
+            while game >= slot {
+                team_slots[game as usize] = Some(team);
+                game /= 2;
+            }
+        }
+
+        let scores: Vec<Score> = brackets
+            .iter()
+            .map(|b| b.points_for_decisions(&team_slots, scoring))
+            .collect();
+        let ranks = standings_ranks(&scores);
+        ConditionalLeaderboard {
+            teams,
+            scores,
+            ranks,
+        }
+    }
+}
+
+/// For each bracket, how many of `boards` it leads outright or shares the
+/// lead in.
+pub fn leads_in(boards: &[ConditionalLeaderboard], entries: usize) -> Vec<usize> {
+    let mut leads = vec![0; entries];
+    for board in boards {
+        for (entry, &rank) in board.ranks.iter().enumerate() {
+            if rank == 0 {
+                leads[entry] += 1;
+            }
+        }
+    }
+    leads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{round_slot_mask, Tournament};
+
+    #[test]
+    fn one_board_per_final_four() {
+        // Through the Sweet 16; the Elite Eight and on are still to be played.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: round_slot_mask(1) | round_slot_mask(2) | round_slot_mask(3),
+            vacated: 0,
+        });
+        let chalk = Bracket { decisions: 0 };
+        // Has the other team in the top Elite Eight game coming through.
+        let contrarian = Bracket { decisions: 1 << 4 };
+
+        let boards = view.conditional_leaderboards(&[chalk, contrarian], 4);
+        assert_eq!(boards.len(), 16);
+        assert_eq!(
+            boards[0].teams,
+            vec![Some(64), Some(80), Some(96), Some(112)]
+        );
+        assert_eq!(leads_in(&boards, 2), vec![8, 8]);
+
+        // With the Elite Eight worth nothing, every board is a tie.
+        let no_elite_eight = ScoringSystem {
+            multipliers: [0, 1, 1, 1, 0, 1, 1],
+            ..ScoringSystem::standard()
+        };
+        let boards = view.conditional_leaderboards_with(&[chalk, contrarian], 4, &no_elite_eight);
+        assert_eq!(leads_in(&boards, 2), vec![16, 16]);
+
+        let matchups = view.conditional_leaderboards(&[chalk, contrarian], 5);
+        assert_eq!(matchups.len(), 16);
+        assert_eq!(matchups[0].teams, vec![Some(64), Some(96)]);
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt;
+
+use crate::score_delta::ScoreDeltaTable;
+use crate::view::TournamentView;
+use crate::{round_name, round_num_for_slot, Bracket, Decisions, ENUMERATION_LIMIT};
+
+/// The game in `slot` won from child `slot * 2 + side`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Outcome {
+    pub slot: u8,
+    pub side: u8,
+}
+
+/// Outcomes that all have to happen, title game first.
+pub type Term = Vec<Outcome>;
+
+/// A condition over game outcomes in disjunctive normal form: it holds when
+/// every outcome of at least one term does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WinCondition {
+    pub terms: Vec<Term>,
+}
+
+impl WinCondition {
+    /// `Some(true)` once the decided games satisfy a term, `Some(false)` once
+    /// every term has an outcome that went the other way, `None` while it
+    /// still depends on games to be played.
+    pub fn evaluate<D: Decisions + ?Sized>(&self, tournament: &D) -> Option<bool> {
+        let mut open = false;
+        for term in &self.terms {
+            let decided: Vec<Option<u8>> =
+                term.iter().map(|o| tournament.decision(o.slot)).collect();
+            if term
+                .iter()
+                .zip(&decided)
+                .any(|(o, d)| d.is_some_and(|side| side != o.side))
+            {
+                continue;
+            }
+            if decided.iter().all(Option::is_some) {
+                return Some(true);
+            }
+            open = true;
+        }
+        if open {
+            None
+        } else {
+            Some(false)
+        }
+    }
+
+    /// The condition once the game in `slot` has been won from `side`:
+    /// terms it contradicts are dropped and the outcome is removed from the
+    /// terms it satisfies.
+    pub fn after(&self, slot: u8, side: u8) -> WinCondition {
+        let mut terms: Vec<Term> = self
+            .terms
+            .iter()
+            .filter(|term| !term.iter().any(|o| o.slot == slot && o.side != side))
+            .map(|term| term.iter().copied().filter(|o| o.slot != slot).collect())
+            .collect();
+        simplify(&mut terms);
+        WinCondition { terms }
+    }
+}
+
+/// Games as `g<slot>` and outcomes as the side won from, e.g.
+/// `g1=0 | (g3=1 & g2=0)`. Always true is `true`, never is `false`.
+impl fmt::Display for WinCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "false");
+        }
+        let terms: Vec<String> = self
+            .terms
+            .iter()
+            .map(|term| {
+                let outcomes: Vec<String> = term
+                    .iter()
+                    .map(|o| format!("g{}={}", o.slot, o.side))
+                    .collect();
+                match outcomes.len() {
+                    0 => "true".to_string(),
+                    1 => outcomes[0].clone(),
+                    _ if self.terms.len() == 1 => outcomes.join(" & "),
+                    _ => format!("({})", outcomes.join(" & ")),
+                }
+            })
+            .collect();
+        write!(f, "{}", terms.join(" | "))
+    }
+}
+
+impl TournamentView {
+    /// The ways bracket `entry` finishes first among `brackets` (alone or
+    /// tied), as alternatives over the games still to be played. Each term
+    /// only names the games it needs; no terms means the entry can't win, and
+    /// a single empty term means it wins however the games go.
+    ///
+    /// Every remaining scenario is scored, so this is `None` while more
+    /// than [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn win_condition(&self, brackets: &[Bracket], entry: usize) -> Option<WinCondition> {
+        Some(WinCondition {
+            terms: self.win_terms(brackets, entry)?,
+        })
+    }
+
+    /// The terms of [`TournamentView::win_condition`].
+    pub fn win_terms(&self, brackets: &[Bracket], entry: usize) -> Option<Vec<Term>> {
+        if !self.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        // Title game first, so it's the highest bit of a scenario's index and
+        // the first game split on.
+        let mut games = self.open_games();
+        games.sort_unstable();
+
+        let deltas = ScoreDeltaTable::for_view(self, brackets);
+        let mut wins = vec![false; 1 << games.len()];
+        self.for_each_scenario(None, |team_slots| {
+            let index = games.iter().fold(0, |acc, &slot| {
+                let side = team_slots[slot as usize] == team_slots[slot as usize * 2 + 1];
+                (acc << 1) | side as usize
+            });
+            let points = deltas.score(entry, team_slots);
+            wins[index] = (0..brackets.len()).all(|i| deltas.score(i, team_slots) <= points);
+        });
+
+        let mut terms = vec![];
+        split(&wins, &games, &mut vec![], &mut terms);
+        simplify(&mut terms);
+        Some(terms)
+    }
+
+    /// [`TournamentView::win_condition`] in words, one line per alternative, e.g.
+    /// "Purdue beats UConn in the Championship". `team_name` names a team
+    /// slot (64..=127).
+    pub fn clinching_conditions<F: Fn(u8) -> String>(
+        &self,
+        brackets: &[Bracket],
+        entry: usize,
+        team_name: F,
+    ) -> Option<Vec<String>> {
+        let terms = self.win_terms(brackets, entry)?;
+        Some(
+            terms
+                .iter()
+                .map(|term| self.describe(term, &team_name))
+                .collect(),
+        )
+    }
+
+    fn describe<F: Fn(u8) -> String>(&self, term: &[Outcome], team_name: &F) -> String {
+        if term.is_empty() {
+            return "however the remaining games go".to_string();
+        }
+
+        let winners: Vec<Option<u8>> = term
+            .iter()
+            .map(|o| self.team_from(term, o.slot * 2 + o.side))
+            .collect();
+        let phrases: Vec<String> = term
+            .iter()
+            .zip(&winners)
+            .enumerate()
+            // A team winning a later game in the same term won this one too.
+            .filter(|&(i, (_, winner))| winner.is_none() || !winners[..i].contains(winner))
+            .map(|(_, (outcome, winner))| {
+                let name = |team: Option<u8>, child: u8| match team {
+                    Some(team) => team_name(team),
+                    None => format!("the game {} winner", child),
+                };
+                let (child, other) = (
+                    outcome.slot * 2 + outcome.side,
+                    outcome.slot * 2 + (1 - outcome.side),
+                );
+                let round = round_name(round_num_for_slot(outcome.slot));
+                match self.team_from(term, other) {
+                    Some(loser) => format!(
+                        "{} beats {} in the {}",
+                        name(*winner, child),
+                        team_name(loser),
+                        round
+                    ),
+                    None => format!("{} wins in the {}", name(*winner, child), round),
+                }
+            })
+            .collect();
+        phrases.join(" and ")
+    }
+
+    /// The team coming out of `slot` when `term` holds, if that's settled.
+    fn team_from(&self, term: &[Outcome], slot: u8) -> Option<u8> {
+        if slot >= 64 || self.is_decided(slot) {
+            return self.team_slots()[slot as usize];
+        }
+        let outcome = term.iter().find(|o| o.slot == slot)?;
+        self.team_from(term, slot * 2 + outcome.side)
+    }
+}
+
+/// Terms covering the winning scenarios in `wins`, whose index bits are the
+/// sides of `games` (first game highest). Splits on one game at a time,
+/// stopping wherever the remaining games don't matter.
+fn split(wins: &[bool], games: &[u8], prefix: &mut Term, terms: &mut Vec<Term>) {
+    if wins.iter().all(|&w| w) {
+        terms.push(prefix.clone());
+        return;
+    }
+    if !wins.contains(&true) {
+        return;
+    }
+    let (slot, rest) = games.split_first().unwrap();
+    let half = wins.len() / 2;
+    for (side, part) in [(0, &wins[..half]), (1, &wins[half..])] {
+        prefix.push(Outcome { slot: *slot, side });
+        split(part, rest, prefix, terms);
+        prefix.pop();
+    }
+}
+
+/// Drops outcomes and terms the rest of the terms make redundant, until
+/// nothing changes. Given `A = x and Q` and `B = not x and R` with `Q` part
+/// of `R`, `B` doesn't need `not x`; a term containing another is dropped.
+fn simplify(terms: &mut Vec<Term>) {
+    let flip = |o: &Outcome| Outcome {
+        slot: o.slot,
+        side: 1 - o.side,
+    };
+    loop {
+        let mut changed = false;
+        for a in 0..terms.len() {
+            for b in 0..terms.len() {
+                if a == b {
+                    continue;
+                }
+                let Some(pos) = terms[b].iter().position(|o| terms[a].contains(&flip(o))) else {
+                    continue;
+                };
+                let x = flip(&terms[b][pos]);
+                let covered = terms[a].iter().all(|o| *o == x || terms[b].contains(o));
+                if covered {
+                    terms[b].remove(pos);
+                    changed = true;
+                }
+            }
+        }
+
+        let before = terms.len();
+        let mut kept: Vec<Term> = vec![];
+        terms.sort_by_key(|t| t.len());
+        for term in terms.drain(..) {
+            if !kept.iter().any(|k| k.iter().all(|o| term.contains(o))) {
+                kept.push(term);
+            }
+        }
+        kept.sort();
+        *terms = kept;
+
+        if !changed && terms.len() == before {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    fn final_four_view() -> TournamentView {
+        // Chalk through the Elite Eight: 64 and 80 meet in slot 2, 96 and
+        // 112 in slot 3.
+        TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        })
+    }
+
+    #[test]
+    fn wins_unless_the_rival_champion_wins() {
+        let view = final_four_view();
+        let chalk = Bracket { decisions: 0 };
+        // Same semifinal picks, but 96 for the title.
+        let rival = Bracket { decisions: 1 << 1 };
+        let brackets = [chalk, rival];
+
+        let o = |slot, side| Outcome { slot, side };
+        assert_eq!(
+            view.win_terms(&brackets, 0).unwrap(),
+            vec![vec![o(1, 0)], vec![o(3, 1)]]
+        );
+        assert_eq!(
+            view.win_terms(&brackets, 1).unwrap(),
+            vec![vec![o(1, 1)], vec![o(2, 1)]]
+        );
+
+        let name = |team: u8| format!("T{}", team);
+        assert_eq!(
+            view.clinching_conditions(&brackets, 0, name).unwrap(),
+            vec![
+                "the game 2 winner wins in the Championship",
+                "T112 beats T96 in the Final Four",
+            ]
+        );
+    }
+
+    #[test]
+    fn terms_cover_exactly_the_winning_scenarios() {
+        let view = final_four_view();
+        let brackets = [
+            Bracket { decisions: 0 },
+            Bracket { decisions: 1 << 2 },
+            Bracket {
+                decisions: (1 << 1) | (1 << 3),
+            },
+        ];
+
+        for entry in 0..brackets.len() {
+            let terms = view.win_terms(&brackets, entry).unwrap();
+            let deltas = ScoreDeltaTable::for_view(&view, &brackets);
+            view.for_each_scenario(None, |team_slots| {
+                let points = deltas.score(entry, team_slots);
+                let wins = (0..brackets.len()).all(|i| deltas.score(i, team_slots) <= points);
+                let side = |slot: usize| (team_slots[slot] == team_slots[slot * 2 + 1]) as u8;
+                let holds = terms
+                    .iter()
+                    .any(|t| t.iter().all(|o| side(o.slot as usize) == o.side));
+                assert_eq!(wins, holds);
+            });
+        }
+    }
+
+    #[test]
+    fn condition_updates_as_games_finish() {
+        let view = final_four_view();
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 1 << 1 }];
+        let condition = view.win_condition(&brackets, 0).unwrap();
+        assert_eq!(condition.to_string(), "g1=0 | g3=1");
+
+        let mut tournament = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        };
+        assert_eq!(condition.evaluate(&tournament), None);
+
+        // 96 wins its semifinal, so it all comes down to the title game.
+        tournament.set_decision(3, 0);
+        assert_eq!(condition.evaluate(&tournament), None);
+        let condition = condition.after(3, 0);
+        assert_eq!(condition.to_string(), "g1=0");
+
+        tournament.set_decision(1, 1);
+        assert_eq!(condition.evaluate(&tournament), Some(false));
+        assert_eq!(condition.after(1, 1).to_string(), "false");
+    }
+}
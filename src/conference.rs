@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::field::Field;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Score, Tournament};
+
+/// How one conference's teams fared in a tournament.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConferenceRecord {
+    pub teams: usize,
+    /// `wins_in_round[r]` is how many of its teams won their round `r` game.
+    pub wins_in_round: [usize; 7],
+}
+
+impl ConferenceRecord {
+    pub fn wins(&self) -> usize {
+        self.wins_in_round.iter().sum()
+    }
+
+    /// Share of the conference's teams that won their game in `round`.
+    pub fn advancement_rate(&self, round: u8) -> f64 {
+        if self.teams == 0 {
+            return 0.0;
+        }
+        self.wins_in_round[round as usize] as f64 / self.teams as f64
+    }
+}
+
+impl Field {
+    /// The conference of the team in `team` (64..=127), if it has one.
+    fn conference_of(&self, team: u8) -> Option<&str> {
+        self.team(team).conference.as_deref()
+    }
+
+    /// Points `bracket` has earned under `scoring` from correct picks of
+    /// each conference's teams. Teams without a conference are left out.
+    pub fn points_by_conference(
+        &self,
+        view: &TournamentView,
+        bracket: &Bracket,
+        scoring: &ScoringSystem,
+    ) -> HashMap<String, Score> {
+        let bracket_team_slots = bracket.decision_team_slots();
+        let mut points = HashMap::new();
+        for (slot, (t, b)) in view
+            .team_slots()
+            .iter()
+            .zip(bracket_team_slots.iter())
+            .enumerate()
+            .take(64)
+        {
+            if let (Some(t), Some(b)) = (*t, *b) {
+                if t == b {
+                    if let Some(conference) = self.conference_of(b) {
+                        let earned = points.entry(conference.to_string()).or_insert(0);
+                        let pick = scoring.points_in(slot as u8, b, view.team_slots());
+                        *earned = Score::saturating_add(*earned, pick);
+                    }
+                }
+            }
+        }
+        points
+    }
+
+    /// Record of every conference with at least one team in the field, by
+    /// the field's rounds.
+    pub fn conference_records(&self, tournament: &Tournament) -> HashMap<String, ConferenceRecord> {
+        let mut records: HashMap<String, ConferenceRecord> = HashMap::new();
+        for conference in (64..128u8).filter_map(|team| self.conference_of(team)) {
+            records.entry(conference.to_string()).or_default().teams += 1;
+        }
+
+        let team_slots = tournament.decision_team_slots();
+        for (slot, winner) in team_slots.iter().enumerate().take(64).skip(1) {
+            let Some(round) = self.format().round(slot as u8) else {
+                continue;
+            };
+            if let Some(conference) = winner.and_then(|w| self.conference_of(w)) {
+                if let Some(record) = records.get_mut(conference) {
+                    record.wins_in_round[round as usize] += 1;
+                }
+            }
+        }
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::standard_field;
+    use crate::{points_for_pick, COMPLETE_MASK};
+
+    #[test]
+    fn conference_points_and_records() {
+        let mut field = standard_field();
+        field.set_conference(64, "Big Ten");
+        field.set_conference(65, "Big Ten");
+        field.set_conference(66, "ACC");
+        assert_eq!(field.team(65).conference.as_deref(), Some("Big Ten"));
+        // First round only: the even-numbered team won every game.
+        let tournament = Tournament {
+            decisions: 0,
+            mask: 0xFFFF_FFFF_0000_0000,
+            vacated: 0,
+        };
+        let view = TournamentView::new(&tournament);
+        let chalk = Bracket { decisions: 0 };
+
+        let points = field.points_by_conference(&view, &chalk, &ScoringSystem::standard());
+        assert_eq!(points["Big Ten"], points_for_pick(32, 64));
+        assert_eq!(points["ACC"], points_for_pick(33, 66));
+        assert_eq!(points.len(), 2);
+
+        let doubling = ScoringSystem::doubling();
+        let points = field.points_by_conference(&view, &chalk, &doubling);
+        assert_eq!(points["Big Ten"], doubling.points(32, 64, Some(65)));
+
+        let records = field.conference_records(&tournament);
+        assert_eq!(records["Big Ten"].teams, 2);
+        assert_eq!(records["Big Ten"].wins(), 1);
+        assert_eq!(records["Big Ten"].advancement_rate(1), 0.5);
+        assert_eq!(records["ACC"].advancement_rate(2), 0.0);
+        assert_eq!(
+            field.conference_records(&Tournament {
+                decisions: 0,
+                mask: COMPLETE_MASK,
+                vacated: 0,
+            })["Big Ten"]
+                .wins(),
+            6
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{standings_ranks, Bracket, Score};
+
+/// Anything entries are scored on while outcomes are still open: a bracket
+/// pool, a slate of games, a survivor pool. Implementing the two methods
+/// gives the contest the generic finish analysis below.
+pub trait Contest {
+    /// One way everything still open can turn out.
+    type Outcome: ?Sized;
+
+    fn entries(&self) -> usize;
+
+    /// Calls `f` once for every remaining outcome.
+    fn for_each_outcome<F: FnMut(&Self::Outcome)>(&self, f: F);
+
+    /// Entry `entry`'s final score under `outcome`.
+    fn score(&self, entry: usize, outcome: &Self::Outcome) -> Score;
+}
+
+/// Calls `f` with every entry's zero-based rank (ties sharing the better
+/// one) under each remaining outcome, and returns how many outcomes there
+/// were.
+pub fn for_each_ranking<C, F>(contest: &C, mut f: F) -> u64
+where
+    C: Contest + ?Sized,
+    F: FnMut(&[usize]),
+{
+    let mut scores: Vec<Score> = Vec::with_capacity(contest.entries());
+    let mut outcomes = 0;
+    contest.for_each_outcome(|outcome| {
+        outcomes += 1;
+        scores.clear();
+        scores.extend((0..contest.entries()).map(|i| contest.score(i, outcome)));
+        f(&standings_ranks(&scores));
+    });
+    outcomes
+}
+
+/// For each entry, how many outcomes leave it in each of the top `places`
+/// zero-based positions (ties sharing the better one).
+pub fn finish_counts<C: Contest + ?Sized>(contest: &C, places: usize) -> Vec<Vec<u64>> {
+    let mut counts = vec![vec![0; places]; contest.entries()];
+    for_each_ranking(contest, |ranks| {
+        for (entry, &rank) in ranks.iter().enumerate() {
+            if rank < places {
+                counts[entry][rank] += 1;
+            }
+        }
+    });
+    counts
+}
+
+/// Each entry's best zero-based finish, or `None` outside the top `places`
+/// in every outcome.
+pub fn best_finishes<C: Contest + ?Sized>(contest: &C, places: usize) -> Vec<Option<usize>> {
+    finish_counts(contest, places)
+        .into_iter()
+        .map(|counts| counts.iter().position(|&n| n > 0))
+        .collect()
+}
+
+/// A bracket pool as a [`Contest`], each outcome being a scenario's winner
+/// table. Pool status and paths to victory are built on it; the best-finish
+/// search behind `Pool::best_finishes` keeps its own walk, which merges
+/// region outcomes that score the same.
+#[derive(Debug)]
+pub struct BracketContest<'a> {
+    view: &'a TournamentView,
+    deltas: ScoreDeltaTable,
+    entries: usize,
+}
+
+impl<'a> BracketContest<'a> {
+    pub fn new(
+        view: &'a TournamentView,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+    ) -> BracketContest<'a> {
+        BracketContest {
+            view,
+            deltas: ScoreDeltaTable::for_view_with(view, brackets, scoring),
+            entries: brackets.len(),
+        }
+    }
+
+    /// Moves entry `entry`'s score by `points` in every outcome, as
+    /// [`ScoreDeltaTable::adjust`].
+    pub fn adjust(&mut self, entry: usize, points: i64) {
+        self.deltas.adjust(entry, points);
+    }
+}
+
+impl Contest for BracketContest<'_> {
+    type Outcome = [Option<u8>];
+
+    fn entries(&self) -> usize {
+        self.entries
+    }
+
+    fn for_each_outcome<F: FnMut(&[Option<u8>])>(&self, f: F) {
+        self.view.for_each_scenario(None, f);
+    }
+
+    fn score(&self, entry: usize, outcome: &[Option<u8>]) -> Score {
+        self.deltas.score(entry, outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_brackets;
+    use crate::{round_slot_mask, BestFinishes, Tournament};
+
+    // Pick'em: each entry picks a side of every game, a point per hit.
+    struct Slate {
+        picks: Vec<Vec<bool>>,
+        games: usize,
+    }
+
+    impl Contest for Slate {
+        type Outcome = u32;
+
+        fn entries(&self) -> usize {
+            self.picks.len()
+        }
+
+        fn for_each_outcome<F: FnMut(&u32)>(&self, mut f: F) {
+            for outcome in 0..1u32 << self.games {
+                f(&outcome);
+            }
+        }
+
+        fn score(&self, entry: usize, outcome: &u32) -> Score {
+            self.picks[entry]
+                .iter()
+                .enumerate()
+                .filter(|&(game, &pick)| (outcome >> game & 1 == 1) == pick)
+                .count() as Score
+        }
+    }
+
+    #[test]
+    fn brackets_match_the_dedicated_engine() {
+        let view = TournamentView::new(&Tournament::new(
+            0,
+            (1..=3).fold(0, |mask, r| mask | round_slot_mask(r)),
+        ));
+        let brackets = random_brackets(2, 8);
+        let expected = BestFinishes::for_view(&brackets, &view);
+
+        let generic = best_finishes(
+            &BracketContest::new(&view, &brackets, &ScoringSystem::standard()),
+            5,
+        );
+        for (bracket, finish) in brackets.iter().zip(generic) {
+            assert_eq!(expected.possible_finishes.get(bracket).copied(), finish);
+        }
+
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        let expected = BestFinishes::for_view_with(&brackets, &view, &upsets, 3);
+        let generic = best_finishes(&BracketContest::new(&view, &brackets, &upsets), 3);
+        for (bracket, finish) in brackets.iter().zip(generic) {
+            assert_eq!(expected.possible_finishes.get(bracket).copied(), finish);
+        }
+    }
+
+    #[test]
+    fn other_contests_get_the_same_analysis() {
+        let slate = Slate {
+            picks: vec![vec![true, true], vec![true, false], vec![true, true]],
+            games: 2,
+        };
+        // Game 1 decides it: entries 0 and 2 share first when it goes their
+        // way, and entry 1 wins alone when it doesn't.
+        assert_eq!(
+            finish_counts(&slate, 2),
+            vec![vec![2, 2], vec![2, 0], vec![2, 2]]
+        );
+        assert_eq!(best_finishes(&slate, 1), vec![Some(0), Some(0), Some(0)]);
+    }
+}
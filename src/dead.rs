@@ -0,0 +1,100 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Score};
+
+/// A pick of `team` to win `slot` that can no longer come true, and the
+/// points it would have been worth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeadPick {
+    pub slot: u8,
+    pub team: u8,
+    pub points: Score,
+}
+
+/// A bracket's dead picks, indexed by the event's round number (1..=6).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeadPicks {
+    pub rounds: [Vec<DeadPick>; 7],
+}
+
+impl DeadPicks {
+    pub fn points_lost_in(&self, round: u8) -> Score {
+        self.rounds[round as usize]
+            .iter()
+            .fold(0, |acc, pick| acc.saturating_add(pick.points))
+    }
+
+    /// Points the bracket can never get back, the leaderboard's "points lost".
+    pub fn points_lost(&self) -> Score {
+        (1..=6).fold(0, |acc, round| {
+            acc.saturating_add(self.points_lost_in(round))
+        })
+    }
+}
+
+impl Bracket {
+    /// Every pick whose team lost before reaching the game, or that was
+    /// decided some other way (including vacated games), worth what
+    /// `scoring` would have given it. Within a round, picks are in slot
+    /// order; padding games, which score nothing, are left out.
+    pub fn dead_picks(&self, view: &TournamentView, scoring: &ScoringSystem) -> DeadPicks {
+        let mut dead = DeadPicks::default();
+        for (slot, team) in self.decision_team_slots().iter().enumerate().skip(1) {
+            let (slot, Some(team)) = (slot as u8, *team) else {
+                continue;
+            };
+            let Some(round) = scoring.format.round(slot) else {
+                continue;
+            };
+            if !view.can_reach(team, slot) {
+                dead.rounds[round as usize].push(DeadPick {
+                    slot,
+                    team,
+                    points: scoring.points(slot, team, None),
+                });
+            }
+        }
+        dead
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{points_for, round_slot_mask, Tournament};
+
+    #[test]
+    fn upset_kills_the_later_picks_too() {
+        // Only the first game of round 1 is in, and the bottom team won it.
+        let view = TournamentView::new(&Tournament {
+            decisions: 1 << 32,
+            mask: 1 << 32,
+            vacated: 0,
+        });
+
+        let chalk = Bracket { decisions: 0 };
+        let standard = ScoringSystem::standard();
+        let dead = chalk.dead_picks(&view, &standard);
+        // Team 64 was picked through the title game.
+        for round in 1..=6 {
+            assert_eq!(dead.rounds[round as usize].len(), 1);
+            assert_eq!(dead.rounds[round as usize][0].team, 64);
+            assert_eq!(dead.points_lost_in(round), points_for(round, 1));
+        }
+        assert_eq!(
+            dead.points_lost(),
+            (1..=6).map(|round| points_for(round, 1)).sum::<Score>()
+        );
+        let doubling = chalk.dead_picks(&view, &ScoringSystem::doubling());
+        assert_eq!(doubling.points_lost_in(6), 32);
+        assert_eq!(doubling.points_lost(), 1 + 2 + 4 + 8 + 16 + 32);
+
+        // Everything else is still alive.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: round_slot_mask(1),
+            vacated: 0,
+        });
+        assert_eq!(chalk.dead_picks(&view, &standard), DeadPicks::default());
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::rng::Rng;
+use crate::score_delta::ScoreDeltaTable;
+use crate::view::TournamentView;
+use crate::{fix_sides, BestFinishes, Bracket, FinishContext};
+
+// Up to 64 subtrees, each either enumerated or sampled.
+const MAX_SPLIT: usize = 6;
+// Samples between clock checks, and in the probe that times a scenario.
+const SAMPLE_BATCH: u64 = 256;
+
+/// A bracket's best finish found within a time budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Finish {
+    /// Zero-based, ties sharing the better place.
+    pub rank: usize,
+    /// False when part of the tournament was only sampled, so a better
+    /// finish may exist. The rank is always reachable in some scenario.
+    pub exact: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApproximateFinishes {
+    /// Best finishes of brackets that reached the top places in some
+    /// scenario that was looked at.
+    pub finishes: HashMap<Bracket, Finish>,
+    pub exact_subtrees: usize,
+    pub sampled_subtrees: usize,
+}
+
+impl TournamentView {
+    /// Best finishes in the top `places`, computed within about `budget` of
+    /// wall-clock time. The first few undecided games split the scenarios
+    /// into subtrees; each one is enumerated when the time left covers it at
+    /// the rate measured so far, and sampled at random from `seed` for its
+    /// share of the time otherwise.
+    pub fn best_finishes_within(
+        &self,
+        brackets: &[Bracket],
+        places: usize,
+        budget: Duration,
+        seed: u64,
+    ) -> ApproximateFinishes {
+        let deadline = Instant::now() + budget;
+        let deltas = ScoreDeltaTable::for_view(self, brackets);
+        let split = self.undecided().len().min(MAX_SPLIT);
+        let (prefix, rest) = self.undecided().split_at(split);
+        let subtrees = 1usize << split;
+        let leaves = 1u64.checked_shl(rest.len() as u32).unwrap_or(u64::MAX);
+
+        let mut context = FinishContext::new();
+        let mut rng = Rng::new(seed);
+        let mut exact = BestFinishes::new(places);
+        let mut sampled = BestFinishes::new(places);
+        let (mut exact_subtrees, mut sampled_subtrees) = (0, 0);
+        let mut per_scenario: Option<Duration> = None;
+
+        for subtree in 0..subtrees {
+            let mut team_slots = self.team_slots().to_vec();
+            if !fix_sides(&mut team_slots, prefix, subtree) {
+                exact_subtrees += 1;
+                continue;
+            }
+            let per_scenario = *per_scenario.get_or_insert_with(|| {
+                // Time a batch of throwaway samples before the first choice.
+                let mut probe = BestFinishes::new(places);
+                let mut probe_slots = team_slots.clone();
+                let started = Instant::now();
+                for _ in 0..SAMPLE_BATCH {
+                    sample(&mut probe_slots, rest, &mut rng);
+                    context.record_leaf(brackets, &deltas, &probe_slots, &mut probe);
+                }
+                started.elapsed() / SAMPLE_BATCH as u32
+            });
+            let share =
+                deadline.saturating_duration_since(Instant::now()) / (subtrees - subtree) as u32;
+            let cost = per_scenario.saturating_mul(leaves.min(u32::MAX as u64) as u32);
+
+            if leaves <= SAMPLE_BATCH || cost <= share {
+                context.fill(brackets, &deltas, &mut team_slots, rest, &mut exact);
+                exact_subtrees += 1;
+            } else {
+                let started = Instant::now();
+                loop {
+                    for _ in 0..SAMPLE_BATCH {
+                        sample(&mut team_slots, rest, &mut rng);
+                        context.record_leaf(brackets, &deltas, &team_slots, &mut sampled);
+                    }
+                    if started.elapsed() >= share {
+                        break;
+                    }
+                }
+                sampled_subtrees += 1;
+            }
+        }
+
+        let mut finishes: HashMap<Bracket, Finish> = HashMap::new();
+        for (bracket, rank) in exact.possible_finishes {
+            finishes.insert(bracket, Finish { rank, exact: true });
+        }
+        for (bracket, rank) in sampled.possible_finishes {
+            let finish = finishes
+                .entry(bracket)
+                .or_insert(Finish { rank, exact: false });
+            finish.rank = finish.rank.min(rank);
+        }
+        for finish in finishes.values_mut() {
+            // Nothing beats first place, however the rest of it went.
+            finish.exact = sampled_subtrees == 0 || finish.rank == 0;
+        }
+        ApproximateFinishes {
+            finishes,
+            exact_subtrees,
+            sampled_subtrees,
+        }
+    }
+}
+
+/// Decides each of `games` with a coin flip, following the walkover rule in
+/// `FinishContext::fill`.
+fn sample(team_slots: &mut [Option<u8>], games: &[u8], rng: &mut Rng) {
+    for &slot in games {
+        let idx = slot as usize;
+        team_slots[idx] = match (team_slots[idx * 2], team_slots[idx * 2 + 1]) {
+            (Some(a), Some(b)) => Some(if rng.next_u64() & 1 == 0 { a } else { b }),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{round_slot_mask, Tournament, DEFAULT_PLACES};
+
+    #[test]
+    fn generous_budget_is_exact() {
+        // The Sweet 16 and on are left: 15 games.
+        let view =
+            TournamentView::new(&Tournament::new(0, round_slot_mask(1) | round_slot_mask(2)));
+        let brackets: Vec<Bracket> = (0..10u64)
+            .map(|i| Bracket::from_decisions(i.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+            .collect();
+
+        let expected = BestFinishes::for_view(&brackets, &view);
+        let approximate =
+            view.best_finishes_within(&brackets, DEFAULT_PLACES, Duration::from_secs(60), 1);
+        assert_eq!(approximate.sampled_subtrees, 0);
+        assert_eq!(approximate.finishes.len(), expected.possible_finishes.len());
+        for (bracket, rank) in &expected.possible_finishes {
+            assert_eq!(
+                approximate.finishes[bracket],
+                Finish {
+                    rank: *rank,
+                    exact: true
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn no_budget_samples_reachable_finishes() {
+        // Everything after the first round: 31 games, far too many to walk.
+        let view = TournamentView::new(&Tournament::new(0, round_slot_mask(1)));
+        let brackets: Vec<Bracket> = (0..10u64)
+            .map(|i| Bracket::from_decisions(i.wrapping_mul(0x2545_F491_4F6C_DD1D)))
+            .collect();
+
+        let approximate = view.best_finishes_within(&brackets, 3, Duration::ZERO, 4);
+        assert_eq!(
+            approximate.exact_subtrees + approximate.sampled_subtrees,
+            64
+        );
+        assert!(approximate.sampled_subtrees > 0);
+        assert!(!approximate.finishes.is_empty());
+        for finish in approximate.finishes.values() {
+            assert!(finish.rank < 3);
+            assert_eq!(finish.exact, finish.rank == 0);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{standings_ranks, BestFinishes, Bracket, Score, Tournament, ENUMERATION_LIMIT};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryDelta {
+    /// Index into the brackets the delta was computed from.
+    pub entry: usize,
+    pub points_gained: Score,
+    /// Zero-based standings positions; tied entries share a rank.
+    pub rank_before: usize,
+    pub rank_after: usize,
+}
+
+impl EntryDelta {
+    /// Places moved up the standings (negative when the entry dropped).
+    pub fn rank_change(&self) -> isize {
+        self.rank_before as isize - self.rank_after as isize
+    }
+}
+
+/// What changed in the standings between two tournament states, usually one
+/// recorded result apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandingsDelta {
+    /// One per bracket, in bracket order.
+    pub entries: Vec<EntryDelta>,
+    /// Entries that could still finish first before but no longer can.
+    pub newly_eliminated: Vec<usize>,
+}
+
+impl StandingsDelta {
+    /// Who can still win is found by scoring every remaining scenario of
+    /// both states, so this is `None` while more than
+    /// [`crate::ENUMERATION_LIMIT`] are left in either.
+    pub fn between(
+        brackets: &[Bracket],
+        before: &Tournament,
+        after: &Tournament,
+        scoring: &ScoringSystem,
+    ) -> Option<StandingsDelta> {
+        let before = TournamentView::new(before);
+        let after = TournamentView::new(after);
+        if !before.is_enumerable(ENUMERATION_LIMIT) || !after.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let (scores_before, scores_after) = (
+            before.score_batch_with(brackets, scoring),
+            after.score_batch_with(brackets, scoring),
+        );
+        let (ranks_before, ranks_after) = (
+            standings_ranks(&scores_before),
+            standings_ranks(&scores_after),
+        );
+
+        let entries = (0..brackets.len())
+            .map(|entry| EntryDelta {
+                entry,
+                points_gained: scores_after[entry].saturating_sub(scores_before[entry]),
+                rank_before: ranks_before[entry],
+                rank_after: ranks_after[entry],
+            })
+            .collect();
+
+        let can_win = |view: &TournamentView| {
+            let best_finishes = BestFinishes::for_view_with(brackets, view, scoring, 1);
+            brackets
+                .iter()
+                .map(|b| best_finishes.possible_finishes.get(b) == Some(&0))
+                .collect::<Vec<bool>>()
+        };
+        let (alive_before, alive_after) = (can_win(&before), can_win(&after));
+        let newly_eliminated = (0..brackets.len())
+            .filter(|&entry| alive_before[entry] && !alive_after[entry])
+            .collect();
+
+        Some(StandingsDelta {
+            entries,
+            newly_eliminated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn title_game_result() {
+        let before = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1),
+            vacated: 0,
+        };
+        let after = Tournament {
+            decisions: 1 << 1,
+            mask: COMPLETE_MASK,
+            vacated: 0,
+        };
+        // Identical except for the champion.
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 1 << 1 }];
+
+        let delta = StandingsDelta::between(&brackets, &before, &after, &ScoringSystem::standard())
+            .unwrap();
+
+        assert_eq!(delta.entries[0].points_gained, 0);
+        assert!(delta.entries[1].points_gained > 0);
+        assert_eq!(delta.entries[1].rank_before, 0);
+        assert_eq!(delta.entries[1].rank_after, 0);
+        assert_eq!(delta.entries[0].rank_change(), -1);
+        assert_eq!(delta.newly_eliminated, vec![0]);
+    }
+
+    #[test]
+    fn too_many_scenarios() {
+        let empty = Tournament::new(0, 0);
+        let brackets = [Bracket { decisions: 0 }];
+        let scoring = ScoringSystem::standard();
+        assert_eq!(
+            StandingsDelta::between(&brackets, &empty, &empty, &scoring),
+            None
+        );
+    }
+
+    #[test]
+    fn scoring_sets_points_gained() {
+        let before = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1),
+            vacated: 0,
+        };
+        let after = Tournament {
+            mask: COMPLETE_MASK,
+            ..before
+        };
+        let brackets = [Bracket { decisions: 0 }];
+        let doubling = ScoringSystem::doubling();
+        let delta = StandingsDelta::between(&brackets, &before, &after, &doubling).unwrap();
+        assert_eq!(delta.entries[0].points_gained, 32);
+    }
+}
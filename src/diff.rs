@@ -0,0 +1,78 @@
+use crate::{round_num_for_slot, Bracket, Decisions, POINTS_PER_ROUND};
+
+/// How much a game counts towards [`Bracket::similarity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Weighting {
+    /// Every game counts the same.
+    #[default]
+    PerPick,
+    /// Games count as much as a correct pick in their round is worth, so
+    /// agreeing on the champion outweighs most of the first round.
+    ByRound,
+}
+
+impl Weighting {
+    fn weight(self, slot: u8) -> f64 {
+        match self {
+            Weighting::PerPick => 1.0,
+            Weighting::ByRound => f64::from(POINTS_PER_ROUND[round_num_for_slot(slot) as usize]),
+        }
+    }
+}
+
+impl Bracket {
+    /// Game slots where the two brackets pick a different team, latest round
+    /// last.
+    pub fn diff(&self, other: &Bracket) -> Vec<u8> {
+        let ours = self.decision_team_slots();
+        let theirs = other.decision_team_slots();
+        (1..=63u8)
+            .rev()
+            .filter(|&slot| ours[slot as usize] != theirs[slot as usize])
+            .collect()
+    }
+
+    /// The weighted share of games the two brackets pick the same way, from
+    /// 0.0 (nothing in common) to 1.0 (identical).
+    pub fn similarity(&self, other: &Bracket, weighting: Weighting) -> f64 {
+        let total: f64 = (1..=63u8).map(|slot| weighting.weight(slot)).sum();
+        let differing: f64 = self
+            .diff(other)
+            .into_iter()
+            .map(|slot| weighting.weight(slot))
+            .sum();
+        1.0 - differing / total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_rounds_weigh_more() {
+        let chalk = Bracket { decisions: 0 };
+        // A different champion only.
+        let title = Bracket { decisions: 1 << 1 };
+        // A first-round upset of the chalk champion, which carries through
+        // every later round.
+        let early = Bracket { decisions: 1 << 32 };
+
+        assert_eq!(chalk.diff(&title), vec![1]);
+        assert_eq!(chalk.diff(&early), vec![32, 16, 8, 4, 2, 1]);
+        assert_eq!(chalk.similarity(&chalk, Weighting::ByRound), 1.0);
+
+        assert!(
+            chalk.similarity(&title, Weighting::PerPick)
+                > chalk.similarity(&early, Weighting::PerPick)
+        );
+        assert!(
+            chalk.similarity(&title, Weighting::ByRound)
+                > chalk.similarity(&early, Weighting::ByRound)
+        );
+        assert!(
+            chalk.similarity(&title, Weighting::ByRound)
+                < chalk.similarity(&title, Weighting::PerPick)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::conditions::{Outcome, Term};
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::{Bracket, Score, ENUMERATION_LIMIT};
+
+/// How far-fetched an entry's best way to finish first is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathDifficulty {
+    /// The negative natural log of the chance of the entry's most likely
+    /// winning scenario: 0.0 when it's certain, infinite when it can't win.
+    pub difficulty: f64,
+    /// That scenario's outcome in every open game, title game first.
+    pub scenario: Option<Term>,
+}
+
+impl PathDifficulty {
+    pub fn is_alive(&self) -> bool {
+        self.difficulty.is_finite()
+    }
+}
+
+impl TournamentView {
+    /// [`PathDifficulty`] per bracket, finishing first meaning alone or tied
+    /// as in [`TournamentView::win_condition`], with each scenario's chance
+    /// from `model`. Sorting the live entries by difficulty gives the
+    /// longest shots still alive.
+    ///
+    /// Every remaining scenario is scored, so this is `None` while more
+    /// than [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn path_difficulties<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+    ) -> Option<Vec<PathDifficulty>> {
+        self.path_difficulties_with(brackets, model, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::path_difficulties`] under `scoring`.
+    pub fn path_difficulties_with<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+        scoring: &ScoringSystem,
+    ) -> Option<Vec<PathDifficulty>> {
+        if !self.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let mut games = self.open_games();
+        games.sort_unstable();
+
+        let deltas = ScoreDeltaTable::for_view_with(self, brackets, scoring);
+        // Per bracket, the most likely winning scenario so far and its chance.
+        let mut best: Vec<Option<(f64, Term)>> = vec![None; brackets.len()];
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+        self.for_each_scenario(None, |team_slots| {
+            let probability = games.iter().fold(1.0, |acc, &slot| {
+                let slot = slot as usize;
+                // Open games always have two teams.
+                let (Some(a), Some(b)) = (team_slots[slot * 2], team_slots[slot * 2 + 1]) else {
+                    return acc;
+                };
+                let p = model.win_probability(a, b).clamp(0.0, 1.0);
+                acc * if team_slots[slot] == Some(a) {
+                    p
+                } else {
+                    1.0 - p
+                }
+            });
+            if probability == 0.0 {
+                return;
+            }
+            let ranks = deltas.ranks(team_slots, &mut scores);
+            for (i, &rank) in ranks.iter().enumerate() {
+                if rank > 0 || best[i].as_ref().is_some_and(|(p, _)| *p >= probability) {
+                    continue;
+                }
+                let scenario = games
+                    .iter()
+                    .map(|&slot| Outcome {
+                        slot,
+                        side: (team_slots[slot as usize] == team_slots[slot as usize * 2 + 1])
+                            as u8,
+                    })
+                    .collect();
+                best[i] = Some((probability, scenario));
+            }
+        });
+
+        let paths = best
+            .into_iter()
+            .map(|found| match found {
+                Some((probability, scenario)) => PathDifficulty {
+                    difficulty: -probability.ln(),
+                    scenario: Some(scenario),
+                },
+                None => PathDifficulty {
+                    difficulty: f64::INFINITY,
+                    scenario: None,
+                },
+            })
+            .collect();
+        Some(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn most_likely_winning_scenario_sets_the_difficulty() {
+        // Chalk through the Elite Eight: 64 and 80 meet in slot 2, 96 and
+        // 112 in slot 3.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let chalk = Bracket::from_decisions(0);
+        // Same semifinal picks, but 96 for the title.
+        let rival = Bracket::from_decisions(1 << 1);
+        // Took the second side of nearly every game, so its picks left are
+        // all out and it trails however they go.
+        let long_shot = Bracket::from_decisions(COMPLETE_MASK & !(1 << 1));
+        // The lower team slot wins four times in five.
+        let model = |a: u8, b: u8| if a < b { 0.8 } else { 0.2 };
+
+        let paths = view
+            .path_difficulties(&[chalk, rival, long_shot], &model)
+            .unwrap();
+        // Chalk wins when everything goes to form.
+        assert!((paths[0].difficulty - -(0.8f64 * 0.8 * 0.8).ln()).abs() < 1e-12);
+        let o = |slot, side| Outcome { slot, side };
+        assert_eq!(paths[0].scenario, Some(vec![o(1, 0), o(2, 0), o(3, 0)]));
+        // The rival needs one upset: 96 taking the title, or 80 beating 64.
+        assert!((paths[1].difficulty - -(0.8f64 * 0.8 * 0.2).ln()).abs() < 1e-12);
+        assert!(paths[1].difficulty > paths[0].difficulty);
+        assert!(!paths[2].is_alive());
+        assert_eq!(paths[2].scenario, None);
+
+        // Scoring only the rounds already played, the long shot is out
+        // whatever happens and the rival ties chalk in every scenario, so
+        // the likeliest one will do.
+        let early_rounds = ScoringSystem {
+            multipliers: [0, 1, 1, 1, 1, 0, 0],
+            ..ScoringSystem::standard()
+        };
+        let paths = view
+            .path_difficulties_with(&[chalk, rival, long_shot], &model, &early_rounds)
+            .unwrap();
+        assert!((paths[1].difficulty - -(0.8f64 * 0.8 * 0.8).ln()).abs() < 1e-12);
+        assert!(!paths[2].is_alive());
+    }
+}
@@ -0,0 +1,311 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::contest::{for_each_ranking, BracketContest};
+use crate::paths::PathsToVictory;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, ENUMERATION_LIMIT};
+
+/// How many remaining scenarios leave each bracket in each place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinishDistribution {
+    /// Per bracket in the order given, per zero-based place (ties sharing
+    /// the better one): the scenarios finishing there. Each row holds every
+    /// place and sums to `scenarios`, unless a memory budget cut it to the
+    /// top places.
+    pub counts: Vec<Vec<u64>>,
+    pub scenarios: u64,
+}
+
+impl FinishDistribution {
+    /// Bracket `entry`'s share of the scenarios finishing in `place`.
+    pub fn share(&self, entry: usize, place: usize) -> f64 {
+        PathsToVictory::share(self.counts[entry][place], self.scenarios)
+    }
+
+    /// Bracket `entry`'s share of the scenarios finishing in the top
+    /// `places`.
+    pub fn top_share(&self, entry: usize, places: usize) -> f64 {
+        let count = self.counts[entry].iter().take(places).sum();
+        PathsToVictory::share(count, self.scenarios)
+    }
+
+    /// Whether the rows hold every place.
+    pub fn is_complete(&self) -> bool {
+        self.counts
+            .first()
+            .is_none_or(|row| row.len() == self.counts.len())
+    }
+
+    /// Bracket `entry`'s average zero-based place over the scenarios.
+    /// Panics unless [`FinishDistribution::is_complete`].
+    pub fn mean_place(&self, entry: usize) -> f64 {
+        assert!(self.is_complete(), "the lower places weren't counted");
+        let total: u64 = self.counts[entry]
+            .iter()
+            .enumerate()
+            .map(|(place, &count)| place as u64 * count)
+            .sum();
+        PathsToVictory::share(total, self.scenarios)
+    }
+}
+
+/// A complete [`FinishDistribution`] whose rows are kept in a temporary
+/// file instead of memory, read back one at a time. The file is removed
+/// when this is dropped.
+#[derive(Debug)]
+pub struct SpilledDistribution {
+    path: PathBuf,
+    file: File,
+    entries: usize,
+    pub scenarios: u64,
+}
+
+impl SpilledDistribution {
+    /// A new, empty file under [`std::env::temp_dir`].
+    fn create(entries: usize) -> io::Result<SpilledDistribution> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let name = format!(
+            "madness-{}-{}.counts",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok(SpilledDistribution {
+            path,
+            file,
+            entries,
+            scenarios: 0,
+        })
+    }
+
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Bracket `entry`'s row: the scenarios finishing in each place.
+    pub fn row(&self, entry: usize) -> io::Result<Vec<u64>> {
+        assert!(entry < self.entries, "no entry {}", entry);
+        let row_bytes = std::mem::size_of::<u64>() * self.entries;
+        let mut bytes = vec![0; row_bytes];
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start((entry * row_bytes) as u64))?;
+        file.read_exact(&mut bytes)?;
+        Ok(bytes
+            .chunks_exact(std::mem::size_of::<u64>())
+            .map(|count| u64::from_le_bytes(count.try_into().expect("eight bytes")))
+            .collect())
+    }
+
+    /// Reads every row back into a [`FinishDistribution`].
+    pub fn load(&self) -> io::Result<FinishDistribution> {
+        let counts = (0..self.entries)
+            .map(|entry| self.row(entry))
+            .collect::<io::Result<_>>()?;
+        Ok(FinishDistribution {
+            counts,
+            scenarios: self.scenarios,
+        })
+    }
+}
+
+impl Drop for SpilledDistribution {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl TournamentView {
+    /// The [`FinishDistribution`] of every bracket. Every remaining
+    /// scenario is scored, so this is `None` while more than
+    /// [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn finish_distribution(&self, brackets: &[Bracket]) -> Option<FinishDistribution> {
+        self.finish_distribution_with(brackets, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::finish_distribution`] under `scoring`.
+    pub fn finish_distribution_with(
+        &self,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+    ) -> Option<FinishDistribution> {
+        self.distribution(brackets, scoring, brackets.len())
+    }
+
+    /// [`TournamentView::finish_distribution_with`] counting only as many
+    /// of the top places as fit in `bytes` of counts, at least the first.
+    /// Every row of a full distribution has a count per entry, so the
+    /// counts grow with the square of the pool; this keeps a large pool's
+    /// to a size of the caller's choosing, dropping the lower places. See
+    /// [`TournamentView::finish_distribution_spilled`] to keep them.
+    pub fn finish_distribution_within(
+        &self,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+        bytes: usize,
+    ) -> Option<FinishDistribution> {
+        let row_bytes = std::mem::size_of::<u64>() * brackets.len().max(1);
+        let places = (bytes / row_bytes).clamp(1, brackets.len().max(1));
+        self.distribution(brackets, scoring, places)
+    }
+
+    /// [`TournamentView::finish_distribution_with`] holding at most about
+    /// `bytes` of counts in memory, at least one row. The scenarios are
+    /// enumerated once for each batch of entries whose rows fit, and each
+    /// batch is written out to a file under [`std::env::temp_dir`] before
+    /// the next starts. `Ok(None)` while more than
+    /// [`crate::ENUMERATION_LIMIT`] scenarios are left.
+    pub fn finish_distribution_spilled(
+        &self,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+        bytes: usize,
+    ) -> io::Result<Option<SpilledDistribution>> {
+        if !self.is_enumerable(ENUMERATION_LIMIT) {
+            return Ok(None);
+        }
+        let entries = brackets.len();
+        let row_bytes = std::mem::size_of::<u64>() * entries.max(1);
+        let batch = (bytes / row_bytes).clamp(1, entries.max(1));
+        let mut spilled = SpilledDistribution::create(entries)?;
+        let contest = BracketContest::new(self, brackets, scoring);
+        let mut writer = BufWriter::new(&spilled.file);
+        for start in (0..entries).step_by(batch) {
+            let end = (start + batch).min(entries);
+            let mut counts = vec![0u64; (end - start) * entries];
+            spilled.scenarios = for_each_ranking(&contest, |ranks| {
+                for (row, &rank) in ranks[start..end].iter().enumerate() {
+                    counts[row * entries + rank] += 1;
+                }
+            });
+            for count in counts {
+                writer.write_all(&count.to_le_bytes())?;
+            }
+        }
+        writer.flush()?;
+        drop(writer);
+        Ok(Some(spilled))
+    }
+
+    fn distribution(
+        &self,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+        places: usize,
+    ) -> Option<FinishDistribution> {
+        if !self.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let contest = BracketContest::new(self, brackets, scoring);
+        let mut counts = vec![vec![0; places]; brackets.len()];
+        let scenarios = for_each_ranking(&contest, |ranks| {
+            for (row, &rank) in counts.iter_mut().zip(ranks) {
+                if rank < places {
+                    row[rank] += 1;
+                }
+            }
+        });
+        Some(FinishDistribution { counts, scenarios })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn counts_every_place() {
+        // Both semifinals and the title game are left.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+
+        let distribution = view.finish_distribution(&brackets).unwrap();
+        assert_eq!(distribution.scenarios, 8);
+        let (paths, _) = view.paths_to_victory(&brackets, 2);
+        for (entry, (row, path)) in distribution.counts.iter().zip(&paths).enumerate() {
+            assert_eq!(row.iter().sum::<u64>(), 8);
+            assert_eq!(row[0], path.wins);
+            assert_eq!(row[0] + row[1], path.cashes);
+            assert_eq!(distribution.top_share(entry, 2), path.cashes as f64 / 8.0);
+        }
+        assert_eq!(distribution.share(0, 0), 3.0 / 8.0);
+        assert!(distribution.mean_place(0) > 0.0);
+    }
+
+    #[test]
+    fn budget_keeps_the_top_places() {
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+        let scoring = ScoringSystem::standard();
+        let full = view.finish_distribution(&brackets).unwrap();
+        assert!(full.is_complete());
+
+        // Room for two places of three brackets.
+        let cut = view
+            .finish_distribution_within(&brackets, &scoring, 2 * 3 * 8)
+            .unwrap();
+        assert!(!cut.is_complete());
+        assert_eq!(cut.scenarios, full.scenarios);
+        for (row, full_row) in cut.counts.iter().zip(&full.counts) {
+            assert_eq!(row[..], full_row[..2]);
+        }
+        assert_eq!(cut.top_share(1, 2), full.top_share(1, 2));
+        // Too little room still keeps first place.
+        assert_eq!(
+            view.finish_distribution_within(&brackets, &scoring, 0)
+                .unwrap()
+                .counts[0]
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn spills_every_place() {
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+        let scoring = ScoringSystem::standard();
+        let full = view.finish_distribution(&brackets).unwrap();
+
+        // Room for two rows of three places, so two passes.
+        let spilled = view
+            .finish_distribution_spilled(&brackets, &scoring, 2 * 3 * 8)
+            .unwrap()
+            .unwrap();
+        assert_eq!(spilled.entries(), 3);
+        assert_eq!(spilled.row(2).unwrap(), full.counts[2]);
+        assert_eq!(spilled.load().unwrap(), full);
+
+        let path = spilled.path.clone();
+        assert!(path.exists());
+        drop(spilled);
+        assert!(!path.exists());
+
+        let everything = TournamentView::new(&Tournament::new(0, 0));
+        assert!(everything
+            .finish_distribution_spilled(&brackets, &scoring, 0)
+            .unwrap()
+            .is_none());
+    }
+}
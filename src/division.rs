@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use crate::pool::{Pool, Standing};
+use crate::{standings_ranks, Score};
+
+/// How a division's score comes from its members' scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    /// The sum of the best `n` members' scores, so divisions of different
+    /// sizes can compete.
+    BestOf(usize),
+}
+
+/// One row of the division standings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivisionStanding {
+    pub division: String,
+    pub score: Score,
+    /// Zero-based, ties sharing the better place.
+    pub rank: usize,
+    /// The division's entries as they stand in the whole pool, best first.
+    pub members: Vec<Standing>,
+}
+
+/// Division of each entry in a pool, keyed by owner.
+#[derive(Debug, Clone, Default)]
+pub struct Divisions {
+    by_owner: HashMap<String, String>,
+}
+
+impl Divisions {
+    pub fn new() -> Divisions {
+        Divisions::default()
+    }
+
+    pub fn insert(&mut self, owner: &str, division: &str) {
+        self.by_owner
+            .insert(owner.to_string(), division.to_string());
+    }
+
+    pub fn division(&self, owner: &str) -> Option<&str> {
+        self.by_owner.get(owner).map(String::as_str)
+    }
+
+    /// Standings of every division with an entry in `pool`, best first and
+    /// by name on a tie. Entries without a division are left out.
+    pub fn standings(&self, pool: &Pool, aggregate: Aggregate) -> Vec<DivisionStanding> {
+        let mut members: HashMap<&str, Vec<Standing>> = HashMap::new();
+        for standing in pool.leaderboard() {
+            if let Some(division) = self.division(&standing.owner) {
+                members.entry(division).or_default().push(standing);
+            }
+        }
+
+        let mut divisions: Vec<(&str, Vec<Standing>)> = members.into_iter().collect();
+        divisions.sort_by(|a, b| a.0.cmp(b.0));
+        let scores: Vec<Score> = divisions
+            .iter()
+            .map(|(_, members)| {
+                let counted = match aggregate {
+                    Aggregate::Sum => members.len(),
+                    Aggregate::BestOf(n) => n.min(members.len()),
+                };
+                // The leaderboard is already best first.
+                members[..counted]
+                    .iter()
+                    .fold(0, |acc: Score, m| acc.saturating_add(m.score))
+            })
+            .collect();
+
+        let mut standings: Vec<DivisionStanding> = divisions
+            .into_iter()
+            .zip(standings_ranks(&scores))
+            .zip(&scores)
+            .map(|(((division, members), rank), &score)| DivisionStanding {
+                division: division.to_string(),
+                score,
+                rank,
+                members,
+            })
+            .collect();
+        standings.sort_by_key(|s| s.rank);
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ScoringSystem;
+    use crate::{round_slot_mask, Bracket, Tournament};
+
+    #[test]
+    fn ranks_divisions_by_their_members() {
+        // The first round went chalk: every pick from slot 64's side scores.
+        let tournament = Tournament::new(0, round_slot_mask(1));
+        let mut pool = Pool::new(tournament, ScoringSystem::standard());
+        pool.insert("ann", Bracket::from_decisions(0));
+        pool.insert("bob", Bracket::from_decisions(0xFFFF_FFFF << 32));
+        pool.insert("cat", Bracket::from_decisions(0xFF << 32));
+        pool.insert("dan", Bracket::from_decisions(0));
+        let mut divisions = Divisions::new();
+        divisions.insert("ann", "Sales");
+        divisions.insert("bob", "Sales");
+        divisions.insert("cat", "Engineering");
+        divisions.insert("dan", "Engineering");
+
+        let score = |owner| pool.bracket(owner).unwrap().score(pool.tournament());
+        let (perfect, cat) = (score("ann"), score("cat"));
+        assert!(cat > 0 && cat < perfect);
+
+        let sum = divisions.standings(&pool, Aggregate::Sum);
+        assert_eq!(sum[0].division, "Engineering");
+        assert_eq!(sum[0].score, perfect + cat);
+        assert_eq!(sum[0].members[0].owner, "dan");
+        assert_eq!((sum[1].division.as_str(), sum[1].score), ("Sales", perfect));
+
+        // Each division's best entry is perfect so far.
+        let best = divisions.standings(&pool, Aggregate::BestOf(1));
+        assert_eq!(best[0].division, "Engineering");
+        assert_eq!((best[1].score, best[1].rank), (perfect, 0));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use crate::binary::BinaryError;
+use crate::code::CodeError;
+use crate::field::FieldError;
+use crate::json::JsonError;
+use crate::odds::OddsError;
+use crate::picks::PickError;
+use crate::pool::PoolError;
+use crate::printable::PrintableError;
+use crate::results::ResultError;
+use crate::validate::Diagnostic;
+
+/// Any error the crate reports, for callers that would rather handle one
+/// type. Each module's own error converts into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MadnessError {
+    /// Bit 0 isn't a game and must be clear.
+    ReservedBit,
+    /// The tournament's bits break an invariant, lowest slot first.
+    InvalidTournament(Vec<Diagnostic>),
+    Result(ResultError),
+    Field(FieldError),
+    Code(CodeError),
+    Pick(PickError),
+    Printable(PrintableError),
+    Odds(OddsError),
+    Pool(PoolError),
+    Json(JsonError),
+    Binary(BinaryError),
+}
+
+impl fmt::Display for MadnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MadnessError::ReservedBit => write!(f, "reserved bit 0 is set"),
+            MadnessError::InvalidTournament(diagnostics) => {
+                let lines: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+                write!(f, "invalid tournament: {}", lines.join("; "))
+            }
+            MadnessError::Result(e) => write!(f, "{}", e),
+            MadnessError::Field(e) => write!(f, "{}", e),
+            MadnessError::Code(e) => write!(f, "{}", e),
+            MadnessError::Pick(e) => write!(f, "{}", e),
+            MadnessError::Printable(e) => write!(f, "{}", e),
+            MadnessError::Odds(e) => write!(f, "{}", e),
+            MadnessError::Pool(e) => write!(f, "{}", e),
+            MadnessError::Json(e) => write!(f, "{}", e),
+            MadnessError::Binary(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MadnessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MadnessError::ReservedBit | MadnessError::InvalidTournament(_) => None,
+            MadnessError::Result(e) => Some(e),
+            MadnessError::Field(e) => Some(e),
+            MadnessError::Code(e) => Some(e),
+            MadnessError::Pick(e) => Some(e),
+            MadnessError::Printable(e) => Some(e),
+            MadnessError::Odds(e) => Some(e),
+            MadnessError::Pool(e) => Some(e),
+            MadnessError::Json(e) => Some(e),
+            MadnessError::Binary(e) => Some(e),
+        }
+    }
+}
+
+impl From<ResultError> for MadnessError {
+    fn from(e: ResultError) -> MadnessError {
+        MadnessError::Result(e)
+    }
+}
+
+impl From<FieldError> for MadnessError {
+    fn from(e: FieldError) -> MadnessError {
+        MadnessError::Field(e)
+    }
+}
+
+impl From<CodeError> for MadnessError {
+    fn from(e: CodeError) -> MadnessError {
+        MadnessError::Code(e)
+    }
+}
+
+impl From<PickError> for MadnessError {
+    fn from(e: PickError) -> MadnessError {
+        MadnessError::Pick(e)
+    }
+}
+
+impl From<PrintableError> for MadnessError {
+    fn from(e: PrintableError) -> MadnessError {
+        MadnessError::Printable(e)
+    }
+}
+
+impl From<OddsError> for MadnessError {
+    fn from(e: OddsError) -> MadnessError {
+        MadnessError::Odds(e)
+    }
+}
+
+impl From<PoolError> for MadnessError {
+    fn from(e: PoolError) -> MadnessError {
+        MadnessError::Pool(e)
+    }
+}
+
+impl From<JsonError> for MadnessError {
+    fn from(e: JsonError) -> MadnessError {
+        MadnessError::Json(e)
+    }
+}
+
+impl From<BinaryError> for MadnessError {
+    fn from(e: BinaryError) -> MadnessError {
+        MadnessError::Binary(e)
+    }
+}
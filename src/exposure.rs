@@ -0,0 +1,331 @@
+use crate::field::Field;
+use crate::paths::PathsToVictory;
+use crate::picks::Round;
+use crate::pool::Pool;
+use crate::rng::Rng;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::{round_num_for_slot, Bracket, Decisions, Score};
+
+impl Bracket {
+    /// The last round (1..=6) `team` (64..=127) is picked to win a game in,
+    /// or 0 when it's picked to lose its first game.
+    pub fn picked_through(&self, team: u8) -> u8 {
+        let team_slots = self.decision_team_slots();
+        let mut game = team / 2;
+        let mut round = 0;
+        while game >= 1 && team_slots[game as usize] == Some(team) {
+            round += 1;
+            game /= 2;
+        }
+        round
+    }
+}
+
+impl Pool {
+    /// Owners of the active entries picking the team `field` knows as
+    /// `team` to reach at least `round`, in entry order. `None` when no
+    /// team goes by that name.
+    pub fn entries_with(&self, field: &Field, team: &str, round: Round) -> Option<Vec<&str>> {
+        let team = field.find(team)?.slot;
+        let owners = self
+            .active_entries()
+            .into_iter()
+            .filter(|e| e.bracket.picked_through(team) >= round as u8)
+            .map(|e| e.owner.as_str())
+            .collect();
+        Some(owners)
+    }
+}
+
+/// How much of the pool is riding one team.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamExposure {
+    pub team: u8,
+    /// `share_through[r]` is the share of entries picking the team to win at
+    /// least through round `r` (index 0 is always 1.0).
+    pub share_through: [f64; 7],
+}
+
+/// How much of the pool's first place rides on a team's next game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamEquity {
+    pub team: u8,
+    /// The undecided game the team plays next.
+    pub slot: u8,
+    /// The share of entries picking the team to win that game.
+    pub picked: f64,
+    /// The share of samples the team won it in.
+    pub advances: f64,
+    /// Per bracket, the chance of finishing first (alone or tied) when the
+    /// team wins the game and when it loses.
+    pub if_advances: Vec<f64>,
+    pub if_loses: Vec<f64>,
+    /// The chance of finishing first that changes hands between the two:
+    /// half the summed differences, so 0.0 when the game can't matter.
+    pub impact: f64,
+}
+
+/// Exposure of every team in the field across a pool of brackets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureReport {
+    pub teams: Vec<TeamExposure>,
+}
+
+impl ExposureReport {
+    pub fn from_brackets(brackets: &[Bracket]) -> ExposureReport {
+        // Winning a round's game means having won every earlier one, so the
+        // count of round `r` wins is the count picked through `r`.
+        let mut counts = [[0usize; 7]; 64];
+        for bracket in brackets {
+            for (slot, team) in bracket.decision_team_slots().iter().enumerate().skip(1) {
+                if let Some(team) = team {
+                    counts[*team as usize - 64][round_num_for_slot(slot as u8) as usize] += 1;
+                }
+            }
+        }
+        for team_counts in &mut counts {
+            team_counts[0] = brackets.len();
+        }
+
+        let entries = brackets.len().max(1) as f64;
+        let teams = counts
+            .iter()
+            .enumerate()
+            .map(|(i, team_counts)| TeamExposure {
+                team: 64 + i as u8,
+                share_through: team_counts.map(|c| c as f64 / entries),
+            })
+            .collect();
+        ExposureReport { teams }
+    }
+
+    /// Sorts teams by their share through `round`, most popular first, keeping
+    /// bracket order among equal shares.
+    pub fn sort_by_round(&mut self, round: u8) {
+        let r = round as usize;
+        self.teams
+            .sort_by(|t1, t2| t2.share_through[r].total_cmp(&t1.share_through[r]));
+    }
+
+    /// The pool's swing teams: for every team with an undecided game next,
+    /// how the chances of finishing first move between it winning and
+    /// losing that game, from `samples` play-outs drawn from `model` as in
+    /// [`TournamentView::simulate_with`]. The report must be of `brackets`.
+    /// Biggest impact first.
+    pub fn equity<M: WinProbability + ?Sized>(
+        &self,
+        view: &TournamentView,
+        brackets: &[Bracket],
+        model: &M,
+        samples: u64,
+        seed: u64,
+        scoring: &ScoringSystem,
+    ) -> Vec<TeamEquity> {
+        // Each live team's next game: the first undecided one above it.
+        let next_games: Vec<(u8, u8)> = (64..128u8)
+            .filter_map(|team| {
+                let mut game = team / 2;
+                while game >= 1 && view.team_slots()[game as usize] == Some(team) {
+                    game /= 2;
+                }
+                let open = game >= 1 && view.undecided().contains(&game);
+                (open && view.team_slots()[team as usize] == Some(team)).then_some((team, game))
+            })
+            .collect();
+
+        let deltas = ScoreDeltaTable::for_view_with(view, brackets, scoring);
+        let mut rng = Rng::new(seed);
+        let mut team_slots = view.team_slots().to_vec();
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+        // Per team: samples it advanced in, and first places per bracket
+        // when it advanced and when it didn't.
+        let mut advanced = vec![0u64; next_games.len()];
+        let mut wins =
+            vec![[vec![0u64; brackets.len()], vec![0u64; brackets.len()]]; next_games.len()];
+        for _ in 0..samples {
+            view.play_out(model, &mut rng, &mut team_slots);
+            let ranks = deltas.ranks(&team_slots, &mut scores);
+            for (k, &(team, slot)) in next_games.iter().enumerate() {
+                let won = team_slots[slot as usize] == Some(team);
+                advanced[k] += won as u64;
+                let counts = &mut wins[k][!won as usize];
+                for (count, &rank) in counts.iter_mut().zip(&ranks) {
+                    *count += (rank == 0) as u64;
+                }
+            }
+        }
+
+        let mut equity: Vec<TeamEquity> = next_games
+            .iter()
+            .zip(advanced)
+            .zip(wins)
+            .map(|((&(team, slot), advanced), [won, lost])| {
+                let share = |counts: Vec<u64>, of: u64| -> Vec<f64> {
+                    counts
+                        .into_iter()
+                        .map(|c| PathsToVictory::share(c, of))
+                        .collect()
+                };
+                let if_advances = share(won, advanced);
+                let if_loses = share(lost, samples - advanced);
+                let impact = if advanced == 0 || advanced == samples {
+                    // Only one side was ever drawn.
+                    0.0
+                } else {
+                    if_advances
+                        .iter()
+                        .zip(&if_loses)
+                        .map(|(a, b)| (a - b).abs())
+                        .sum::<f64>()
+                        / 2.0
+                };
+                let picked = self
+                    .teams
+                    .iter()
+                    .find(|t| t.team == team)
+                    .map_or(0.0, |t| t.share_through[round_num_for_slot(slot) as usize]);
+                TeamEquity {
+                    team,
+                    slot,
+                    picked,
+                    advances: PathsToVictory::share(advanced, samples),
+                    if_advances,
+                    if_loses,
+                    impact,
+                }
+            })
+            .collect();
+        equity.sort_by(|a, b| b.impact.total_cmp(&a.impact));
+        equity
+    }
+
+    /// One row per team: the team slot, then its share through rounds 1..=6.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("team,round_1,round_2,round_3,round_4,round_5,round_6\n");
+        for team in &self.teams {
+            csv.push_str(&team.team.to_string());
+            for share in &team.share_through[1..] {
+                csv.push_str(&format!(",{:.4}", share));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_picked_through() {
+        let chalk = Bracket { decisions: 0 };
+        // Team 64 taken out in the Sweet 16 by the winner of slot 17.
+        let early_exit = Bracket { decisions: 1 << 8 };
+
+        assert_eq!(chalk.picked_through(64), 6);
+        assert_eq!(chalk.picked_through(65), 0);
+        assert_eq!(chalk.picked_through(80), 4);
+        assert_eq!(early_exit.picked_through(64), 2);
+    }
+
+    #[test]
+    fn pool_entries_by_team_name() {
+        let field = crate::testing::standard_field();
+        let mut pool = Pool::new(crate::Tournament::new(0, 0), ScoringSystem::standard());
+        pool.insert("ann", Bracket { decisions: 0 });
+        // Team 64 taken out in the Sweet 16 by the winner of slot 17.
+        pool.insert("bob", Bracket { decisions: 1 << 8 });
+        let name = field.team(64).name.clone();
+
+        assert_eq!(
+            pool.entries_with(&field, &name, Round::EliteEight),
+            Some(vec!["ann"])
+        );
+        assert_eq!(
+            pool.entries_with(&field, &name, Round::Sweet16),
+            Some(vec!["ann", "bob"])
+        );
+        pool.withdraw("bob", "asked out").unwrap();
+        assert_eq!(
+            pool.entries_with(&field, &name, Round::Sweet16),
+            Some(vec!["ann"])
+        );
+        assert_eq!(
+            pool.entries_with(&field, "Nowhere State", Round::Second),
+            None
+        );
+    }
+
+    #[test]
+    fn most_popular_final_four() {
+        let brackets = [
+            Bracket { decisions: 0 },
+            Bracket { decisions: 0 },
+            Bracket { decisions: 1 << 4 },
+        ];
+
+        let mut report = ExposureReport::from_brackets(&brackets);
+        report.sort_by_round(4);
+
+        assert_eq!(report.teams.len(), 64);
+        assert_eq!(report.teams[0].team, 80);
+        assert_eq!(report.teams[0].share_through[4], 1.0);
+        let team_64 = report.teams.iter().find(|t| t.team == 64).unwrap();
+        assert!((team_64.share_through[4] - 2.0 / 3.0).abs() < 1e-9);
+        assert!(report
+            .to_csv()
+            .starts_with("team,round_1,round_2,round_3,round_4,round_5,round_6\n80,"));
+    }
+
+    #[test]
+    fn equity_matches_the_exhaustive_split() {
+        // The Final Four is left: 64 v 80 in slot 2, 96 v 112 in slot 3.
+        let view = TournamentView::new(&crate::Tournament::new(0, crate::COMPLETE_MASK & !0b1110));
+        // Chalk, chalk but 96 for the title, and 80 over 64 to win it all.
+        let brackets = [
+            Bracket { decisions: 0 },
+            Bracket { decisions: 1 << 1 },
+            Bracket { decisions: 1 << 2 },
+        ];
+        let report = ExposureReport::from_brackets(&brackets);
+        let scoring = ScoringSystem::standard();
+        let equity = report.equity(&view, &brackets, &|_, _| 0.5, 20_000, 5, &scoring);
+        assert_eq!(equity.len(), 4);
+        assert!(equity.windows(2).all(|w| w[0].impact >= w[1].impact));
+
+        // Coin flips make every scenario equally likely, so the exact split
+        // counts them.
+        let deltas = ScoreDeltaTable::for_view_with(&view, &brackets, &scoring);
+        for row in &equity {
+            let mut firsts = [[0u64; 3]; 2];
+            let mut scenarios = [0u64; 2];
+            view.for_each_scenario(None, |team_slots| {
+                let lost = (team_slots[row.slot as usize] != Some(row.team)) as usize;
+                scenarios[lost] += 1;
+                let ranks = deltas.ranks(team_slots, &mut Vec::new());
+                for (count, rank) in firsts[lost].iter_mut().zip(ranks) {
+                    *count += (rank == 0) as u64;
+                }
+            });
+            let close = |a: f64, count: u64, of: u64| (a - count as f64 / of as f64).abs() < 0.03;
+            for (chances, (counts, of)) in [&row.if_advances, &row.if_loses]
+                .into_iter()
+                .zip(firsts.iter().zip(scenarios))
+            {
+                for (&chance, &count) in chances.iter().zip(counts) {
+                    assert!(close(chance, count, of));
+                }
+            }
+            assert!((row.advances - 0.5).abs() < 0.03);
+        }
+        let team_80 = equity.iter().find(|e| e.team == 80).unwrap();
+        assert_eq!((team_80.slot, team_80.picked), (2, 1.0 / 3.0));
+        // With 80 through, the third entry can't lose, so 80's game is the
+        // bigger swing.
+        assert!([64, 80].contains(&equity[0].team));
+    }
+}
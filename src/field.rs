@@ -0,0 +1,219 @@
+use std::fmt;
+
+use crate::format::{BracketFormat, FormatError};
+use crate::names::AliasTable;
+use crate::{Bracket, Decisions, ROUNDS};
+
+/// A team in the field, at its team slot (64..=127).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Team {
+    pub slot: u8,
+    pub name: String,
+    pub seed: u8,
+    pub region: String,
+    /// Set with [`Field::set_conference`].
+    pub conference: Option<String>,
+}
+
+/// The 64 teams by team slot, so results and picks can be reported as teams
+/// instead of slot numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    teams: Vec<Team>,
+    format: BracketFormat,
+    aliases: AliasTable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    /// A field has exactly 64 teams.
+    TeamCount(usize),
+    /// A region's seeds aren't 1 through 16, once each.
+    Seeds(FormatError),
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::TeamCount(count) => write!(f, "field has {} teams, not 64", count),
+            FieldError::Seeds(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Enters a field in bracket order: top of the bracket first, sixteen
+/// teams per region.
+#[derive(Debug, Clone, Default)]
+pub struct FieldBuilder {
+    region: String,
+    teams: Vec<Team>,
+}
+
+impl FieldBuilder {
+    pub fn new() -> FieldBuilder {
+        FieldBuilder::default()
+    }
+
+    /// Names the region of the teams pushed after this.
+    pub fn region(&mut self, name: &str) {
+        self.region = name.to_string();
+    }
+
+    /// Adds the team in the next team slot.
+    pub fn push(&mut self, name: &str, seed: u8) {
+        self.teams.push(Team {
+            slot: 64 + self.teams.len() as u8,
+            name: name.to_string(),
+            seed,
+            region: self.region.clone(),
+            conference: None,
+        });
+    }
+
+    pub fn build(self) -> Result<Field, FieldError> {
+        if self.teams.len() != 64 {
+            return Err(FieldError::TeamCount(self.teams.len()));
+        }
+        let regions = self
+            .teams
+            .chunks(16)
+            .map(|region| region.iter().map(|team| team.seed).collect())
+            .collect();
+        let format = BracketFormat::new(ROUNDS, regions).map_err(FieldError::Seeds)?;
+        Ok(Field {
+            teams: self.teams,
+            format,
+            aliases: AliasTable::standard(),
+        })
+    }
+}
+
+impl Field {
+    /// The team in `slot` (64..=127).
+    pub fn team(&self, slot: u8) -> &Team {
+        &self.teams[slot as usize - 64]
+    }
+
+    /// Renames the team in `slot`, e.g. once a play-in decides who fills it.
+    pub fn set_name(&mut self, slot: u8, name: &str) {
+        self.teams[slot as usize - 64].name = name.to_string();
+    }
+
+    /// Puts the team in `slot` in `conference`.
+    pub fn set_conference(&mut self, slot: u8, conference: &str) {
+        self.teams[slot as usize - 64].conference = Some(conference.to_string());
+    }
+
+    /// Looks a team up by name, ignoring the differences
+    /// [`normalize_team_name`] ignores and taking any spelling the field's
+    /// aliases know, so "UConn" finds Connecticut.
+    ///
+    /// [`normalize_team_name`]: crate::names::normalize_team_name
+    pub fn find(&self, name: &str) -> Option<&Team> {
+        self.teams
+            .iter()
+            .find(|team| self.aliases.same_team(name, &team.name))
+    }
+
+    /// The names [`Field::find`] knows teams by besides their own:
+    /// [`AliasTable::standard`] unless replaced.
+    pub fn aliases(&self) -> &AliasTable {
+        &self.aliases
+    }
+
+    /// Looks names up through `aliases` from now on.
+    pub fn set_aliases(&mut self, aliases: AliasTable) {
+        self.aliases = aliases;
+    }
+
+    /// The field's seeds as a format, to score with them as
+    /// [`ScoringSystem::format`].
+    ///
+    /// [`ScoringSystem::format`]: crate::scoring::ScoringSystem::format
+    pub fn format(&self) -> BracketFormat {
+        self.format
+    }
+
+    /// The team that won (or was picked to win) the game in `slot`.
+    pub fn winner<D: Decisions + ?Sized>(&self, decisions: &D, slot: u8) -> Option<&Team> {
+        decisions.decision_team_slots()[slot as usize].map(|team| self.team(team))
+    }
+
+    pub fn champion(&self, bracket: &Bracket) -> &Team {
+        self.team(bracket.champion())
+    }
+
+    pub fn final_four(&self, bracket: &Bracket) -> [&Team; 4] {
+        bracket.final_four().map(|team| self.team(team))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ScoringSystem;
+    use crate::testing::{standard_field, standard_field_builder};
+    use crate::{round_slot_mask, Tournament};
+
+    #[test]
+    fn builds_in_bracket_order() {
+        let field = standard_field();
+        assert_eq!(field.team(64).name, "East 1");
+        assert_eq!(field.team(81).region, "West");
+        assert_eq!(field.team(81).seed, 16);
+        assert_eq!(field.find("midwest 2").map(|t| t.slot), Some(126));
+        let mut renamed = field.clone();
+        renamed.set_name(64, "Connecticut");
+        assert_eq!(renamed.find("UConn").map(|t| t.slot), Some(64));
+        let mut aliases = AliasTable::new();
+        aliases.insert("Huskies", "Connecticut");
+        renamed.set_aliases(aliases);
+        assert_eq!(renamed.find("Huskies").map(|t| t.slot), Some(64));
+        assert_eq!(renamed.find("UConn"), None);
+        assert_eq!(field.format(), BracketFormat::standard());
+
+        let chalk = Bracket::from_decisions(0);
+        assert_eq!(field.champion(&chalk).name, "East 1");
+        assert_eq!(field.final_four(&chalk)[3].name, "Midwest 1");
+        assert_eq!(field.winner(&chalk, 63).map(|t| t.seed), Some(2));
+    }
+
+    #[test]
+    fn rejects_bad_fields() {
+        let mut short = FieldBuilder::new();
+        short.push("Only", 1);
+        assert_eq!(short.build(), Err(FieldError::TeamCount(1)));
+
+        let mut doubled = standard_field_builder();
+        doubled.teams[20].seed = 1;
+        assert_eq!(
+            doubled.build(),
+            Err(FieldError::Seeds(FormatError::Seeds { region: 1 }))
+        );
+    }
+
+    #[test]
+    fn scores_with_the_fields_seeds() {
+        // The East's 1 and 16 seeds trade places.
+        let mut swapped = standard_field_builder();
+        swapped.teams[0].seed = 16;
+        swapped.teams[1].seed = 1;
+        let field = swapped.build().unwrap();
+        let scoring = ScoringSystem {
+            format: field.format(),
+            ..ScoringSystem::standard()
+        };
+
+        // Everything through the first round went to the top side.
+        let tournament = Tournament::new(0, round_slot_mask(1));
+        let chalk = Bracket::from_decisions(0);
+        let gained = scoring.points(32, 64, None) - ScoringSystem::standard().points(32, 64, None);
+        assert_eq!(gained, 15);
+        assert_eq!(
+            chalk.score_with(&tournament, &scoring),
+            chalk.score_with(&tournament, &ScoringSystem::standard()) + gained
+        );
+    }
+}
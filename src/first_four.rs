@@ -0,0 +1,250 @@
+use crate::field::Field;
+use crate::scoring::ScoringSystem;
+use crate::{Bracket, Decisions, Score, Tournament, POINTS_PER_ROUND};
+
+/// A play-in game: two teams playing for team slot `slot` (64..=127) of
+/// the main draw. The winner takes the slot's seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayIn {
+    pub slot: u8,
+    pub teams: [String; 2],
+}
+
+/// Results or picks plus the four First Four games, which sit outside the
+/// 63-game tree. `play_ins[game]` is `0` when the first of that game's
+/// teams wins it, `1` for the second, and `None` while it's undecided (or
+/// unpicked). The main draw only sees team slots, so picks there follow
+/// whoever wins the play-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WithFirstFour<D> {
+    pub main: D,
+    pub play_ins: [Option<u8>; 4],
+}
+
+impl<D: Decisions> WithFirstFour<D> {
+    pub fn new(main: D) -> WithFirstFour<D> {
+        WithFirstFour {
+            main,
+            play_ins: [None; 4],
+        }
+    }
+
+    /// Records (or picks) `side` winning play-in `game` (0..4).
+    pub fn set_play_in(&mut self, game: usize, side: u8) {
+        assert!(side <= 1, "winner must be 0 or 1, got {}", side);
+        self.play_ins[game] = Some(side);
+    }
+}
+
+/// What main-draw picks of a play-in slot are worth when the entry picked
+/// the team that lost the play-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayInLoser {
+    /// The play-in winner stands in for the pick, so the slot's main-draw
+    /// picks score as usual.
+    Replacement,
+    /// The picked team never played, so the slot's main-draw picks score
+    /// nothing.
+    DeadPick,
+}
+
+/// The First Four games of a 68-team field, what a correct play-in pick is
+/// worth and how picks of a play-in loser are treated. Set `points` and
+/// `loser_rule` after [`FirstFour::new`] to change them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirstFour {
+    pub games: [PlayIn; 4],
+    pub points: Score,
+    pub loser_rule: PlayInLoser,
+}
+
+impl FirstFour {
+    /// Each game must be for a different team slot. A correct play-in pick
+    /// is worth a first-round pick before the seed bonus, and the play-in
+    /// winner replaces a losing pick.
+    pub fn new(games: [PlayIn; 4]) -> FirstFour {
+        for (i, game) in games.iter().enumerate() {
+            assert!((64..128).contains(&game.slot), "no team slot {}", game.slot);
+            assert!(
+                games[..i].iter().all(|other| other.slot != game.slot),
+                "two play-ins for slot {}",
+                game.slot
+            );
+        }
+        FirstFour {
+            games,
+            points: POINTS_PER_ROUND[1] as Score,
+            loser_rule: PlayInLoser::Replacement,
+        }
+    }
+
+    /// The winner of play-in `game` in `results`, once it's decided.
+    pub fn winner<D: Decisions>(&self, game: usize, results: &WithFirstFour<D>) -> Option<&str> {
+        let side = results.play_ins[game]?;
+        Some(&self.games[game].teams[side as usize])
+    }
+
+    /// `field` with each decided play-in's winner in its team slot, so
+    /// names resolve to the team that's actually playing. Slots still
+    /// waiting on a play-in keep the field's placeholder.
+    pub fn resolve<D: Decisions>(&self, field: &Field, results: &WithFirstFour<D>) -> Field {
+        let mut resolved = field.clone();
+        for (game, play_in) in self.games.iter().enumerate() {
+            if let Some(winner) = self.winner(game, results) {
+                resolved.set_name(play_in.slot, winner);
+            }
+        }
+        resolved
+    }
+
+    pub fn points_for(
+        &self,
+        bracket: &WithFirstFour<Bracket>,
+        tournament: &WithFirstFour<Tournament>,
+    ) -> Score {
+        bracket
+            .play_ins
+            .iter()
+            .zip(&tournament.play_ins)
+            .filter(|(pick, result)| pick.is_some() && pick == result)
+            .fold(0, |acc: Score, _| acc.saturating_add(self.points))
+    }
+
+    /// The bracket's score including its play-in picks.
+    pub fn score(
+        &self,
+        bracket: &WithFirstFour<Bracket>,
+        tournament: &WithFirstFour<Tournament>,
+    ) -> Score {
+        self.score_with(bracket, tournament, &ScoringSystem::standard())
+    }
+
+    /// [`FirstFour::score`] with the main draw under `scoring`.
+    pub fn score_with(
+        &self,
+        bracket: &WithFirstFour<Bracket>,
+        tournament: &WithFirstFour<Tournament>,
+        scoring: &ScoringSystem,
+    ) -> Score {
+        bracket
+            .main
+            .score_with(&tournament.main, scoring)
+            .saturating_sub(self.dead_points(bracket, tournament, scoring))
+            .saturating_add(self.points_for(bracket, tournament))
+    }
+
+    /// Main-draw points `loser_rule` takes back: under
+    /// [`PlayInLoser::DeadPick`], those from correct picks of a slot whose
+    /// play-in the entry picked the other way.
+    fn dead_points(
+        &self,
+        bracket: &WithFirstFour<Bracket>,
+        tournament: &WithFirstFour<Tournament>,
+        scoring: &ScoringSystem,
+    ) -> Score {
+        if self.loser_rule == PlayInLoser::Replacement {
+            return 0;
+        }
+        let results = tournament.main.decision_team_slots();
+        let picks = bracket.main.decision_team_slots();
+        let mut dead: Score = 0;
+        for (game, play_in) in self.games.iter().enumerate() {
+            let (Some(pick), Some(result)) = (bracket.play_ins[game], tournament.play_ins[game])
+            else {
+                continue;
+            };
+            if pick == result {
+                continue;
+            }
+            // The games the slot's team can reach, first round up.
+            let team = Some(play_in.slot);
+            let mut slot = play_in.slot / 2;
+            while slot > 0 {
+                if picks[slot as usize] == team && results[slot as usize] == team {
+                    dead = dead.saturating_add(scoring.points_in(slot, play_in.slot, &results));
+                }
+                slot /= 2;
+            }
+        }
+        dead
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_slot_mask;
+    use crate::testing::standard_field;
+
+    fn first_four() -> FirstFour {
+        // Two 16 seeds and two 11 seeds.
+        let games = [
+            (65, "Texas Southern", "Mount St. Mary's"),
+            (81, "Norfolk St.", "Appalachian St."),
+            (105, "Drake", "Wichita St."),
+            (121, "UCLA", "Michigan St."),
+        ];
+        FirstFour::new(games.map(|(slot, a, b)| PlayIn {
+            slot,
+            teams: [a.to_string(), b.to_string()],
+        }))
+    }
+
+    #[test]
+    fn play_in_winners_fill_their_slots() {
+        let field = standard_field();
+        let first_four = first_four();
+
+        let mut tournament = WithFirstFour::new(Tournament::new(0, round_slot_mask(1)));
+        tournament.set_play_in(0, 1);
+        tournament.set_play_in(3, 0);
+        let resolved = first_four.resolve(&field, &tournament);
+        assert_eq!(resolved.team(65).name, "Mount St. Mary's");
+        assert_eq!(resolved.team(65).seed, 16);
+        assert_eq!(resolved.team(121).name, "UCLA");
+        assert_eq!(resolved.team(81).name, "West 16");
+
+        // Picks in the main draw follow the slot, whoever won the play-in.
+        let mut bracket = WithFirstFour::new(Bracket::from_decisions(0));
+        bracket.set_play_in(0, 1);
+        bracket.set_play_in(3, 1);
+        assert_eq!(
+            first_four.points_for(&bracket, &tournament),
+            first_four.points
+        );
+        assert_eq!(
+            first_four.score(&bracket, &tournament),
+            bracket.main.score(&tournament.main) + first_four.points
+        );
+
+        // With slot 121 winning its first game and picked to, counting a
+        // pick of its play-in loser as dead takes that win back; the other
+        // first-round picks stand.
+        let first_four = FirstFour {
+            points: 3,
+            loser_rule: PlayInLoser::DeadPick,
+            ..first_four
+        };
+        tournament.main = Tournament::new(1 << 60, round_slot_mask(1));
+        bracket.main = Bracket::from_decisions(1 << 60);
+        let scoring = ScoringSystem::standard();
+        let results = tournament.main.decision_team_slots();
+        assert_eq!(results[60], Some(121));
+        assert_eq!(
+            first_four.score(&bracket, &tournament),
+            bracket.main.score(&tournament.main) + 3 - scoring.points_in(60, 121, &results)
+        );
+
+        // Slot 121 picked and won all the way; every one of those points is
+        // taken back, however big.
+        let champion = 1 << 60 | 1 << 7 | 1 << 3 | 1 << 1;
+        tournament.main = Tournament::new(champion, crate::COMPLETE_MASK);
+        bracket.main = Bracket::from_decisions(champion);
+        let huge = ScoringSystem {
+            multipliers: [Score::MAX; 7],
+            ..scoring
+        };
+        assert_eq!(bracket.main.champion(), 121);
+        assert_eq!(first_four.score_with(&bracket, &tournament, &huge), 3);
+    }
+}
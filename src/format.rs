@@ -0,0 +1,282 @@
+use std::fmt;
+
+use crate::{round_slot_mask, Decisions, ROUNDS, SEED_ORDER};
+
+/// The shape of an event: how many rounds it plays and the seed in each
+/// first-round position of each region, top to bottom.
+///
+/// An event with fewer than six rounds sits at the top of the 64-team tree.
+/// Its teams take every `64 >> rounds`-th team slot and the games below its
+/// first round are padding, decided for the even side like a bye, so the
+/// rest of the crate sees an ordinary tournament.
+///
+/// Picks are scored under a format through [`ScoringSystem::format`], which
+/// takes rounds and seeds from it.
+///
+/// [`ScoringSystem::format`]: crate::scoring::ScoringSystem::format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BracketFormat {
+    rounds: u8,
+    regions: u8,
+    // The seed in each team slot, 64 first, or 0 where there's no team.
+    seeds: [u8; 64],
+}
+
+/// Why a format doesn't describe a bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// Only 1 to 6 rounds fit in the tree.
+    Rounds(u8),
+    /// The regions aren't a power of two of the same size, filling the
+    /// rounds between them.
+    Regions(usize),
+    /// A region's order isn't each seed from 1 to its size exactly once.
+    Seeds { region: usize },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Rounds(rounds) => write!(f, "{} rounds don't fit a bracket", rounds),
+            FormatError::Regions(regions) => {
+                write!(f, "{} regions don't divide the field evenly", regions)
+            }
+            FormatError::Seeds { region } => {
+                write!(f, "region {} doesn't place each seed exactly once", region)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl BracketFormat {
+    /// The NCAA draw, men's and women's: four regions seeded 1 v 16, 8 v 9,
+    /// ... over six rounds.
+    pub fn standard() -> BracketFormat {
+        let mut seeds = [0; 64];
+        for (offset, seed) in seeds.iter_mut().enumerate() {
+            *seed = SEED_ORDER[offset % 16];
+        }
+        BracketFormat {
+            rounds: ROUNDS,
+            regions: 4,
+            seeds,
+        }
+    }
+
+    /// `rounds` rounds with one order per region, top region first.
+    pub fn new(rounds: u8, regions: Vec<Vec<u8>>) -> Result<BracketFormat, FormatError> {
+        if !(1..=ROUNDS).contains(&rounds) {
+            return Err(FormatError::Rounds(rounds));
+        }
+        let teams = 1usize << rounds;
+        let count = regions.len();
+        if !count.is_power_of_two() || (count > 1 && count > teams / 2) {
+            return Err(FormatError::Regions(count));
+        }
+        let spacing = 1 << (ROUNDS - rounds);
+        let mut seeds = [0; 64];
+        for (region, order) in regions.iter().enumerate() {
+            if order.len() != teams / count {
+                return Err(FormatError::Regions(count));
+            }
+            let mut seen = vec![false; order.len() + 1];
+            for (position, &seed) in order.iter().enumerate() {
+                if seed == 0 || seed as usize > order.len() || seen[seed as usize] {
+                    return Err(FormatError::Seeds { region });
+                }
+                seen[seed as usize] = true;
+                seeds[(region * order.len() + position) * spacing] = seed;
+            }
+        }
+        Ok(BracketFormat {
+            rounds,
+            regions: count as u8,
+            seeds,
+        })
+    }
+
+    /// A single region seeded like the NCAA's, as many conference
+    /// tournaments are.
+    pub fn single_region(rounds: u8) -> Result<BracketFormat, FormatError> {
+        if !(1..=ROUNDS).contains(&rounds) {
+            return Err(FormatError::Rounds(rounds));
+        }
+        BracketFormat::new(rounds, vec![standard_order(1 << rounds)])
+    }
+
+    pub fn rounds(&self) -> u8 {
+        self.rounds
+    }
+
+    pub fn regions(&self) -> usize {
+        self.regions as usize
+    }
+
+    pub fn teams(&self) -> usize {
+        1 << self.rounds
+    }
+
+    /// The team slot (64..=127) of the `index`th team from the top.
+    pub fn team_slot(&self, index: usize) -> u8 {
+        assert!(index < self.teams(), "no team {}", index);
+        64 + (index << (ROUNDS - self.rounds)) as u8
+    }
+
+    /// The position from the top of the team in `team`, if the format puts
+    /// one there.
+    fn index(&self, team: u8) -> Option<usize> {
+        let offset = team.checked_sub(64)? as usize;
+        let spacing = 1 << (ROUNDS - self.rounds);
+        (offset < 64 && offset.is_multiple_of(spacing)).then_some(offset / spacing)
+    }
+
+    /// The seed of the team in `team` (64..=127).
+    pub fn seed(&self, team: u8) -> Option<u8> {
+        let seed = *self.seeds.get(team.checked_sub(64)? as usize)?;
+        (seed != 0).then_some(seed)
+    }
+
+    /// The region, top first, of the team in `team` (64..=127).
+    pub fn region(&self, team: u8) -> Option<usize> {
+        Some(self.index(team)? / (self.teams() / self.regions()))
+    }
+
+    /// The event's round (1 = its first) of the game in `slot`, or `None`
+    /// for padding.
+    pub fn round(&self, slot: u8) -> Option<u8> {
+        let depth = (u8::BITS - slot.leading_zeros()) as u8;
+        (1..=self.rounds)
+            .contains(&depth)
+            .then(|| self.rounds + 1 - depth)
+    }
+
+    /// Bits of the game slots played in the event's `round`.
+    pub fn round_slot_mask(&self, round: u8) -> u64 {
+        assert!((1..=self.rounds).contains(&round), "no round {}", round);
+        round_slot_mask(round + ROUNDS - self.rounds)
+    }
+
+    /// Bits of the padding games below the event's first round.
+    pub fn padding(&self) -> u64 {
+        (1..=ROUNDS - self.rounds).fold(0, |mask, round| mask | round_slot_mask(round))
+    }
+
+    /// Decides the padding games. Call this on the tournament before
+    /// building a view, and on brackets before scoring, as with byes.
+    pub fn apply<D: Decisions + ?Sized>(&self, decisions: &mut D) {
+        for slot in 1..64u8 {
+            if self.padding() & (1 << slot) != 0 {
+                decisions.set_decision(slot, 0);
+            }
+        }
+    }
+}
+
+impl Default for BracketFormat {
+    fn default() -> BracketFormat {
+        BracketFormat::standard()
+    }
+}
+
+/// Seeds `1..=teams` in the NCAA's pairings: `SEED_ORDER` for 16, its top
+/// seeds in place for fewer, and each seed split into it and its opponent
+/// for more.
+fn standard_order(teams: usize) -> Vec<u8> {
+    let mut order: Vec<u8> = SEED_ORDER
+        .iter()
+        .copied()
+        .filter(|&seed| seed as usize <= teams)
+        .collect();
+    while order.len() < teams {
+        let size = order.len() as u8 * 2;
+        order = order
+            .iter()
+            .flat_map(|&seed| [seed, size + 1 - seed])
+            .collect();
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ScoringSystem;
+    use crate::view::TournamentView;
+    use crate::{
+        points_for, points_for_pick, round_num_for_slot, seed_for_slot, Bracket, Score, Tournament,
+        COMPLETE_MASK,
+    };
+
+    #[test]
+    fn standard_format_matches_the_builtin_layout() {
+        let format = BracketFormat::standard();
+        assert_eq!(standard_order(16), SEED_ORDER);
+        for team in 64..=127 {
+            assert_eq!(format.seed(team), Some(seed_for_slot(team)));
+            assert_eq!(format.region(team), Some((team as usize - 64) / 16));
+        }
+        for slot in 1..64 {
+            assert_eq!(format.round(slot), Some(round_num_for_slot(slot)));
+            assert_eq!(
+                ScoringSystem::standard().points(slot, 64, None),
+                points_for_pick(slot, 64)
+            );
+        }
+        assert_eq!(format.padding(), 0);
+    }
+
+    #[test]
+    fn smaller_events_sit_at_the_top_of_the_tree() {
+        // An eight-team conference tournament.
+        let format = BracketFormat::single_region(3).unwrap();
+        assert_eq!(format.teams(), 8);
+        assert_eq!(format.team_slot(1), 72);
+        assert_eq!(format.seed(72), Some(8));
+        assert_eq!(standard_order(8), [1, 8, 5, 4, 6, 3, 7, 2]);
+        assert_eq!(format.seed(65), None);
+        assert_eq!(format.round(4), Some(1));
+        assert_eq!(format.round(8), None);
+        assert_eq!(format.round_slot_mask(1), 0xF0);
+        assert_eq!(format.padding(), COMPLETE_MASK & !0xFE);
+
+        let mut tournament = Tournament::new(0, 0);
+        format.apply(&mut tournament);
+        let view = TournamentView::new(&tournament);
+        assert_eq!(view.team_slots()[8], Some(64));
+        assert_eq!(view.team_slots()[9], Some(72));
+        assert_eq!(view.undecided(), &[7, 6, 5, 4, 3, 2, 1]);
+
+        // The top side wins every game, and padding scores nothing.
+        let mut tournament = Tournament::new(0, COMPLETE_MASK);
+        format.apply(&mut tournament);
+        let chalk = Bracket::from_decisions(0);
+        let view = TournamentView::new(&tournament);
+        let scoring = ScoringSystem {
+            format,
+            ..ScoringSystem::standard()
+        };
+        // Seeds 1, 5, 6 and 7 win the first round, then 1 and 6.
+        let first_round: Score = [1, 5, 6, 7].iter().map(|&seed| points_for(1, seed)).sum();
+        assert_eq!(
+            view.score_with(&chalk, &scoring),
+            first_round + points_for(2, 1) + points_for(2, 6) + points_for(3, 1)
+        );
+    }
+
+    #[test]
+    fn formats_must_fill_the_rounds() {
+        assert_eq!(BracketFormat::new(7, vec![]), Err(FormatError::Rounds(7)));
+        assert_eq!(
+            BracketFormat::new(4, vec![vec![1, 2, 3]; 3]),
+            Err(FormatError::Regions(3))
+        );
+        assert_eq!(
+            BracketFormat::new(2, vec![vec![1, 1], vec![1, 2]]),
+            Err(FormatError::Seeds { region: 0 })
+        );
+        let women = BracketFormat::new(6, vec![SEED_ORDER.to_vec(); 4]).unwrap();
+        assert_eq!(women, BracketFormat::standard());
+    }
+}
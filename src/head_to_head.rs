@@ -0,0 +1,170 @@
+use crate::rng::Rng;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Tournament};
+
+// More remaining scenarios than this are sampled instead of enumerated.
+const ENUMERATION_LIMIT: u128 = 1 << 20;
+const SAMPLES: u64 = 1 << 16;
+const SAMPLE_SEED: u64 = 0x4832_4832;
+
+/// How two brackets do against each other over the remaining scenarios.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadToHead {
+    pub a_wins: u64,
+    pub b_wins: u64,
+    pub ties: u64,
+    /// False when there were too many scenarios to enumerate and the counts
+    /// are over random ones, every winner equally likely.
+    pub exact: bool,
+    /// Undecided games whose result moves A's chances, biggest swing first.
+    pub pivotal: Vec<PivotalGame>,
+}
+
+/// An undecided game and how much its winner matters to a head-to-head.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotalGame {
+    pub slot: u8,
+    /// The winner giving A the best share of wins, and the winner giving it
+    /// the worst.
+    pub best_for_a: u8,
+    pub best_for_b: u8,
+    /// A's share of wins with `best_for_a` winning, minus its share with
+    /// `best_for_b` winning.
+    pub swing: f64,
+}
+
+/// Plays out the rest of `tournament` for brackets `a` and `b` under the
+/// standard scoring, enumerating every scenario when there aren't too
+/// many and sampling them otherwise.
+pub fn compare(a: &Bracket, b: &Bracket, tournament: &Tournament) -> HeadToHead {
+    compare_with(a, b, tournament, &ScoringSystem::standard())
+}
+
+/// [`compare`] under `scoring`.
+pub fn compare_with(
+    a: &Bracket,
+    b: &Bracket,
+    tournament: &Tournament,
+    scoring: &ScoringSystem,
+) -> HeadToHead {
+    let view = TournamentView::new(tournament);
+    let deltas = ScoreDeltaTable::for_view_with(&view, &[*a, *b], scoring);
+    let undecided = view.undecided();
+    let exact = tournament.is_enumerable(ENUMERATION_LIMIT);
+    let mut result = HeadToHead {
+        a_wins: 0,
+        b_wins: 0,
+        ties: 0,
+        exact,
+        pivotal: vec![],
+    };
+    // Per undecided game and winning team: A's wins and the scenarios.
+    let mut by_winner = vec![[(0u64, 0u64); 128]; undecided.len()];
+
+    let mut record = |team_slots: &[Option<u8>]| {
+        let (score_a, score_b) = (deltas.score(0, team_slots), deltas.score(1, team_slots));
+        let a_won = score_a > score_b;
+        if a_won {
+            result.a_wins += 1;
+        } else if score_b > score_a {
+            result.b_wins += 1;
+        } else {
+            result.ties += 1;
+        }
+        for (counts, &slot) in by_winner.iter_mut().zip(undecided) {
+            if let Some(team) = team_slots[slot as usize] {
+                let (wins, scenarios) = &mut counts[team as usize];
+                *wins += a_won as u64;
+                *scenarios += 1;
+            }
+        }
+    };
+    if exact {
+        view.for_each_scenario(None, &mut record);
+    } else {
+        let mut rng = Rng::new(SAMPLE_SEED);
+        let mut team_slots = view.team_slots().to_vec();
+        for _ in 0..SAMPLES {
+            // Children come before parents, so both sides are known.
+            for &slot in undecided {
+                let idx = slot as usize;
+                team_slots[idx] = match (team_slots[idx * 2], team_slots[idx * 2 + 1]) {
+                    (Some(a), Some(b)) => Some(if rng.next_u64() & 1 == 0 { a } else { b }),
+                    (a, b) => a.or(b),
+                };
+            }
+            record(&team_slots);
+        }
+    }
+
+    for (counts, &slot) in by_winner.iter().zip(undecided) {
+        let shares: Vec<(u8, f64)> = (64..128u8)
+            .filter(|&team| counts[team as usize].1 > 0)
+            .map(|team| {
+                let (wins, scenarios) = counts[team as usize];
+                (team, wins as f64 / scenarios as f64)
+            })
+            .collect();
+        let best = shares
+            .iter()
+            .copied()
+            .reduce(|x, y| if y.1 > x.1 { y } else { x });
+        let worst = shares
+            .iter()
+            .copied()
+            .reduce(|x, y| if y.1 < x.1 { y } else { x });
+        if let (Some(best), Some(worst)) = (best, worst) {
+            if best.1 > worst.1 {
+                result.pivotal.push(PivotalGame {
+                    slot,
+                    best_for_a: best.0,
+                    best_for_b: worst.0,
+                    swing: best.1 - worst.1,
+                });
+            }
+        }
+    }
+    result
+        .pivotal
+        .sort_by(|x, y| y.swing.total_cmp(&x.swing).then(x.slot.cmp(&y.slot)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn counts_the_matchup_and_finds_the_decider() {
+        // Both semifinals and the title game are left; the brackets only
+        // disagree on the title game.
+        let tournament = Tournament::new(0, COMPLETE_MASK & !0b1110);
+        let a = Bracket::from_decisions(0);
+        let b = Bracket::from_decisions(1 << 1);
+
+        let result = compare(&a, &b, &tournament);
+        assert!(result.exact);
+        assert_eq!((result.a_wins + result.b_wins + result.ties), 8);
+        // Team 64 winning it all is the only way A gets ahead.
+        assert_eq!(result.a_wins, 2);
+        assert_eq!(result.pivotal[0].slot, 1);
+        assert_eq!(result.pivotal[0].best_for_a, 64);
+        assert_eq!(result.pivotal[0].swing, 1.0);
+
+        // Counting only the rounds already played, they tie every time.
+        let early_rounds = ScoringSystem {
+            multipliers: [0, 1, 1, 1, 1, 0, 0],
+            ..ScoringSystem::standard()
+        };
+        let result = compare_with(&a, &b, &tournament, &early_rounds);
+        assert_eq!(result.ties, 8);
+        assert!(result.pivotal.is_empty());
+
+        let early = compare(&a, &b, &Tournament::new(0, 0));
+        assert!(!early.exact);
+        assert_eq!(early.a_wins + early.b_wins + early.ties, SAMPLES);
+    }
+}
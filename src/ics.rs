@@ -0,0 +1,227 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::rooting::RootingInterest;
+use crate::schedule::Schedule;
+use crate::{ordinal, round_name, round_num_for_slot};
+
+/// `t` as an iCalendar UTC date-time, e.g. `20240321T161500Z`.
+fn format_utc(t: SystemTime) -> String {
+    let secs = t
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Appends a content line, folded at 75 octets as RFC 5545 requires.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Which side to root for in `interest` and what it's worth, e.g. "Root
+/// for Gonzaga: best finish 1st if they win, 3rd if not". `team_name`
+/// names a team slot (64..=127).
+fn rooting_note<F: Fn(u8) -> String>(interest: &RootingInterest, team_name: &F) -> String {
+    let side = interest.side as usize;
+    let team = match interest.team {
+        Some(team) => team_name(team),
+        None => format!("the game {} winner", interest.slot * 2 + interest.side),
+    };
+    if interest.places_gained() > 0 {
+        format!(
+            "Root for {}: best finish {} if they win, {} if not",
+            team,
+            ordinal(interest.best_finish[side]),
+            ordinal(interest.best_finish[1 - side])
+        )
+    } else {
+        format!(
+            "Root for {}: wins the pool in {:.0}% of scenarios if they win, {:.0}% if not",
+            team,
+            interest.win_share[side] * 100.0,
+            interest.win_share[1 - side] * 100.0
+        )
+    }
+}
+
+impl Schedule {
+    /// An iCalendar file with one event per game of an entry's rooting
+    /// `guide`, from [`crate::TournamentView::rooting_guide`], most
+    /// important first and each described by the side to root for.
+    /// `team_name` names a team slot (64..=127). Games without a tip time
+    /// are left out, since there's nothing to put on a calendar. `stamp` is
+    /// when the file was made.
+    pub fn to_ics<F: Fn(u8) -> String>(
+        &self,
+        guide: &[RootingInterest],
+        team_name: F,
+        stamp: SystemTime,
+    ) -> String {
+        let mut out = String::new();
+        push_line(&mut out, "BEGIN:VCALENDAR");
+        push_line(&mut out, "VERSION:2.0");
+        push_line(&mut out, "PRODID:-//madness-rs//EN");
+
+        for interest in guide {
+            let slot = interest.slot;
+            let note = rooting_note(interest, &team_name);
+            let Some(info) = self.game(slot) else {
+                continue;
+            };
+            let Some(tip_time) = info.tip_time else {
+                continue;
+            };
+
+            push_line(&mut out, "BEGIN:VEVENT");
+            push_line(&mut out, &format!("UID:game-{}@madness-rs", slot));
+            push_line(&mut out, &format!("DTSTAMP:{}", format_utc(stamp)));
+            push_line(&mut out, &format!("DTSTART:{}", format_utc(tip_time)));
+            push_line(
+                &mut out,
+                &format!(
+                    "SUMMARY:{}",
+                    escape_text(&format!(
+                        "{}: game {}",
+                        round_name(round_num_for_slot(slot)),
+                        slot
+                    ))
+                ),
+            );
+            if let Some(venue) = &info.venue {
+                push_line(&mut out, &format!("LOCATION:{}", escape_text(venue)));
+            }
+            let description = match &info.broadcast {
+                Some(broadcast) => format!("{}\nOn {}", note, broadcast),
+                None => note,
+            };
+            push_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", escape_text(&description)),
+            );
+            push_line(&mut out, "END:VEVENT");
+        }
+
+        push_line(&mut out, "END:VCALENDAR");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::GameInfo;
+    use crate::view::TournamentView;
+    use crate::{Bracket, Tournament, COMPLETE_MASK};
+    use std::time::Duration;
+
+    #[test]
+    fn formats_utc_dates() {
+        assert_eq!(format_utc(UNIX_EPOCH), "19700101T000000Z");
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(1_711_037_700)),
+            "20240321T161500Z"
+        );
+    }
+
+    #[test]
+    fn writes_events_for_the_games_that_matter() {
+        // Both semifinals and the title game are left; the title game has no
+        // tip time yet.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+        let guide = view.rooting_guide(&brackets, 0).unwrap();
+
+        let tip_time = UNIX_EPOCH + Duration::from_secs(1_711_037_700);
+        let mut schedule = Schedule::new();
+        schedule
+            .insert(
+                2,
+                GameInfo {
+                    tip_time: Some(tip_time),
+                    venue: Some("Glendale, AZ".to_string()),
+                    broadcast: Some("CBS".to_string()),
+                },
+            )
+            .unwrap();
+        schedule
+            .insert(
+                3,
+                GameInfo {
+                    tip_time: Some(tip_time + Duration::from_secs(9000)),
+                    ..GameInfo::default()
+                },
+            )
+            .unwrap();
+        schedule.insert(1, GameInfo::default()).unwrap();
+
+        let name = |team: u8| format!("T{}", team);
+        let ics = schedule.to_ics(&guide, name, tip_time);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART:20240321T161500Z\r\n"));
+        assert!(ics.contains("SUMMARY:Final Four: game 2\r\n"));
+        assert!(ics.contains("LOCATION:Glendale\\, AZ\r\n"));
+        // The entry's champion's semifinal first, then the rival's.
+        let first = ics
+            .find(
+                "DESCRIPTION:Root for T64: best finish 1st if they win\\, 2nd if not\\nOn CBS\r\n",
+            )
+            .unwrap();
+        let second = ics
+            .find("DESCRIPTION:Root for T112: wins the pool in 50% of scenarios if they win\\, \r\n 25% if not\r\n")
+            .unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let mut out = String::new();
+        push_line(&mut out, &"x".repeat(100));
+        assert_eq!(
+            out,
+            format!("{}\r\n {}\r\n", "x".repeat(75), "x".repeat(25))
+        );
+    }
+}
@@ -0,0 +1,401 @@
+use std::fmt;
+
+use crate::error::MadnessError;
+use crate::{BestFinishes, Bracket, Decisions, Tournament};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// Not JSON, from this byte offset on.
+    Syntax(usize),
+    /// JSON, but not in the shape written for the type.
+    Shape(&'static str),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Syntax(offset) => write!(f, "malformed JSON at byte {}", offset),
+            JsonError::Shape(what) => write!(f, "unexpected JSON: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+// What the readers below need of JSON: whole numbers only, and an object's
+// members in the order written.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn member(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn parse(text: &str) -> Result<Value, JsonError> {
+        let mut reader = Reader {
+            bytes: text.as_bytes(),
+            pos: 0,
+        };
+        let value = reader.value()?;
+        reader.skip_whitespace();
+        if reader.pos != reader.bytes.len() {
+            return Err(JsonError::Syntax(reader.pos));
+        }
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b" \t\r\n".contains(b))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) != Some(&byte) {
+            return Err(JsonError::Syntax(self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, JsonError> {
+        if !self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            return Err(JsonError::Syntax(self.pos));
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, JsonError> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'-' | b'0'..=b'9') => self.integer(),
+            _ => Err(JsonError::Syntax(self.pos)),
+        }
+    }
+
+    fn integer(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+        if self.bytes[self.pos] == b'-' {
+            self.pos += 1;
+        }
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        // Fractions and exponents never appear in what's written here.
+        if matches!(self.bytes.get(self.pos), Some(b'.' | b'e' | b'E')) {
+            return Err(JsonError::Syntax(self.pos));
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .map(Value::Integer)
+            .ok_or(JsonError::Syntax(start))
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut out = Vec::new();
+        loop {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return Err(JsonError::Syntax(self.pos));
+            };
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = match self.bytes.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let hex = self.bytes.get(self.pos + 1..self.pos + 5);
+                            let c = hex
+                                .and_then(|hex| std::str::from_utf8(hex).ok())
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or(JsonError::Syntax(self.pos))?;
+                            self.pos += 4;
+                            c
+                        }
+                        _ => return Err(JsonError::Syntax(self.pos)),
+                    };
+                    self.pos += 1;
+                    out.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                byte => out.push(byte),
+            }
+        }
+        // The input was a `str` and escapes add whole chars.
+        Ok(String::from_utf8(out).expect("valid UTF-8"))
+    }
+
+    fn array(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                _ => return Err(JsonError::Syntax(self.pos)),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                _ => return Err(JsonError::Syntax(self.pos)),
+            }
+        }
+    }
+}
+
+/// The 63 entries of `key` in the top-level object `value`, game slot 1
+/// first.
+fn games<'a>(value: &'a Value, key: &str, shape: &'static str) -> Result<&'a [Value], JsonError> {
+    match value.member(key) {
+        Some(Value::Array(games)) if games.len() == 63 => Ok(games),
+        _ => Err(JsonError::Shape(shape)),
+    }
+}
+
+const NO_CONTEST: &str = "no contest";
+
+impl Tournament {
+    /// The results as JSON, to store between game updates:
+    /// `{"games":[...]}` with one entry per game from slot 1 to 63, each
+    /// the winning side as [`Decisions::set_decision`] takes it, `null`
+    /// while undecided or `"no contest"`.
+    pub fn to_json(&self) -> String {
+        let games: Vec<String> = (1..=63u8)
+            .map(|slot| {
+                if self.vacated & (1 << slot) != 0 {
+                    format!("\"{}\"", NO_CONTEST)
+                } else {
+                    self.decision(slot)
+                        .map_or_else(|| "null".to_string(), |side| side.to_string())
+                }
+            })
+            .collect();
+        format!("{{\"games\":[{}]}}", games.join(","))
+    }
+
+    /// Reads the results back from [`Tournament::to_json`]'s form, checking
+    /// them as [`Tournament::try_new`] does. Other members are ignored.
+    pub fn from_json(text: &str) -> Result<Tournament, MadnessError> {
+        let value = Reader::parse(text)?;
+        let shape = "a tournament has 63 games, each 0, 1, null or \"no contest\"";
+        let mut tournament = Tournament::new(0, 0);
+        for (slot, game) in (1..=63u8).zip(games(&value, "games", shape)?) {
+            match game {
+                Value::Null => {}
+                Value::Integer(side @ 0..=1) => tournament.set_decision(slot, *side as u8),
+                Value::String(s) if s == NO_CONTEST => {
+                    tournament.set_decision(slot, 0);
+                    tournament.vacated |= 1 << slot;
+                }
+                _ => return Err(JsonError::Shape(shape).into()),
+            }
+        }
+        tournament
+            .validate_strict()
+            .map_err(MadnessError::InvalidTournament)?;
+        Ok(tournament)
+    }
+}
+
+impl Bracket {
+    /// The picks as JSON: `{"picks":[...]}` with each game's side from
+    /// slot 1 to 63.
+    pub fn to_json(&self) -> String {
+        let picks: Vec<String> = (1..=63u8)
+            .map(|slot| self.decision(slot).unwrap_or(0).to_string())
+            .collect();
+        format!("{{\"picks\":[{}]}}", picks.join(","))
+    }
+
+    /// Reads the picks back from [`Bracket::to_json`]'s form. Other members
+    /// are ignored.
+    pub fn from_json(text: &str) -> Result<Bracket, MadnessError> {
+        let value = Reader::parse(text)?;
+        let shape = "a bracket has 63 picks, each 0 or 1";
+        let mut bracket = Bracket::from_decisions(0);
+        for (slot, pick) in (1..=63u8).zip(games(&value, "picks", shape)?) {
+            match pick {
+                Value::Integer(side @ 0..=1) => bracket.set_decision(slot, *side as u8),
+                _ => return Err(JsonError::Shape(shape).into()),
+            }
+        }
+        Ok(bracket)
+    }
+}
+
+impl BestFinishes {
+    /// The finishes as JSON for a web page: `{"places":5,"finishes":[...]}`
+    /// with a `{"bracket":code,"best_finish":place}` per bracket that can
+    /// reach the top places, best first. Brackets are given by
+    /// [`Bracket::to_code`].
+    pub fn to_json(&self) -> String {
+        let mut finishes: Vec<(&Bracket, usize)> = self
+            .possible_finishes
+            .iter()
+            .map(|(bracket, &finish)| (bracket, finish))
+            .collect();
+        finishes.sort_by_key(|&(bracket, finish)| (finish, bracket.decisions));
+        let finishes: Vec<String> = finishes
+            .iter()
+            .map(|(bracket, finish)| {
+                format!(
+                    "{{\"bracket\":\"{}\",\"best_finish\":{}}}",
+                    bracket.to_code(),
+                    finish
+                )
+            })
+            .collect();
+        format!(
+            "{{\"places\":{},\"finishes\":[{}]}}",
+            self.places(),
+            finishes.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{random_brackets, random_tournament};
+    use crate::view::TournamentView;
+    use crate::{round_slot_mask, COMPLETE_MASK};
+
+    #[test]
+    fn round_trips_results_and_picks() {
+        for seed in 0..5 {
+            let tournament = random_tournament(seed, 40);
+            assert_eq!(Tournament::from_json(&tournament.to_json()), Ok(tournament));
+        }
+        let mut vacated = Tournament::new(0, round_slot_mask(1));
+        vacated.record_no_contest(17).unwrap();
+        let json = vacated.to_json();
+        assert!(json.starts_with("{\"games\":[null,"));
+        assert!(json.contains(",\"no contest\","));
+        assert_eq!(Tournament::from_json(&json), Ok(vacated));
+
+        for bracket in random_brackets(4, 5) {
+            assert_eq!(Bracket::from_json(&bracket.to_json()), Ok(bracket));
+        }
+        // Whitespace, member order and other members don't matter.
+        let spaced = format!(
+            "{{ \"owner\": \"S\\u00e9bastien\", \"tags\": [true, {{}}],\n  \"picks\": [{}] }}",
+            vec!["1"; 63].join(", ")
+        );
+        assert_eq!(
+            Bracket::from_json(&spaced),
+            Ok(Bracket::from_decisions(COMPLETE_MASK))
+        );
+    }
+
+    #[test]
+    fn rejects_what_it_did_not_write() {
+        assert_eq!(
+            Bracket::from_json("{\"picks\":[0,"),
+            Err(MadnessError::Json(JsonError::Syntax(12)))
+        );
+        assert!(matches!(
+            Bracket::from_json("{\"picks\":[0,1]}"),
+            Err(MadnessError::Json(JsonError::Shape(_)))
+        ));
+        let two = Bracket::from_decisions(0).to_json().replacen('0', "2", 1);
+        assert!(matches!(
+            Bracket::from_json(&two),
+            Err(MadnessError::Json(JsonError::Shape(_)))
+        ));
+        // The title game decided before its semifinals.
+        let early = Tournament::new(0, 0).to_json().replacen("null", "0", 1);
+        assert!(matches!(
+            Tournament::from_json(&early),
+            Err(MadnessError::InvalidTournament(_))
+        ));
+    }
+
+    #[test]
+    fn writes_best_finishes_best_first() {
+        // Only the title game is left: 64 against 96.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b10));
+        let chalk = Bracket::from_decisions(0);
+        let rival = Bracket::from_decisions(1 << 1);
+        // Has 80 in the title game, so can't catch either.
+        let out = Bracket::from_decisions(1 << 2);
+        let finishes = BestFinishes::for_view_places(&[out, rival, chalk], &view, 2);
+        assert_eq!(
+            finishes.to_json(),
+            format!(
+                "{{\"places\":2,\"finishes\":[{{\"bracket\":\"{}\",\"best_finish\":0}},\
+                 {{\"bracket\":\"{}\",\"best_finish\":0}}]}}",
+                chalk.to_code(),
+                rival.to_code()
+            )
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use crate::Score;
+
+/// One owner's result in an archived season.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonResult {
+    pub owner: String,
+    /// Zero-based final standings position.
+    pub finish: usize,
+    pub points: Score,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Season {
+    pub year: u16,
+    pub results: Vec<SeasonResult>,
+}
+
+impl Season {
+    fn average_points(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.results.iter().map(|r| r.points as f64).sum::<f64>() / self.results.len() as f64
+    }
+}
+
+/// An owner's record across every archived season they played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnerHistory {
+    pub seasons: usize,
+    pub championships: usize,
+    pub average_finish: f64,
+    /// Sum over seasons of the owner's points minus that season's average.
+    pub points_vs_average: f64,
+}
+
+/// Aggregates archived seasons by owner name.
+pub fn league_history(seasons: &[Season]) -> HashMap<String, OwnerHistory> {
+    let mut history: HashMap<String, OwnerHistory> = HashMap::new();
+    for season in seasons {
+        let average = season.average_points();
+        for result in &season.results {
+            let owner = history.entry(result.owner.clone()).or_insert(OwnerHistory {
+                seasons: 0,
+                championships: 0,
+                average_finish: 0.0,
+                points_vs_average: 0.0,
+            });
+            owner.seasons += 1;
+            owner.championships += (result.finish == 0) as usize;
+            // Running sum for now, turned into the mean below.
+            owner.average_finish += result.finish as f64;
+            owner.points_vs_average += result.points as f64 - average;
+        }
+    }
+    for owner in history.values_mut() {
+        owner.average_finish /= owner.seasons as f64;
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_by_owner() {
+        let result = |owner: &str, finish, points| SeasonResult {
+            owner: owner.to_string(),
+            finish,
+            points,
+        };
+        let seasons = [
+            Season {
+                year: 2023,
+                results: vec![result("Dave", 0, 120), result("Sarah", 1, 80)],
+            },
+            Season {
+                year: 2024,
+                results: vec![
+                    result("Sarah", 0, 130),
+                    result("Dave", 1, 110),
+                    result("Mike", 2, 60),
+                ],
+            },
+        ];
+
+        let history = league_history(&seasons);
+
+        assert_eq!(history["Dave"].seasons, 2);
+        assert_eq!(history["Dave"].championships, 1);
+        assert_eq!(history["Dave"].average_finish, 0.5);
+        assert_eq!(history["Dave"].points_vs_average, 20.0 + 10.0);
+        assert_eq!(history["Mike"].points_vs_average, -40.0);
+    }
+}
@@ -1,13 +1,122 @@
-// An attribute to hide warnings for unused code.
-#![allow(dead_code)]
+//! Scoring and standings for 64-team single-elimination bracket pools.
+//!
+//! A [`Tournament`] holds the results so far and a [`Bracket`] one entry's
+//! picks, both as decision bits: bit `slot` of a game slot (1 is the title
+//! game, 32..=63 the first round) is `0` when the game was won from slot
+//! `slot * 2` and `1` when won from `slot * 2 + 1`. The first round's
+//! children are the team slots 64..=127, in bracket order.
+//!
+//! ```
+//! use madness_rs::{Bracket, Decisions, Tournament};
+//!
+//! let mut tournament = Tournament::new(0, 0);
+//! // The top team in slot 64 wins its first-round game.
+//! tournament.set_decision(32, 0);
+//!
+//! let chalk = Bracket::from_decisions(0);
+//! assert_eq!(chalk.score(&tournament), 2);
+//! ```
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::iter;
 
-const COMPLETE_MASK: u64 = 0xFFFFFFFFFFFFFFFE;
+pub mod binary;
+pub mod bounds;
+pub mod breakdown;
+pub mod byes;
+pub mod cache;
+pub mod calibration;
+pub mod cascade;
+pub mod champion;
+pub mod chunked;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod code;
+pub mod combined;
+pub mod conditional;
+pub mod conditions;
+pub mod conference;
+pub mod contest;
+pub mod dead;
+pub mod deadline;
+pub mod delta;
+pub mod diff;
+pub mod difficulty;
+pub mod distribution;
+pub mod division;
+pub mod error;
+pub mod exposure;
+pub mod field;
+pub mod first_four;
+pub mod format;
+pub mod head_to_head;
+pub mod ics;
+pub mod json;
+pub mod league;
+pub mod movers;
+pub mod names;
+pub mod odds;
+pub mod orphans;
+pub mod packed;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod partial;
+pub mod paths;
+pub mod perfect;
+pub mod picks;
+pub mod pool;
+pub mod printable;
+pub mod prizes;
+pub mod projection;
+pub mod provenance;
+pub mod prune;
+pub mod range;
+pub mod recap;
+pub mod records;
+pub mod region;
+pub mod relevance;
+pub mod remaining;
+pub mod render;
+pub mod results;
+mod rng;
+pub mod rooting;
+pub mod scenarios;
+pub mod schedule;
+pub mod score_delta;
+pub mod scoring;
+pub mod side;
+pub mod simulate;
+pub mod sized;
+pub mod stage;
+pub mod status;
+pub mod swap;
+pub mod testing;
+pub mod third_place;
+pub mod vacated;
+pub mod validate;
+pub mod view;
+
+pub use cascade::{Cascade, PickChange};
+pub use error::MadnessError;
+pub use partial::PartialBracket;
+use score_delta::ScoreDeltaTable;
+pub use scoring::ScoringSystem;
+pub use view::TournamentView;
+
+/// Every game slot decided: the mask of a finished tournament.
+pub const COMPLETE_MASK: u64 = 0xFFFFFFFFFFFFFFFE;
 const POINTS_PER_ROUND: [u8; 7] = [0, 1, 2, 3, 5, 8, 13];
 const SEED_ORDER: [u8; 16] = [1, 16, 8, 9, 5, 12, 4, 13, 6, 11, 3, 14, 7, 10, 2, 15];
 
+const ROUNDS: u8 = 6;
+
+/// Points, accumulated in 64 bits on every target (including 32-bit wasm).
+/// The built-in scoring tops out below 2,000 points per bracket; every sum of
+/// scores in the crate saturates at `Score::MAX` rather than wrapping, so
+/// custom point values can't overflow silently.
+pub type Score = u64;
+
 fn seed_for_slot(slot: u8) -> u8 {
     SEED_ORDER[slot as usize % 16]
 }
@@ -17,15 +126,86 @@ fn round_num_for_slot(slot: u8) -> u8 {
     7 - depth
 }
 
-trait Decisions {
+/// Bits of the game slots played in `round` (1 = first round, 6 = title game).
+fn round_slot_mask(round: u8) -> u64 {
+    let lo = 1u64 << (ROUNDS - round);
+    ((1u64 << lo) - 1) << lo
+}
+
+fn round_name(round: u8) -> &'static str {
+    match round {
+        1 => "First Round",
+        2 => "Second Round",
+        3 => "Sweet 16",
+        4 => "Elite Eight",
+        5 => "Final Four",
+        6 => "Championship",
+        _ => "Unknown Round",
+    }
+}
+
+/// Points for correctly picking a `seed` to win a game in `round` (1..=6).
+fn points_for(round: u8, seed: u8) -> Score {
+    POINTS_PER_ROUND[round as usize] as Score + seed as Score
+}
+
+/// Points for correctly picking `team` to win the game in `slot`, under
+/// [`ScoringSystem::standard`].
+fn points_for_pick(slot: u8, team: u8) -> Score {
+    points_for(round_num_for_slot(slot), seed_for_slot(team))
+}
+
+/// Results or picks as decision bits, one per game slot.
+///
+/// Game slots are numbered like a binary heap: the title game is slot 1, the
+/// games feeding slot `s` are `s * 2` and `s * 2 + 1`, and the first round is
+/// 32..=63, fed by the teams in team slots 64..=127. Bit `s` of
+/// [`Decisions::decisions`] is the side that won game `s`, and bit 0 is
+/// reserved.
+///
+/// The trait is object safe, so a type of your own (a row loaded from a
+/// database, say) can be scored and enumerated through `&dyn Decisions` with
+/// [`TournamentView::from_decisions`] and [`TournamentView::score_picks`].
+/// Only [`Decisions::decisions`], [`Decisions::mask`] and
+/// [`Decisions::set_decision`] need implementing; a full bracket's mask is
+/// [`COMPLETE_MASK`].
+pub trait Decisions {
+    /// Which side won each game in [`Decisions::mask`].
     fn decisions(&self) -> u64;
+    /// Bits of the games that have been decided.
     fn mask(&self) -> u64;
 
+    /// Decided games that produced no winner (no-contests). Their slots, and
+    /// any later slot that would have been filled from them, have no team.
+    fn vacated(&self) -> u64 {
+        0
+    }
+
+    /// Records the winner of the game in `slot` (1..=63): `0` if it came from
+    /// slot `slot * 2`, `1` if from `slot * 2 + 1`. In the first round
+    /// (32..=63) those are the team slots themselves. Panics on a slot
+    /// outside 1..=63 or a winner other than 0 or 1.
+    fn set_decision(&mut self, slot: u8, winner: u8);
+
+    /// The winner recorded for `slot` in the same 0/1 form, or `None` if the
+    /// game is undecided.
+    fn decision(&self, slot: u8) -> Option<u8> {
+        let position = 1u64 << slot;
+        (self.mask() & position != 0).then_some((self.decisions() & position != 0) as u8)
+    }
+
+    /// Bits of the decided games in `round` (1..=6).
+    fn decided_mask_for_round(&self, round: u8) -> u64 {
+        self.mask() & round_slot_mask(round)
+    }
+
+    /// The team (64..=127) that won each game slot, by slot. Undecided and
+    /// vacated games, and slot 0, have no team.
     fn decision_team_slots(&self) -> [Option<u8>; 64] {
         let mut res: [Option<u8>; 64] = [None; 64];
         for i in (1..=63).rev() {
             let current_position: u64 = 1 << i;
-            if (current_position & self.mask()) != 0 {
+            if (current_position & self.mask()) != 0 && (current_position & self.vacated()) == 0 {
                 let decision = if (self.decisions() & current_position) == 0 {
                     0
                 } else {
@@ -40,139 +220,844 @@ trait Decisions {
             }
         }
 
-        return res;
+        res
     }
 }
-#[derive(Debug)]
-struct Tournament {
+/// Results so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
     decisions: u64,
     mask: u64,
+    vacated: u64,
+}
+
+impl Tournament {
+    /// A tournament with the games in `mask` decided as `decisions` says.
+    /// Bit 0 is reserved and ignored.
+    pub fn new(decisions: u64, mask: u64) -> Tournament {
+        Tournament {
+            decisions: decisions & mask & COMPLETE_MASK,
+            mask: mask & COMPLETE_MASK,
+            vacated: 0,
+        }
+    }
 }
 
 impl Decisions for Tournament {
     fn decisions(&self) -> u64 {
-        return self.decisions;
+        self.decisions
     }
 
     fn mask(&self) -> u64 {
-        return self.mask;
+        self.mask
+    }
+
+    fn vacated(&self) -> u64 {
+        self.vacated
+    }
+
+    fn set_decision(&mut self, slot: u8, winner: u8) {
+        let position = checked_decision_bit(slot, winner);
+        self.decisions = (self.decisions & !position) | (position * winner as u64);
+        self.mask |= position;
     }
 }
 
+/// The bit for `slot`, after checking `slot` is a game and `winner` a side.
+fn checked_decision_bit(slot: u8, winner: u8) -> u64 {
+    assert!((1..=63).contains(&slot), "no game in slot {}", slot);
+    assert!(winner <= 1, "winner must be 0 or 1, got {}", winner);
+    1u64 << slot
+}
+
+/// One entry's picks for every game.
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
-struct Bracket {
+pub struct Bracket {
     decisions: u64,
 }
 
 impl Decisions for Bracket {
     fn decisions(&self) -> u64 {
-        return self.decisions;
+        self.decisions
     }
 
     fn mask(&self) -> u64 {
-        return COMPLETE_MASK;
+        COMPLETE_MASK
     }
-}
 
-impl Bracket {
-    fn points_for_decisions(&self, tournament_team_slots: &[Option<u8>]) -> usize {
-        let bracket_team_slots = self.decision_team_slots();
-        tournament_team_slots
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (i, t)| {
-                if let Some(t) = *t {
-                    if let Some(b) = bracket_team_slots[i] {
-                        if t == b {
-                            let team_seed = seed_for_slot(b);
-                            let round_number = round_num_for_slot(i as u8);
-
-                            return acc
-                                + POINTS_PER_ROUND[round_number as usize] as usize
-                                + team_seed as usize;
-                        }
-                    }
-                }
-                return acc;
-            })
+    fn set_decision(&mut self, slot: u8, winner: u8) {
+        let position = checked_decision_bit(slot, winner);
+        self.decisions = (self.decisions & !position) | (position * winner as u64);
     }
 }
 
-struct BestFinishes {
-    possible_finishes: HashMap<Bracket, usize>,
-}
-
-impl BestFinishes {
-    fn new() -> BestFinishes {
-        BestFinishes {
-            possible_finishes: HashMap::new(),
+impl Bracket {
+    pub fn from_decisions(decisions: u64) -> Bracket {
+        Bracket {
+            decisions: decisions & COMPLETE_MASK,
         }
     }
 
-    fn calc(brackets: &[Bracket], tournament_team_slots: &mut [Option<u8>]) -> BestFinishes {
-        let mut best_finishes = BestFinishes::new();
+    /// Points scored against `tournament` so far.
+    pub fn score(&self, tournament: &Tournament) -> Score {
+        self.score_with(tournament, &ScoringSystem::standard())
+    }
 
-        let no_decision_idx = tournament_team_slots.into_iter().rposition(|x| x.is_none());
+    pub fn score_with(&self, tournament: &Tournament, scoring: &ScoringSystem) -> Score {
+        TournamentView::new(tournament).score_with(self, scoring)
+    }
 
-        if no_decision_idx.is_some() && no_decision_idx != Some(0) {
-            let no_decision_idx = no_decision_idx.unwrap();
+    fn points_for_decisions(
+        &self,
+        tournament_team_slots: &[Option<u8>],
+        scoring: &ScoringSystem,
+    ) -> Score {
+        points_for_team_slots(tournament_team_slots, &self.decision_team_slots(), scoring)
+    }
 
-            tournament_team_slots[no_decision_idx] = tournament_team_slots[no_decision_idx * 2]; // decision 0
-            let child_results = BestFinishes::calc(brackets, tournament_team_slots);
-            best_finishes.merge(child_results);
+    /// Team slots picked to win the `N` games starting at `first_slot`.
+    fn picked_winners<const N: usize>(&self, first_slot: usize) -> [u8; N] {
+        let team_slots = self.decision_team_slots();
+        // Every game of a complete bracket has a pick.
+        std::array::from_fn(|i| team_slots[first_slot + i].unwrap())
+    }
 
-            tournament_team_slots[no_decision_idx] =
-                tournament_team_slots[(no_decision_idx * 2) + 1]; //decision 1
-            let child_results = BestFinishes::calc(brackets, tournament_team_slots);
-            best_finishes.merge(child_results);
+    pub fn champion(&self) -> u8 {
+        self.picked_winners::<1>(1)[0]
+    }
 
-            tournament_team_slots[no_decision_idx] = None;
+    /// The finalist picked to lose the title game.
+    pub fn runner_up(&self) -> u8 {
+        let [semifinal_2, semifinal_3] = self.picked_winners::<2>(2);
+        if self.champion() == semifinal_2 {
+            semifinal_3
         } else {
-            let mut tuples: Vec<(&Bracket, usize)> = brackets
-                .iter()
-                .map(|b| (b, b.points_for_decisions(tournament_team_slots)))
-                .collect();
+            semifinal_2
+        }
+    }
 
-            tuples.sort_by(|(_, r1), (_, r2)| r2.cmp(r1));
+    /// Regional champions, in slot order (slots 4..=7).
+    pub fn final_four(&self) -> [u8; 4] {
+        self.picked_winners(4)
+    }
 
-            let mut rank = 0;
-            for (i, (b, _)) in tuples.iter().enumerate() {
-                if i > 0 && tuples[i - 1].1 != tuples[i].1 {
-                    rank = i;
-                }
-                if rank > 4 {
-                    //only take top-5 ranking
-                    break;
+    /// Sweet 16 winners, in slot order (slots 8..=15).
+    pub fn elite_eight(&self) -> [u8; 8] {
+        self.picked_winners(8)
+    }
+}
+
+/// Every bracket's score against `tournament`, working out the
+/// tournament's teams once for the lot. Shorthand for
+/// [`TournamentView::score_batch`].
+pub fn score_batch(tournament: &Tournament, brackets: &[Bracket]) -> Vec<Score> {
+    TournamentView::new(tournament).score_batch(brackets)
+}
+
+/// Points for every pick in `picked_team_slots` that matches the winner in
+/// `tournament_team_slots`. Slots with no team on either side score nothing.
+fn points_for_team_slots(
+    tournament_team_slots: &[Option<u8>],
+    picked_team_slots: &[Option<u8>],
+    scoring: &ScoringSystem,
+) -> Score {
+    tournament_team_slots
+        .iter()
+        .zip(picked_team_slots.iter())
+        .enumerate()
+        .fold(0, |acc, (i, (t, b))| {
+            if let Some(t) = *t {
+                if let Some(b) = *b {
+                    if t == b {
+                        return acc.saturating_add(scoring.points_in(
+                            i as u8,
+                            b,
+                            tournament_team_slots,
+                        ));
+                    }
                 }
-                best_finishes.possible_finishes.insert(**b, rank);
             }
+            acc
+        })
+}
+
+/// Zero-based standings position for each score, with ties sharing the
+/// better position: the number of scores strictly higher.
+fn standings_ranks(scores: &[Score]) -> Vec<usize> {
+    let mut sorted = scores.to_vec();
+    sorted.sort_unstable_by(|s1, s2| s2.cmp(s1));
+    scores
+        .iter()
+        .map(|s| sorted.partition_point(|other| other > s))
+        .collect()
+}
+
+/// A zero-based place as "1st", "2nd" and so on.
+fn ordinal(rank: usize) -> String {
+    let place = rank + 1;
+    let suffix = match (place % 10, place % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", place, suffix)
+}
+
+/// Finish positions tracked when no other number is given.
+const DEFAULT_PLACES: usize = 5;
+
+/// The most remaining scenarios the exhaustive analyses, such as
+/// [`TournamentView::rooting_guide`], will score. With more left they
+/// return `None` rather than run for hours.
+pub const ENUMERATION_LIMIT: u128 = 1 << 24;
+
+/// Each bracket's best possible zero-based finish in the top `places`
+/// over every remaining scenario.
+#[derive(Debug)]
+pub struct BestFinishes {
+    places: usize,
+    /// Brackets that can't finish in the top `places` aren't recorded.
+    pub possible_finishes: HashMap<Bracket, usize>,
+}
+
+impl BestFinishes {
+    fn new(places: usize) -> BestFinishes {
+        BestFinishes {
+            places,
+            possible_finishes: HashMap::new(),
         }
-        best_finishes
     }
 
+    /// The top five under [`ScoringSystem::standard`].
+    pub fn for_view(brackets: &[Bracket], view: &TournamentView) -> BestFinishes {
+        BestFinishes::for_view_places(brackets, view, DEFAULT_PLACES)
+    }
 
-    fn rankings(&self) -> Vec<Vec<&Bracket>> {
-        let mut ret: Vec<Vec<&Bracket>> = iter::repeat_with(|| vec![]).take(5).collect();
+    pub fn for_view_places(
+        brackets: &[Bracket],
+        view: &TournamentView,
+        places: usize,
+    ) -> BestFinishes {
+        BestFinishes::for_view_with(brackets, view, &ScoringSystem::standard(), places)
+    }
+
+    pub fn for_view_with(
+        brackets: &[Bracket],
+        view: &TournamentView,
+        scoring: &ScoringSystem,
+        places: usize,
+    ) -> BestFinishes {
+        let deltas = ScoreDeltaTable::for_view_with(view, brackets, scoring);
+        BestFinishes::for_deltas(brackets, view, &deltas, places)
+    }
+
+    /// The search over a table built for `brackets` and `view`, which may
+    /// carry adjustments.
+    pub fn for_deltas(
+        brackets: &[Bracket],
+        view: &TournamentView,
+        deltas: &ScoreDeltaTable,
+        places: usize,
+    ) -> BestFinishes {
+        FinishContext::new().best_finishes(brackets, view, deltas, places)
+    }
+
+    pub fn places(&self) -> usize {
+        self.places
+    }
+
+    /// The brackets whose best finish is each position, best first.
+    pub fn rankings(&self) -> Vec<Vec<&Bracket>> {
+        let mut ret: Vec<Vec<&Bracket>> = iter::repeat_with(Vec::new).take(self.places).collect();
         self.possible_finishes.iter().for_each(|(b, rank)| {
             ret[*rank].push(b);
         });
         ret
     }
 
+    #[cfg(feature = "parallel")]
     fn merge(&mut self, other: BestFinishes) {
         other.possible_finishes.into_iter().for_each(|(b, rank)| {
-            let current_rank = self.possible_finishes.get(&b);
-            if current_rank.is_none() || *current_rank.unwrap() > rank {
-                self.possible_finishes.insert(b, rank);
-            }
+            self.record(b, rank);
         });
     }
+
+    fn record(&mut self, bracket: Bracket, rank: usize) {
+        let current_rank = self.possible_finishes.get(&bracket);
+        if current_rank.is_none() || *current_rank.unwrap() > rank {
+            self.possible_finishes.insert(bracket, rank);
+        }
+    }
+}
+
+/// Scratch space for the best-finish enumeration. Leaf buffers are allocated
+/// once per context rather than once per scenario, so a caller recomputing
+/// after every result can keep one context around and reuse it.
+#[derive(Debug, Default)]
+struct FinishContext {
+    team_slots: Vec<Option<u8>>,
+    scores: Vec<Score>,
+    // Running scores, one row of brackets per game decided so far.
+    levels: Vec<Score>,
+    // Scores with the outcomes picked so far, one row of brackets per region.
+    totals: Vec<Score>,
+    top: BinaryHeap<Reverse<Score>>,
+}
+
+/// One way a region's open games can go: its winners, in the order the
+/// games were listed, and the points each bracket gets from them.
+#[derive(Debug)]
+struct RegionOutcome {
+    winners: Vec<Option<u8>>,
+    points: Vec<Score>,
+}
+
+impl FinishContext {
+    fn new() -> FinishContext {
+        FinishContext::default()
+    }
+
+    fn best_finishes(
+        &mut self,
+        brackets: &[Bracket],
+        view: &TournamentView,
+        deltas: &ScoreDeltaTable,
+        places: usize,
+    ) -> BestFinishes {
+        let mut tournament_team_slots = std::mem::take(&mut self.team_slots);
+        tournament_team_slots.clear();
+        tournament_team_slots.extend_from_slice(view.team_slots());
+
+        let mut best_finishes = BestFinishes::new(places);
+        self.fill(
+            brackets,
+            deltas,
+            &mut tournament_team_slots,
+            view.undecided(),
+            &mut best_finishes,
+        );
+
+        self.team_slots = tournament_team_slots;
+        best_finishes
+    }
+
+    /// Records every scenario of the `undecided` games, which must be among
+    /// the games `deltas` was built for and list children before parents.
+    ///
+    /// Scores are carried down the enumeration a game at a time rather than
+    /// recomputed at each leaf. Each region's games are enumerated once, and
+    /// outcomes with the same champion and the same points for every
+    /// bracket are kept once, since the rest of the tournament can't tell
+    /// them apart; only the distinct outcomes are crossed with the other
+    /// regions and the Final Four.
+    fn fill(
+        &mut self,
+        brackets: &[Bracket],
+        deltas: &ScoreDeltaTable,
+        tournament_team_slots: &mut [Option<u8>],
+        undecided: &[u8],
+        best_finishes: &mut BestFinishes,
+    ) {
+        let columns = |games: &mut dyn Iterator<Item = u8>| -> Vec<(u8, usize)> {
+            games
+                .map(|slot| {
+                    let column = deltas.column(slot).expect("game isn't in the delta table");
+                    (slot, column)
+                })
+                .collect()
+        };
+        let outer = columns(&mut undecided.iter().copied().filter(|&slot| slot < 4));
+        let regions: Vec<(u8, Vec<(u8, usize)>)> = (4..8u8)
+            .map(|root| {
+                let games = undecided
+                    .iter()
+                    .copied()
+                    .filter(|&slot| slot >= 4 && slot >> (5 - slot.leading_zeros()) == root);
+                (root, columns(&mut { games }))
+            })
+            .filter(|(_, games)| !games.is_empty())
+            .collect();
+
+        let n = brackets.len();
+        let depth = regions
+            .iter()
+            .map(|(_, g)| g.len())
+            .max()
+            .unwrap_or(0)
+            .max(outer.len());
+        let mut levels = std::mem::take(&mut self.levels);
+        levels.clear();
+        levels.resize((depth + 1) * n, 0);
+
+        let outcomes: Vec<Vec<RegionOutcome>> = regions
+            .iter()
+            .map(|(root, games)| {
+                region_outcomes(deltas, tournament_team_slots, *root, games, &mut levels, n)
+            })
+            .collect();
+
+        let mut totals = std::mem::take(&mut self.totals);
+        totals.clear();
+        totals.extend((0..n).map(|i| deltas.score(i, tournament_team_slots)));
+        totals.resize((regions.len() + 1) * n, 0);
+        self.combine(
+            brackets,
+            deltas,
+            tournament_team_slots,
+            &regions,
+            &outcomes,
+            &outer,
+            &mut totals,
+            &mut levels,
+            best_finishes,
+        );
+        self.levels = levels;
+        self.totals = totals;
+    }
+
+    /// Every pick of one outcome per region, then every way the games
+    /// joining the regions can go. The first row of `totals` holds the
+    /// scores so far, and there's a row below it for each region left.
+    #[allow(clippy::too_many_arguments)]
+    fn combine(
+        &mut self,
+        brackets: &[Bracket],
+        deltas: &ScoreDeltaTable,
+        tournament_team_slots: &mut [Option<u8>],
+        regions: &[(u8, Vec<(u8, usize)>)],
+        outcomes: &[Vec<RegionOutcome>],
+        outer: &[(u8, usize)],
+        totals: &mut [Score],
+        levels: &mut [Score],
+        best_finishes: &mut BestFinishes,
+    ) {
+        let n = brackets.len();
+        let (scores, below) = totals.split_at_mut(n);
+        let Some(((_, games), rest)) = regions.split_first() else {
+            levels[..n].copy_from_slice(scores);
+            walk(
+                deltas,
+                tournament_team_slots,
+                outer,
+                levels,
+                n,
+                &mut |_, scores| self.record_scores(brackets, scores, best_finishes),
+            );
+            return;
+        };
+        for outcome in &outcomes[0] {
+            for (&(slot, _), &winner) in games.iter().zip(&outcome.winners) {
+                tournament_team_slots[slot as usize] = winner;
+            }
+            for ((total, &score), &points) in below.iter_mut().zip(&*scores).zip(&outcome.points) {
+                *total = score.saturating_add(points);
+            }
+            self.combine(
+                brackets,
+                deltas,
+                tournament_team_slots,
+                rest,
+                &outcomes[1..],
+                outer,
+                below,
+                levels,
+                best_finishes,
+            );
+        }
+        for &(slot, _) in games {
+            tournament_team_slots[slot as usize] = None;
+        }
+    }
+
+    /// Ranks every bracket in one completed scenario.
+    fn record_leaf(
+        &mut self,
+        brackets: &[Bracket],
+        deltas: &ScoreDeltaTable,
+        tournament_team_slots: &[Option<u8>],
+        best_finishes: &mut BestFinishes,
+    ) {
+        let mut scores = std::mem::take(&mut self.scores);
+        scores.clear();
+        scores.extend((0..brackets.len()).map(|i| deltas.score(i, tournament_team_slots)));
+        self.record_scores(brackets, &scores, best_finishes);
+        self.scores = scores;
+    }
+
+    /// Ranks every bracket by its score in one completed scenario.
+    fn record_scores(
+        &mut self,
+        brackets: &[Bracket],
+        scores: &[Score],
+        best_finishes: &mut BestFinishes,
+    ) {
+        // Min-heap of the best `places` scores. A bracket ranks in the top
+        // `places` exactly when it scores at least the smallest of them, and
+        // its rank is how many of them beat it.
+        self.top.clear();
+        for &score in scores {
+            self.top.push(Reverse(score));
+            if self.top.len() > best_finishes.places {
+                self.top.pop();
+            }
+        }
+
+        if let Some(&Reverse(cutoff)) = self.top.peek() {
+            for (b, &score) in brackets.iter().zip(scores) {
+                if score >= cutoff {
+                    let rank = self.top.iter().filter(|Reverse(s)| *s > score).count();
+                    best_finishes.record(*b, rank);
+                }
+            }
+        }
+    }
+}
+
+/// Decides each game of `games` from the side in the matching bit of
+/// `sides`. False if that side can't win (the walkover rule in
+/// `FinishContext::fill`), so the subtree has no scenarios.
+fn fix_sides(team_slots: &mut [Option<u8>], games: &[u8], sides: usize) -> bool {
+    for (i, &slot) in games.iter().enumerate() {
+        let idx = slot as usize;
+        let (decision_0, decision_1) = (team_slots[idx * 2], team_slots[idx * 2 + 1]);
+        team_slots[idx] = if (sides >> i) & 1 == 0 {
+            if decision_0.is_none() && decision_1.is_some() {
+                return false;
+            }
+            decision_0
+        } else {
+            if decision_1.is_none() {
+                return false;
+            }
+            decision_1
+        };
+    }
+    true
+}
+
+/// The distinct ways the region rooted at `root` can go, by champion and
+/// the points each bracket gets from `games`.
+fn region_outcomes(
+    deltas: &ScoreDeltaTable,
+    tournament_team_slots: &mut [Option<u8>],
+    root: u8,
+    games: &[(u8, usize)],
+    levels: &mut [Score],
+    n: usize,
+) -> Vec<RegionOutcome> {
+    // Keyed by champion and points.
+    let mut seen: HashMap<(Option<u8>, Vec<Score>), RegionOutcome> = HashMap::new();
+    levels[..n].fill(0);
+    walk(
+        deltas,
+        tournament_team_slots,
+        games,
+        levels,
+        n,
+        &mut |team_slots, points| {
+            seen.entry((team_slots[root as usize], points.to_vec()))
+                .or_insert_with(|| RegionOutcome {
+                    winners: games.iter().map(|&(s, _)| team_slots[s as usize]).collect(),
+                    points: points.to_vec(),
+                });
+        },
+    );
+    seen.into_values().collect()
+}
+
+/// Calls `leaf` with the winners and scores of every way `games` can go.
+/// `levels` holds a row of `n` scores per game, starting from the first,
+/// and each game adds its points to the row below.
+fn walk<F: FnMut(&[Option<u8>], &[Score])>(
+    deltas: &ScoreDeltaTable,
+    tournament_team_slots: &mut [Option<u8>],
+    games: &[(u8, usize)],
+    levels: &mut [Score],
+    n: usize,
+    leaf: &mut F,
+) {
+    let (current, next) = levels.split_at_mut(n);
+    let Some((&(slot, column), rest)) = games.split_first() else {
+        leaf(tournament_team_slots, current);
+        return;
+    };
+    let slot = slot as usize;
+    let decision_0 = tournament_team_slots[slot * 2];
+    let decision_1 = tournament_team_slots[slot * 2 + 1];
+
+    // A side coming out of a vacated game has no team, so the other side
+    // advances on a walkover and there's only one branch.
+    let branches = [
+        (decision_0, decision_0.is_some() || decision_1.is_none()),
+        (decision_1, decision_1.is_some()),
+    ];
+    for (winner, _) in branches.into_iter().filter(|&(_, open)| open) {
+        tournament_team_slots[slot] = winner;
+        for (i, score) in next[..n].iter_mut().enumerate() {
+            *score =
+                current[i].saturating_add(deltas.game_points(i, column, tournament_team_slots));
+        }
+        walk(deltas, tournament_team_slots, rest, next, n, leaf);
+    }
+    tournament_team_slots[slot] = None;
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         assert_eq!(true, true);
     }
+
+    #[test]
+    fn score_batch_matches_individual_scores() {
+        let tournament = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK,
+            vacated: 0,
+        };
+        let brackets = [
+            Bracket { decisions: 0 },
+            Bracket {
+                decisions: COMPLETE_MASK,
+            },
+        ];
+        let tournament_team_slots = tournament.decision_team_slots();
+
+        let scores = score_batch(&tournament, &brackets);
+        assert_eq!(
+            scores,
+            TournamentView::new(&tournament).score_batch(&brackets)
+        );
+
+        let expected: Vec<Score> = brackets
+            .iter()
+            .map(|b| b.points_for_decisions(&tournament_team_slots, &ScoringSystem::standard()))
+            .collect();
+        assert_eq!(scores, expected);
+        assert!(scores[0] > scores[1]);
+
+        let doubling = ScoringSystem::doubling();
+        assert_eq!(
+            TournamentView::new(&tournament).score_batch_with(&brackets, &doubling),
+            brackets
+                .iter()
+                .map(|b| b.score_with(&tournament, &doubling))
+                .collect::<Vec<Score>>()
+        );
+    }
+
+    #[test]
+    fn standings_ranks_share_ties() {
+        assert_eq!(standings_ranks(&[10, 30, 20, 30]), vec![3, 0, 2, 0]);
+    }
+
+    #[test]
+    fn best_finishes_from_view() {
+        let tournament = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1),
+            vacated: 0,
+        };
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 1 << 1 }];
+
+        let from_view = BestFinishes::for_view(&brackets, &TournamentView::new(&tournament));
+
+        assert_eq!(from_view.places(), DEFAULT_PLACES);
+        assert_eq!(from_view.possible_finishes[&brackets[0]], 0);
+        assert_eq!(from_view.possible_finishes[&brackets[1]], 0);
+    }
+
+    #[test]
+    fn best_finishes_with_open_first_round_game() {
+        // The last first-round game and every game it feeds are still open.
+        let open = [63, 31, 15, 7, 3, 1]
+            .iter()
+            .fold(0, |acc, s| acc | (1 << s));
+        let tournament = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !open,
+            vacated: 0,
+        };
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: open }];
+
+        let view = TournamentView::new(&tournament);
+        let best_finishes = BestFinishes::for_view(&brackets, &view);
+
+        assert_eq!(view.undecided(), &[63, 31, 15, 7, 3, 1]);
+        assert_eq!(best_finishes.possible_finishes[&brackets[0]], 0);
+    }
+
+    #[test]
+    fn best_finishes_match_full_ranking() {
+        let view = TournamentView::new(&Tournament {
+            decisions: 0x5555_5555_5555_5554,
+            mask: COMPLETE_MASK & !0xFE,
+            vacated: 0,
+        });
+        // Spread-out picks, plus a duplicate so ties straddle the top-5 cut.
+        let mut brackets: Vec<Bracket> = (1..12u64)
+            .map(|i| Bracket {
+                decisions: i.wrapping_mul(0x9E37_79B9_7F4A_7C15) & COMPLETE_MASK,
+            })
+            .collect();
+        brackets.push(brackets[3]);
+
+        // One winner's pool, the default five, and eight paid places.
+        for places in [1, DEFAULT_PLACES, 8] {
+            let mut expected: HashMap<Bracket, usize> = HashMap::new();
+            view.for_each_scenario(None, |team_slots| {
+                let scores: Vec<Score> = brackets
+                    .iter()
+                    .map(|b| b.points_for_decisions(team_slots, &ScoringSystem::standard()))
+                    .collect();
+                for (b, rank) in brackets.iter().zip(standings_ranks(&scores)) {
+                    if rank < places {
+                        let best = expected.entry(*b).or_insert(rank);
+                        *best = rank.min(*best);
+                    }
+                }
+            });
+
+            let best_finishes = BestFinishes::for_view_places(&brackets, &view, places);
+            assert_eq!(best_finishes.possible_finishes, expected);
+            assert_eq!(best_finishes.rankings().len(), places);
+        }
+    }
+
+    #[test]
+    fn merged_region_outcomes_match_full_ranking() {
+        // Through the second round, so each region has three open games.
+        let view = TournamentView::new(&testing::random_through(7, 2));
+        let scoring = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        // Brackets agreeing on most of the open games, so many region
+        // outcomes score the same and get merged.
+        let brackets: Vec<Bracket> = testing::random_brackets(3, 12)
+            .into_iter()
+            .map(|b| Bracket::from_decisions(b.decisions & 0xF0F0 | 0xFFFF_0000))
+            .collect();
+
+        let mut expected: HashMap<Bracket, usize> = HashMap::new();
+        view.for_each_scenario(None, |team_slots| {
+            let scores: Vec<Score> = brackets
+                .iter()
+                .map(|b| b.points_for_decisions(team_slots, &scoring))
+                .collect();
+            for (b, rank) in brackets.iter().zip(standings_ranks(&scores)) {
+                if rank < DEFAULT_PLACES {
+                    let best = expected.entry(*b).or_insert(rank);
+                    *best = rank.min(*best);
+                }
+            }
+        });
+
+        let best_finishes = BestFinishes::for_view_with(&brackets, &view, &scoring, DEFAULT_PLACES);
+        assert_eq!(best_finishes.possible_finishes, expected);
+    }
+
+    #[test]
+    fn finish_context_reuse_matches_fresh_runs() {
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 0b1010 }];
+        let views = [
+            TournamentView::new(&Tournament {
+                decisions: 0,
+                mask: COMPLETE_MASK & !0b1110,
+                vacated: 0,
+            }),
+            TournamentView::new(&Tournament {
+                decisions: 1 << 3,
+                mask: COMPLETE_MASK & !(1 << 1),
+                vacated: 0,
+            }),
+        ];
+
+        let mut context = FinishContext::new();
+        for view in &views {
+            assert_eq!(
+                context
+                    .best_finishes(
+                        &brackets,
+                        view,
+                        &ScoreDeltaTable::for_view(view, &brackets),
+                        DEFAULT_PLACES
+                    )
+                    .possible_finishes,
+                BestFinishes::for_view(&brackets, view).possible_finishes
+            );
+        }
+    }
+
+    #[test]
+    fn low_level_decision_bits() {
+        let mut tournament = Tournament {
+            decisions: 0,
+            mask: 0,
+            vacated: 0,
+        };
+        tournament.set_decision(40, 1);
+        tournament.set_decision(41, 0);
+
+        assert_eq!(tournament.decision(40), Some(1));
+        assert_eq!(tournament.decision(41), Some(0));
+        assert_eq!(tournament.decision(20), None);
+        assert_eq!(tournament.decided_mask_for_round(1), (1 << 40) | (1 << 41));
+        assert_eq!(tournament.decided_mask_for_round(2), 0);
+        assert_eq!(tournament.decision_team_slots()[40], Some(81));
+
+        let mut bracket = Bracket { decisions: 0 };
+        bracket.set_decision(1, 1);
+        assert_eq!(bracket.decision(1), Some(1));
+        bracket.set_decision(1, 0);
+        assert_eq!(bracket, Bracket { decisions: 0 });
+    }
+
+    #[test]
+    #[should_panic(expected = "no game in slot 0")]
+    fn set_decision_rejects_slot_zero() {
+        Bracket { decisions: 0 }.set_decision(0, 1);
+    }
+
+    #[test]
+    fn late_round_accessors() {
+        let chalk = Bracket { decisions: 0 };
+        assert_eq!(chalk.champion(), 64);
+        assert_eq!(chalk.runner_up(), 96);
+        assert_eq!(chalk.final_four(), [64, 80, 96, 112]);
+        assert_eq!(chalk.elite_eight(), [64, 72, 80, 88, 96, 104, 112, 120]);
+
+        let bottom = Bracket { decisions: 1 << 1 };
+        assert_eq!(bottom.champion(), 96);
+        assert_eq!(bottom.runner_up(), 64);
+    }
+
+    #[test]
+    fn scoring_probes() {
+        assert_eq!(points_for(1, 1), 2);
+        assert_eq!(points_for(6, 2), 15);
+        assert_eq!(points_for_pick(1, 64), points_for(6, 1));
+        assert_eq!(points_for_pick(32, 65), points_for(1, 16));
+        assert_eq!(ScoringSystem::standard().max_points_in_round(3), 19);
+    }
+
+    #[test]
+    fn constructors_clear_the_reserved_bit() {
+        let tournament = Tournament::new(u64::MAX, 1 | (1 << 32));
+        assert_eq!(tournament.mask(), 1 << 32);
+        assert_eq!(tournament.decisions(), 1 << 32);
+        assert_eq!(Bracket::from_decisions(1).decisions(), 0);
+
+        let finished = Tournament::new(0, COMPLETE_MASK);
+        let chalk = Bracket::from_decisions(0);
+        assert_eq!(
+            chalk.score(&finished),
+            TournamentView::new(&finished).score_batch(&[chalk])[0]
+        );
+    }
 }
@@ -0,0 +1,125 @@
+use crate::view::TournamentView;
+use crate::{standings_ranks, Bracket, Tournament};
+
+/// A single step in the history, between snapshots `step - 1` and `step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub step: usize,
+    /// Places gained, negative for a drop.
+    pub places: isize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoverStats {
+    /// Index into the brackets the stats were computed from.
+    pub entry: usize,
+    pub biggest_jump: Option<Move>,
+    pub biggest_drop: Option<Move>,
+    /// Standard deviation of the step-to-step rank changes.
+    pub volatility: f64,
+    /// Most consecutive snapshots the entry spent in first (ties included).
+    pub longest_in_first: usize,
+}
+
+/// Per-entry movement stats over a history of tournament snapshots, oldest
+/// first. Each consecutive pair of snapshots is one step.
+pub fn mover_stats(brackets: &[Bracket], history: &[Tournament]) -> Vec<MoverStats> {
+    let ranks: Vec<Vec<usize>> = history
+        .iter()
+        .map(|t| standings_ranks(&TournamentView::new(t).score_batch(brackets)))
+        .collect();
+
+    (0..brackets.len())
+        .map(|entry| {
+            let moves: Vec<Move> = ranks
+                .windows(2)
+                .enumerate()
+                .map(|(i, w)| Move {
+                    step: i + 1,
+                    places: w[0][entry] as isize - w[1][entry] as isize,
+                })
+                .collect();
+
+            let biggest_jump = moves.iter().filter(|m| m.places > 0).fold(
+                None,
+                |best: Option<Move>, m| match best {
+                    Some(b) if b.places >= m.places => Some(b),
+                    _ => Some(*m),
+                },
+            );
+            let biggest_drop =
+                moves
+                    .iter()
+                    .filter(|m| m.places < 0)
+                    .fold(None, |worst: Option<Move>, m| match worst {
+                        Some(w) if w.places <= m.places => Some(w),
+                        _ => Some(*m),
+                    });
+
+            let volatility = if moves.is_empty() {
+                0.0
+            } else {
+                let n = moves.len() as f64;
+                let mean = moves.iter().map(|m| m.places as f64).sum::<f64>() / n;
+                let variance = moves
+                    .iter()
+                    .map(|m| (m.places as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / n;
+                variance.sqrt()
+            };
+
+            let (mut run, mut longest_in_first) = (0, 0);
+            for snapshot in &ranks {
+                run = if snapshot[entry] == 0 { run + 1 } else { 0 };
+                longest_in_first = longest_in_first.max(run);
+            }
+
+            MoverStats {
+                entry,
+                biggest_jump,
+                biggest_drop,
+                volatility,
+                longest_in_first,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn tracks_jumps_drops_and_time_in_first() {
+        let state = |decisions, mask| Tournament {
+            decisions,
+            mask,
+            vacated: 0,
+        };
+        let open = COMPLETE_MASK & !0b1110;
+        // The semifinals, then the title game, go the first bracket's way;
+        // the second bracket only disagrees on the slot 3 semifinal.
+        let history = [
+            state(0, open),
+            state(0, open | (1 << 2) | (1 << 3)),
+            state(0, COMPLETE_MASK),
+        ];
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 1 << 3 }];
+
+        let stats = mover_stats(&brackets, &history);
+
+        assert_eq!(stats[0].biggest_jump, None);
+        assert_eq!(stats[0].longest_in_first, 3);
+        assert_eq!(
+            stats[1].biggest_drop,
+            Some(Move {
+                step: 1,
+                places: -1
+            })
+        );
+        assert_eq!(stats[1].longest_in_first, 1);
+        assert_eq!(stats[1].volatility, 0.5);
+    }
+}
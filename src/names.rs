@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+/// Common alternate names, as (alias, canonical name).
+const STANDARD_ALIASES: [(&str, &str); 16] = [
+    ("UConn", "Connecticut"),
+    ("UNC", "North Carolina"),
+    ("Ole Miss", "Mississippi"),
+    ("Pitt", "Pittsburgh"),
+    ("UCF", "Central Florida"),
+    ("USC", "Southern California"),
+    ("LSU", "Louisiana State"),
+    ("BYU", "Brigham Young"),
+    ("TCU", "Texas Christian"),
+    ("SMU", "Southern Methodist"),
+    ("VCU", "Virginia Commonwealth"),
+    ("UNLV", "Nevada-Las Vegas"),
+    ("UAB", "Alabama-Birmingham"),
+    ("UMass", "Massachusetts"),
+    ("Nova", "Villanova"),
+    ("Zags", "Gonzaga"),
+];
+
+/// The comparison key for a team name: lowercase words with punctuation
+/// dropped, and the ambiguous "St." spelled out — "Saint" when it leads the
+/// name ("St. John's"), "State" when it ends it ("Michigan St.").
+pub fn normalize_team_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter_map(|c| match c {
+            '-' | '&' | '/' => Some(' '),
+            c if c.is_alphanumeric() || c.is_whitespace() => Some(c.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect();
+
+    let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+    let last = words.len().saturating_sub(1);
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = match *word {
+            "st" if i == 0 => "saint",
+            "st" if i == last && i > 0 => "state",
+            "u" | "univ" => "university",
+            w => w,
+        };
+    }
+    words.join(" ")
+}
+
+/// Levenshtein distance between two strings, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + (ca != *cb) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Similarity of two normalized names, from 0.0 (nothing alike) to 1.0.
+fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(a, b) as f64 / longest as f64
+}
+
+/// A possible match for an imported name among the known team names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub name: String,
+    pub confidence: f64,
+}
+
+/// The outcome of matching an imported name, never a silent guess: anything
+/// short of the confidence threshold comes back for confirmation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeamMatch {
+    /// Same team by normalization or the alias table.
+    Exact(String),
+    /// The best candidate cleared the threshold.
+    Fuzzy(Candidate),
+    /// Ranked candidates, best first, none confident enough to use.
+    NeedsConfirmation(Vec<Candidate>),
+}
+
+/// Maps every known spelling of a team to one canonical name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AliasTable {
+    // normalized alias -> canonical name
+    aliases: HashMap<String, String>,
+}
+
+impl AliasTable {
+    pub fn new() -> AliasTable {
+        AliasTable::default()
+    }
+
+    /// A table preloaded with the usual abbreviations and nicknames.
+    pub fn standard() -> AliasTable {
+        let mut table = AliasTable::new();
+        for (alias, canonical) in STANDARD_ALIASES {
+            table.insert(alias, canonical);
+        }
+        table
+    }
+
+    /// Registers `alias` (and `canonical` itself) as names for `canonical`.
+    pub fn insert(&mut self, alias: &str, canonical: &str) {
+        self.aliases
+            .insert(normalize_team_name(canonical), canonical.to_string());
+        self.aliases
+            .insert(normalize_team_name(alias), canonical.to_string());
+    }
+
+    /// The canonical name for `name`, if it's a known spelling.
+    pub fn canonical(&self, name: &str) -> Option<&str> {
+        self.aliases
+            .get(&normalize_team_name(name))
+            .map(String::as_str)
+    }
+
+    /// True when both names refer to the same team under this table.
+    pub fn same_team(&self, a: &str, b: &str) -> bool {
+        let key = |name| match self.canonical(name) {
+            Some(canonical) => normalize_team_name(canonical),
+            None => normalize_team_name(name),
+        };
+        key(a) == key(b)
+    }
+
+    /// Every name in `known` scored against `name`, best first. A known name
+    /// scores by its closest spelling: itself or any alias that maps to it.
+    pub fn candidates(&self, name: &str, known: &[&str]) -> Vec<Candidate> {
+        let target = normalize_team_name(name);
+        let mut candidates: Vec<Candidate> = known
+            .iter()
+            .map(|&known_name| {
+                let known_key = normalize_team_name(known_name);
+                let confidence = self
+                    .aliases
+                    .iter()
+                    .filter(|(_, canonical)| normalize_team_name(canonical) == known_key)
+                    .map(|(alias, _)| similarity(&target, alias))
+                    .fold(similarity(&target, &known_key), f64::max);
+                Candidate {
+                    name: known_name.to_string(),
+                    confidence,
+                }
+            })
+            .collect();
+        candidates.sort_by(|c1, c2| c2.confidence.total_cmp(&c1.confidence));
+        candidates
+    }
+
+    /// Matches `name` against `known`, taking a fuzzy match only when its
+    /// confidence is at least `threshold`.
+    pub fn match_name(&self, name: &str, known: &[&str], threshold: f64) -> TeamMatch {
+        if let Some(&exact) = known.iter().find(|&&k| self.same_team(name, k)) {
+            return TeamMatch::Exact(exact.to_string());
+        }
+        let mut candidates = self.candidates(name, known);
+        match candidates.first() {
+            Some(best) if best.confidence >= threshold => TeamMatch::Fuzzy(candidates.remove(0)),
+            _ => TeamMatch::NeedsConfirmation(candidates),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_saint_and_state() {
+        assert_eq!(normalize_team_name("St. John's"), "saint johns");
+        assert_eq!(normalize_team_name("Saint John's"), "saint johns");
+        assert_eq!(normalize_team_name("Michigan St."), "michigan state");
+        assert_eq!(normalize_team_name("  Texas   A&M "), "texas a m");
+    }
+
+    #[test]
+    fn resolves_aliases() {
+        let table = AliasTable::standard();
+
+        assert_eq!(table.canonical("UConn"), Some("Connecticut"));
+        assert_eq!(table.canonical("connecticut"), Some("Connecticut"));
+        assert_eq!(table.canonical("Duke"), None);
+        assert!(table.same_team("UNLV", "Nevada Las Vegas"));
+        assert!(table.same_team("Iowa St", "Iowa State"));
+        assert!(!table.same_team("UNC", "NC State"));
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "duke"), 4);
+        assert_eq!(edit_distance("duke", "duke"), 0);
+    }
+
+    #[test]
+    fn fuzzy_matching_asks_below_threshold() {
+        let table = AliasTable::standard();
+        let known = ["Gonzaga", "Connecticut", "Georgia", "Villanova"];
+
+        assert_eq!(
+            table.match_name("UCONN", &known, 0.8),
+            TeamMatch::Exact("Connecticut".to_string())
+        );
+        match table.match_name("Gonzagaa", &known, 0.8) {
+            TeamMatch::Fuzzy(c) => assert_eq!(c.name, "Gonzaga"),
+            other => panic!("expected a fuzzy match, got {:?}", other),
+        }
+        // "Zag" is closest to Gonzaga through its "Zags" alias.
+        match table.match_name("Zag", &known, 0.8) {
+            TeamMatch::NeedsConfirmation(c) => {
+                assert_eq!(c.len(), known.len());
+                assert_eq!(c[0].name, "Gonzaga");
+            }
+            other => panic!("expected candidates, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::simulate::WinProbability;
+
+/// A bookmaker's price on one side of a game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Odds {
+    /// Moneyline odds: `+150` pays 150 on a 100 stake, `-200` needs a 200
+    /// stake to win 100.
+    American(i32),
+    /// The total returned per unit staked, stake included, e.g. `2.5`.
+    Decimal(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OddsError {
+    /// Neither a moneyline nor a decimal price.
+    Malformed(String),
+    /// A moneyline between -100 and +100, or a decimal price of 1 or less.
+    OutOfRange(String),
+}
+
+impl fmt::Display for OddsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OddsError::Malformed(text) => write!(f, "{:?} isn't a price", text),
+            OddsError::OutOfRange(text) => write!(f, "{:?} isn't a possible price", text),
+        }
+    }
+}
+
+impl std::error::Error for OddsError {}
+
+impl Odds {
+    /// Reads `+150` or `-200` as moneyline odds and `2.50` as decimal odds.
+    /// A price without a dot is always a moneyline, so a decimal price of
+    /// 3 must be written `3.0`; `3` is a moneyline inside -100 to +100 and
+    /// out of range.
+    pub fn parse(text: &str) -> Result<Odds, OddsError> {
+        let text = text.trim();
+        let malformed = || OddsError::Malformed(text.to_string());
+        let out_of_range = || OddsError::OutOfRange(text.to_string());
+        if text.contains('.') {
+            let price: f64 = text.parse().map_err(|_| malformed())?;
+            if price.is_nan() || price <= 1.0 {
+                return Err(out_of_range());
+            }
+            return Ok(Odds::Decimal(price));
+        }
+        let line: i32 = text.parse().map_err(|_| malformed())?;
+        if line.unsigned_abs() < 100 {
+            return Err(out_of_range());
+        }
+        Ok(Odds::American(line))
+    }
+
+    /// The probability the price implies, vig included.
+    pub fn implied_probability(&self) -> f64 {
+        match *self {
+            Odds::American(line) if line < 0 => {
+                let line = -f64::from(line);
+                line / (line + 100.0)
+            }
+            Odds::American(line) => 100.0 / (f64::from(line) + 100.0),
+            Odds::Decimal(price) => 1.0 / price,
+        }
+    }
+}
+
+/// Both sides' prices on one game, by team slot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moneyline {
+    pub team: u8,
+    pub opponent: u8,
+    pub team_odds: Odds,
+    pub opponent_odds: Odds,
+}
+
+impl Moneyline {
+    /// `team`'s chance with the vig taken out: both implied probabilities
+    /// scaled to sum to 1.
+    pub fn fair_probability(&self) -> f64 {
+        let team = self.team_odds.implied_probability();
+        team / (team + self.opponent_odds.implied_probability())
+    }
+
+    /// The bookmaker's margin: how far the implied probabilities sum past 1.
+    pub fn overround(&self) -> f64 {
+        self.team_odds.implied_probability() + self.opponent_odds.implied_probability() - 1.0
+    }
+}
+
+/// Where posted lines come from, e.g. an odds provider's API.
+pub trait OddsFeed {
+    type Error;
+
+    /// The lines posted now, for whichever games the feed has.
+    fn moneylines(&mut self) -> Result<Vec<Moneyline>, Self::Error>;
+}
+
+/// Win probabilities implied by posted lines, with the vig removed, and
+/// `fallback`'s for matchups without a line, such as games whose teams
+/// aren't known yet.
+#[derive(Debug, Clone)]
+pub struct MarketOdds<M> {
+    lines: HashMap<(u8, u8), f64>,
+    fallback: M,
+}
+
+impl<M: WinProbability> MarketOdds<M> {
+    pub fn new(fallback: M) -> MarketOdds<M> {
+        MarketOdds {
+            lines: HashMap::new(),
+            fallback,
+        }
+    }
+
+    /// Adds a line, replacing any earlier one on the same matchup.
+    pub fn insert(&mut self, line: &Moneyline) {
+        let (low, high) = (line.team.min(line.opponent), line.team.max(line.opponent));
+        let p = line.fair_probability();
+        let p_low = if line.team == low { p } else { 1.0 - p };
+        self.lines.insert((low, high), p_low);
+    }
+
+    /// Adds every line `feed` has posted, returning how many.
+    pub fn refresh<F: OddsFeed>(&mut self, feed: &mut F) -> Result<usize, F::Error> {
+        let lines = feed.moneylines()?;
+        for line in &lines {
+            self.insert(line);
+        }
+        Ok(lines.len())
+    }
+}
+
+impl<M: WinProbability> WinProbability for MarketOdds<M> {
+    fn win_probability(&self, team: u8, opponent: u8) -> f64 {
+        let (low, high) = (team.min(opponent), team.max(opponent));
+        match self.lines.get(&(low, high)) {
+            Some(&p_low) if team == low => p_low,
+            Some(&p_low) => 1.0 - p_low,
+            None => self.fallback.win_probability(team, opponent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Posted(Vec<Moneyline>);
+
+    impl OddsFeed for Posted {
+        type Error = OddsError;
+
+        fn moneylines(&mut self) -> Result<Vec<Moneyline>, OddsError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn parses_prices() {
+        assert_eq!(Odds::parse("+150"), Ok(Odds::American(150)));
+        assert_eq!(Odds::parse(" -200 "), Ok(Odds::American(-200)));
+        assert_eq!(Odds::parse("2.50"), Ok(Odds::Decimal(2.5)));
+        assert!(matches!(Odds::parse("-50"), Err(OddsError::OutOfRange(_))));
+        assert!(matches!(Odds::parse("0.9"), Err(OddsError::OutOfRange(_))));
+        assert!(matches!(Odds::parse("evens"), Err(OddsError::Malformed(_))));
+        assert_eq!(Odds::parse("-2147483648"), Ok(Odds::American(i32::MIN)));
+        assert!(matches!(Odds::parse("3"), Err(OddsError::OutOfRange(_))));
+        assert_eq!(Odds::parse("3.0"), Ok(Odds::Decimal(3.0)));
+
+        assert_eq!(Odds::American(-200).implied_probability(), 2.0 / 3.0);
+        assert_eq!(Odds::American(150).implied_probability(), 0.4);
+        assert_eq!(Odds::Decimal(2.5).implied_probability(), 0.4);
+    }
+
+    #[test]
+    fn removes_the_vig() {
+        // -110 both ways: a coin flip with about 4.8% overround.
+        let line = Moneyline {
+            team: 64,
+            opponent: 65,
+            team_odds: Odds::American(-110),
+            opponent_odds: Odds::American(-110),
+        };
+        assert!((line.fair_probability() - 0.5).abs() < 1e-12);
+        assert!((line.overround() - 0.0476).abs() < 1e-4);
+
+        let mut odds = MarketOdds::new(|_: u8, _: u8| 0.5);
+        let mut feed = Posted(vec![Moneyline {
+            team: 67,
+            opponent: 66,
+            team_odds: Odds::American(-300),
+            opponent_odds: Odds::American(250),
+        }]);
+        assert_eq!(odds.refresh(&mut feed), Ok(1));
+        let p = odds.win_probability(67, 66);
+        assert!((p - 0.75 / (0.75 + 100.0 / 350.0)).abs() < 1e-12);
+        assert!((odds.win_probability(66, 67) - (1.0 - p)).abs() < 1e-12);
+        assert_eq!(odds.win_probability(64, 65), 0.5);
+    }
+}
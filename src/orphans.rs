@@ -0,0 +1,167 @@
+use crate::partial::PartialBracket;
+use crate::Decisions;
+
+/// Picks as a spreadsheet holds them: the team (64..=127) picked to win each
+/// game slot. Unlike the decision bits of a [`crate::Bracket`], nothing
+/// stops this from naming a team the same entry knocked out earlier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamPicks {
+    pub picks: [Option<u8>; 64],
+}
+
+/// A pick of `team` in `slot` that can't happen under the entry's own picks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrphanedPick {
+    pub slot: u8,
+    pub team: u8,
+    /// The earlier game where the entry picked `team` to lose, if `team` is
+    /// in `slot`'s part of the bracket at all.
+    pub eliminated_in: Option<u8>,
+}
+
+/// The team `picks` has coming out of `child`, which for a first-round game
+/// is the team slot itself.
+fn feeds(picks: &[Option<u8>; 64], child: u8) -> Option<u8> {
+    if child >= 64 {
+        Some(child)
+    } else {
+        picks[child as usize]
+    }
+}
+
+/// The child of `slot` that `team` has to come through, if it plays there.
+fn side_for(slot: u8, team: u8) -> Option<u8> {
+    (1..=6)
+        .find(|&k| team >> k == slot)
+        .map(|k| team >> (k - 1))
+}
+
+impl TeamPicks {
+    /// Every pick naming a team that doesn't come out of either feeding game,
+    /// latest round last.
+    pub fn orphaned_picks(&self) -> Vec<OrphanedPick> {
+        (1..=63u8)
+            .rev()
+            .filter_map(|slot| {
+                let team = self.picks[slot as usize]?;
+                let sides = [
+                    feeds(&self.picks, slot * 2),
+                    feeds(&self.picks, slot * 2 + 1),
+                ];
+                if sides.contains(&Some(team)) {
+                    return None;
+                }
+                // Walk up from the team's first-round game to the first game
+                // the entry gave to someone else.
+                let eliminated_in = side_for(slot, team).map(|_| {
+                    let mut game = team / 2;
+                    while self.picks[game as usize] == Some(team) {
+                        game /= 2;
+                    }
+                    game
+                });
+                Some(OrphanedPick {
+                    slot,
+                    team,
+                    eliminated_in,
+                })
+            })
+            .collect()
+    }
+
+    /// Repairs orphaned picks, returning the consistent picks and the slots
+    /// that were changed. An orphan is replaced by whoever the entry has
+    /// coming out of the side its team would have come from; an orphan whose
+    /// team doesn't play in that game, or a missing pick, is left missing.
+    pub fn normalize(&self) -> (PartialBracket, Vec<u8>) {
+        let mut repaired = self.picks;
+        let mut changed = vec![];
+        let mut picks = PartialBracket {
+            decisions: 0,
+            mask: 0,
+        };
+
+        for slot in (1..=63u8).rev() {
+            let Some(team) = repaired[slot as usize] else {
+                continue;
+            };
+            let sides = [feeds(&repaired, slot * 2), feeds(&repaired, slot * 2 + 1)];
+            let winner = match sides.iter().position(|&s| s == Some(team)) {
+                Some(side) => Some(side as u8),
+                None => {
+                    changed.push(slot);
+                    side_for(slot, team)
+                        .map(|child| child - slot * 2)
+                        .filter(|&side| sides[side as usize].is_some())
+                }
+            };
+            repaired[slot as usize] = winner.and_then(|side| sides[side as usize]);
+            if let Some(side) = winner {
+                picks.set_decision(slot, side);
+            }
+        }
+
+        (picks, changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bracket;
+
+    fn chalk_picks() -> TeamPicks {
+        TeamPicks {
+            picks: Bracket { decisions: 0 }.decision_team_slots(),
+        }
+    }
+
+    #[test]
+    fn consistent_picks_have_no_orphans() {
+        let picks = chalk_picks();
+        assert!(picks.orphaned_picks().is_empty());
+        assert_eq!(
+            picks.normalize(),
+            (
+                PartialBracket {
+                    decisions: 0,
+                    mask: crate::COMPLETE_MASK
+                },
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn finds_and_repairs_orphans() {
+        let mut picks = chalk_picks();
+        // Team 65 loses in the first round but is still picked in round two,
+        // which also orphans 64's pick in the next game. Team 100 is picked
+        // in a region it isn't in, orphaning the picks after it.
+        picks.picks[16] = Some(65);
+        picks.picks[4] = Some(100);
+
+        let orphan = |slot, team, eliminated_in| OrphanedPick {
+            slot,
+            team,
+            eliminated_in,
+        };
+        assert_eq!(
+            picks.orphaned_picks(),
+            vec![
+                orphan(16, 65, Some(32)),
+                orphan(8, 64, Some(16)),
+                orphan(4, 100, None),
+                orphan(2, 64, Some(16)),
+            ]
+        );
+
+        // Restoring 64 in slot 16 un-orphans slot 8; the rest can't be saved.
+        let (normalized, changed) = picks.normalize();
+        assert_eq!(changed, vec![16, 4, 2, 1]);
+        let team_slots = normalized.decision_team_slots();
+        assert_eq!(team_slots[16], Some(64));
+        assert_eq!(team_slots[4], None);
+        assert_eq!(normalized.missing(), vec![4, 2, 1]);
+    }
+}
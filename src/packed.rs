@@ -0,0 +1,150 @@
+use crate::pool::Pool;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{standings_ranks, Bracket, Score, Tournament};
+
+/// A pool's active entries held as parallel arrays, for contests too big
+/// for a `Vec` of [`crate::pool::Entry`]: per entry, eight bytes of
+/// picks, four of adjustment, four marking where its name ends and the
+/// name's own bytes, with every owner's name in one shared buffer instead
+/// of a `String` each.
+///
+/// Entries are numbered in the order they were pushed. Owners aren't
+/// checked for repeats, since an index to check them against would cost
+/// more than the entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackedPool {
+    decisions: Vec<u64>,
+    // Each ledger's total, clamped to what fits.
+    adjustments: Vec<i32>,
+    // The owners back to back; entry `i`'s name ends at `ends[i]` and
+    // starts where the one before it ends.
+    names: String,
+    ends: Vec<u32>,
+}
+
+impl PackedPool {
+    pub fn new() -> PackedPool {
+        PackedPool::default()
+    }
+
+    /// Room for `entries` entries whose names total `name_bytes` bytes.
+    pub fn with_capacity(entries: usize, name_bytes: usize) -> PackedPool {
+        PackedPool {
+            decisions: Vec::with_capacity(entries),
+            adjustments: Vec::with_capacity(entries),
+            names: String::with_capacity(name_bytes),
+            ends: Vec::with_capacity(entries),
+        }
+    }
+
+    /// The active entries of `pool` in its order, ledgers totalled.
+    pub fn from_pool(pool: &Pool) -> PackedPool {
+        let active = pool.active_entries();
+        let name_bytes = active.iter().map(|e| e.owner.len()).sum();
+        let mut packed = PackedPool::with_capacity(active.len(), name_bytes);
+        for entry in active {
+            packed.push(&entry.owner, entry.bracket, entry.adjustment());
+        }
+        packed
+    }
+
+    /// Adds `owner`'s entry with `adjustment` points on top of its score.
+    pub fn push(&mut self, owner: &str, bracket: Bracket, adjustment: i64) {
+        self.decisions.push(bracket.decisions);
+        self.adjustments
+            .push(adjustment.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+        self.names.push_str(owner);
+        self.ends.push(self.names.len() as u32);
+    }
+
+    pub fn len(&self) -> usize {
+        self.decisions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+
+    pub fn owner(&self, entry: usize) -> &str {
+        let start = match entry {
+            0 => 0,
+            _ => self.ends[entry - 1] as usize,
+        };
+        &self.names[start..self.ends[entry] as usize]
+    }
+
+    pub fn bracket(&self, entry: usize) -> Bracket {
+        Bracket::from_decisions(self.decisions[entry])
+    }
+
+    pub fn adjustment(&self, entry: usize) -> i64 {
+        self.adjustments[entry] as i64
+    }
+
+    /// Every entry's picks, in entry order, for the crate's searches.
+    pub fn brackets(&self) -> Vec<Bracket> {
+        self.decisions
+            .iter()
+            .map(|&d| Bracket::from_decisions(d))
+            .collect()
+    }
+
+    /// Every entry's score against `tournament` under `scoring`, adjustments
+    /// applied, in entry order. The tournament's teams are worked out once
+    /// for the whole pool.
+    pub fn scores(&self, tournament: &Tournament, scoring: &ScoringSystem) -> Vec<Score> {
+        let view = TournamentView::new(tournament);
+        self.decisions
+            .iter()
+            .zip(&self.adjustments)
+            .map(|(&d, &adjustment)| {
+                view.score_with(&Bracket::from_decisions(d), scoring)
+                    .saturating_add_signed(adjustment as i64)
+            })
+            .collect()
+    }
+
+    /// Every entry's zero-based rank, ties sharing the better one, in entry
+    /// order.
+    pub fn ranks(&self, tournament: &Tournament, scoring: &ScoringSystem) -> Vec<usize> {
+        standings_ranks(&self.scores(tournament, scoring))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_brackets;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn packs_the_active_entries() {
+        let tournament = Tournament::new(0, COMPLETE_MASK & !0xFE);
+        let mut pool = Pool::new(tournament.clone(), ScoringSystem::doubling());
+        for (i, bracket) in random_brackets(3, 6).into_iter().enumerate() {
+            pool.insert(&format!("entry {}", i), bracket);
+        }
+        pool.insert("", Bracket::from_decisions(0));
+        pool.adjust("entry 2", -4, "late swap").unwrap();
+        pool.disqualify("entry 4", "duplicate").unwrap();
+
+        let packed = PackedPool::from_pool(&pool);
+        assert_eq!(packed.len(), 6);
+        assert_eq!(packed.owner(0), "entry 0");
+        assert_eq!(packed.owner(4), "entry 5");
+        assert_eq!(packed.owner(5), "");
+        assert_eq!(packed.bracket(5), Bracket::from_decisions(0));
+        assert_eq!(packed.adjustment(2), -4);
+
+        let scores = packed.scores(&tournament, pool.scoring());
+        let ranks = packed.ranks(&tournament, pool.scoring());
+        for standing in pool.leaderboard() {
+            let entry = (0..packed.len())
+                .find(|&i| packed.owner(i) == standing.owner)
+                .unwrap();
+            assert_eq!(scores[entry], standing.score);
+            assert_eq!(ranks[entry], standing.rank);
+        }
+    }
+}
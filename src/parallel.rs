@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{fix_sides, BestFinishes, Bracket, FinishContext};
+
+/// A bracket the threaded and single-threaded best-finish searches don't
+/// agree on, from [`TournamentView::best_finishes_checked`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disagreement {
+    pub bracket: Bracket,
+    pub parallel: Option<usize>,
+    pub serial: Option<usize>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bracket {:#x} finishes {:?} threaded but {:?} single-threaded",
+            self.bracket.decisions, self.parallel, self.serial
+        )
+    }
+}
+
+impl std::error::Error for Disagreement {}
+
+impl TournamentView {
+    /// Each bracket's best zero-based finish in the top `places` under
+    /// `scoring`, brackets that can't get there left out, with the search
+    /// spread over `threads` threads. The answer is the single-threaded
+    /// search's, as behind `Pool::best_finishes`.
+    pub fn best_finishes_parallel(
+        &self,
+        brackets: &[Bracket],
+        places: usize,
+        scoring: &ScoringSystem,
+        threads: usize,
+    ) -> HashMap<Bracket, usize> {
+        BestFinishes::for_view_parallel(brackets, self, scoring, places, threads).possible_finishes
+    }
+
+    /// [`TournamentView::best_finishes_parallel`] checked against the
+    /// single-threaded search, for verifying a build or a machine. The first
+    /// bracket in `brackets` whose finishes differ is the error.
+    pub fn best_finishes_checked(
+        &self,
+        brackets: &[Bracket],
+        places: usize,
+        scoring: &ScoringSystem,
+        threads: usize,
+    ) -> Result<HashMap<Bracket, usize>, Disagreement> {
+        let parallel = self.best_finishes_parallel(brackets, places, scoring, threads);
+        let serial = BestFinishes::for_view_with(brackets, self, scoring, places).possible_finishes;
+        for &bracket in brackets {
+            let (threaded, single) = (parallel.get(&bracket), serial.get(&bracket));
+            if threaded != single {
+                return Err(Disagreement {
+                    bracket,
+                    parallel: threaded.copied(),
+                    serial: single.copied(),
+                });
+            }
+        }
+        Ok(parallel)
+    }
+}
+
+impl BestFinishes {
+    /// [`BestFinishes::for_view_with`] spread over `threads` threads. The
+    /// first few undecided games are fixed each way they can go, and every
+    /// thread takes the resulting subtrees one at a time until none are
+    /// left, so a thread that draws small subtrees picks up more of them.
+    fn for_view_parallel(
+        brackets: &[Bracket],
+        view: &TournamentView,
+        scoring: &ScoringSystem,
+        places: usize,
+        threads: usize,
+    ) -> BestFinishes {
+        let threads = threads.max(1);
+        // About eight subtrees per thread evens out the uneven ones.
+        let split = view
+            .undecided()
+            .len()
+            .min((threads * 8).next_power_of_two().trailing_zeros() as usize);
+        let (prefix, rest) = view.undecided().split_at(split);
+        let deltas = ScoreDeltaTable::for_view_with(view, brackets, scoring);
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut context = FinishContext::new();
+                        let mut best_finishes = BestFinishes::new(places);
+                        loop {
+                            let subtree = next.fetch_add(1, Ordering::Relaxed);
+                            if subtree >= 1 << split {
+                                break;
+                            }
+                            let mut team_slots = view.team_slots().to_vec();
+                            if fix_sides(&mut team_slots, prefix, subtree) {
+                                context.fill(
+                                    brackets,
+                                    &deltas,
+                                    &mut team_slots,
+                                    rest,
+                                    &mut best_finishes,
+                                );
+                            }
+                        }
+                        best_finishes
+                    })
+                })
+                .collect();
+
+            let mut best_finishes = BestFinishes::new(places);
+            for worker in workers {
+                best_finishes.merge(worker.join().unwrap());
+            }
+            best_finishes
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{round_slot_mask, testing, Tournament};
+
+    #[test]
+    fn matches_the_single_threaded_walk() {
+        // The Sweet 16 and on are left: 15 games.
+        let view = TournamentView::new(&Tournament::new(
+            0b0110_1001 << 32,
+            round_slot_mask(1) | round_slot_mask(2),
+        ));
+        let brackets: Vec<Bracket> = (0..12u64)
+            .map(|i| Bracket::from_decisions(i.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+            .collect();
+
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        for (scoring, places) in [(ScoringSystem::standard(), 5), (upsets, 3)] {
+            let expected = BestFinishes::for_view_with(&brackets, &view, &scoring, places);
+            for threads in [1, 3, 8] {
+                let parallel = view.best_finishes_parallel(&brackets, places, &scoring, threads);
+                assert_eq!(parallel, expected.possible_finishes);
+            }
+        }
+    }
+
+    #[test]
+    fn answers_the_same_every_run() {
+        let view = TournamentView::new(&testing::random_through(11, 2));
+        let brackets = testing::random_brackets(5, 16);
+        let scoring = ScoringSystem::standard();
+
+        let first = view
+            .best_finishes_checked(&brackets, 4, &scoring, 4)
+            .unwrap();
+        for threads in [1, 2, 4, 7, 16] {
+            for _ in 0..3 {
+                assert_eq!(
+                    view.best_finishes_checked(&brackets, 4, &scoring, threads),
+                    Ok(first.clone())
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+
+use crate::scoring::ScoringSystem;
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::{
+    checked_decision_bit, points_for_team_slots, seed_for_slot, Bracket, Decisions, Score,
+    COMPLETE_MASK,
+};
+
+/// An entry with some picks missing; `mask` marks the slots that have one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PartialBracket {
+    pub decisions: u64,
+    pub mask: u64,
+}
+
+impl Decisions for PartialBracket {
+    fn decisions(&self) -> u64 {
+        self.decisions
+    }
+
+    fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    fn set_decision(&mut self, slot: u8, winner: u8) {
+        let position = checked_decision_bit(slot, winner);
+        self.decisions = (self.decisions & !position) | (position * winner as u64);
+        self.mask |= position;
+    }
+}
+
+/// What a pool does with an entry's missing picks.
+#[derive(Copy, Clone)]
+pub enum MissingPicks<'m> {
+    /// Leave them missing; they never score.
+    Losses,
+    /// Fill each with the better seed, taking the top side on equal seeds.
+    Chalk,
+    /// Fill each with the team the model favors, as [`Favorites`] does.
+    Favorites(&'m dyn WinProbability),
+}
+
+impl fmt::Debug for MissingPicks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MissingPicks::Losses => write!(f, "Losses"),
+            MissingPicks::Chalk => write!(f, "Chalk"),
+            MissingPicks::Favorites(_) => write!(f, "Favorites(..)"),
+        }
+    }
+}
+
+/// An entry after its pool's policy was applied, with a record of the slots
+/// that were filled in on the entrant's behalf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub picks: PartialBracket,
+    pub filled: Vec<u8>,
+}
+
+impl PartialBracket {
+    /// Game slots without a pick, latest round last.
+    pub fn missing(&self) -> Vec<u8> {
+        (1..=63u8)
+            .rev()
+            .filter(|&slot| self.mask & (1 << slot) == 0)
+            .collect()
+    }
+
+    /// The full bracket, once every game has a pick.
+    pub fn to_bracket(self) -> Option<Bracket> {
+        (self.mask & COMPLETE_MASK == COMPLETE_MASK).then_some(Bracket {
+            decisions: self.decisions & COMPLETE_MASK,
+        })
+    }
+
+    pub fn resolve(&self, policy: MissingPicks) -> Resolved {
+        match policy {
+            MissingPicks::Losses => Resolved {
+                picks: *self,
+                filled: vec![],
+            },
+            MissingPicks::Chalk => self.fill(&Chalk),
+            MissingPicks::Favorites(model) => self.fill(&Favorites(|team, opponent| {
+                model.win_probability(team, opponent)
+            })),
+        }
+    }
+
+    /// The full bracket with every missing pick made by `strategy`, and the
+    /// slots it filled, latest round last.
+    pub fn complete_with<S: PickStrategy + ?Sized>(&self, strategy: &S) -> Completed {
+        let Resolved { picks, filled } = self.fill(strategy);
+        Completed {
+            bracket: Bracket {
+                decisions: picks.decisions & COMPLETE_MASK,
+            },
+            filled,
+        }
+    }
+
+    fn fill<S: PickStrategy + ?Sized>(&self, strategy: &S) -> Resolved {
+        let mut picks = *self;
+        let mut filled = vec![];
+        let mut team_slots: [Option<u8>; 128] = [None; 128];
+        for (team, slot) in team_slots.iter_mut().enumerate().skip(64) {
+            *slot = Some(team as u8);
+        }
+
+        for slot in (1..=63u8).rev() {
+            let bit = 1u64 << slot;
+            let idx = slot as usize;
+            if picks.mask & bit == 0 {
+                let side = strategy.side(slot, [team_slots[idx * 2], team_slots[idx * 2 + 1]]);
+                picks.set_decision(slot, side);
+                filled.push(slot);
+            }
+            let decision = (picks.decisions & bit != 0) as usize;
+            team_slots[idx] = team_slots[idx * 2 + decision];
+        }
+
+        Resolved { picks, filled }
+    }
+}
+
+/// A full bracket made from a partial one, with a record of the slots that
+/// were filled in automatically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completed {
+    pub bracket: Bracket,
+    pub filled: Vec<u8>,
+}
+
+/// Makes a missing pick: which side (0 or 1) of `slot` wins, given the teams
+/// picked to come from each side.
+pub trait PickStrategy {
+    fn side(&self, slot: u8, teams: [Option<u8>; 2]) -> u8;
+}
+
+/// The better seed, taking the top side on equal seeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chalk;
+
+impl PickStrategy for Chalk {
+    fn side(&self, _slot: u8, teams: [Option<u8>; 2]) -> u8 {
+        let seed = |team: Option<u8>| team.map_or(u8::MAX, seed_for_slot);
+        (seed(teams[1]) < seed(teams[0])) as u8
+    }
+}
+
+/// Whichever team `model` gives the better chance, taking the top side at
+/// even odds.
+#[derive(Debug, Clone, Copy)]
+pub struct Favorites<M>(pub M);
+
+impl<M: WinProbability> PickStrategy for Favorites<M> {
+    fn side(&self, slot: u8, teams: [Option<u8>; 2]) -> u8 {
+        match teams {
+            [Some(a), Some(b)] => (self.0.win_probability(a, b) < 0.5) as u8,
+            _ => Chalk.side(slot, teams),
+        }
+    }
+}
+
+impl TournamentView {
+    pub fn score_partial(&self, picks: &PartialBracket) -> Score {
+        self.score_partial_with(picks, &ScoringSystem::standard())
+    }
+
+    /// Points for `picks` under `scoring`; missing picks score nothing.
+    pub fn score_partial_with(&self, picks: &PartialBracket, scoring: &ScoringSystem) -> Score {
+        points_for_team_slots(self.team_slots(), &picks.decision_team_slots(), scoring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tournament;
+
+    #[test]
+    fn missing_picks_policies() {
+        // Everything picked except the title game and the 8/9 game in slot 33.
+        let partial = PartialBracket {
+            decisions: 0,
+            mask: COMPLETE_MASK & !((1 << 1) | (1 << 33)),
+        };
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK,
+            vacated: 0,
+        });
+
+        let losses = partial.resolve(MissingPicks::Losses);
+        assert!(losses.filled.is_empty());
+        assert_eq!(losses.picks.to_bracket(), None);
+        assert_eq!(partial.missing(), vec![33, 1]);
+        assert!(view.score_partial(&losses.picks) < view.score(&Bracket { decisions: 0 }));
+
+        let chalk = partial.resolve(MissingPicks::Chalk);
+        assert_eq!(chalk.filled, vec![33, 1]);
+        // The 8 seed beats the 9, and two 1 seeds meet in the final.
+        assert_eq!(chalk.picks.to_bracket(), Some(Bracket { decisions: 0 }));
+
+        // A model that always likes the higher team slot.
+        let underdogs = |team: u8, opponent: u8| if team > opponent { 0.9 } else { 0.1 };
+        let favorites = partial.resolve(MissingPicks::Favorites(&underdogs));
+        assert_eq!(favorites.filled, vec![33, 1]);
+        let bracket = favorites.picks.to_bracket().unwrap();
+        assert_eq!(bracket.decision(33), Some(1));
+        assert_eq!(bracket.champion(), 96);
+
+        let doubling = ScoringSystem::doubling();
+        assert_eq!(
+            view.score_partial_with(&chalk.picks, &doubling),
+            view.score_with(&Bracket { decisions: 0 }, &doubling)
+        );
+        assert_eq!(
+            view.score_partial_with(&losses.picks, &doubling),
+            view.score_with(&Bracket { decisions: 0 }, &doubling) - 32 - 1
+        );
+    }
+
+    #[test]
+    fn completes_with_a_strategy() {
+        let partial = PartialBracket {
+            decisions: 0,
+            mask: COMPLETE_MASK & !((1 << 1) | (1 << 33)),
+        };
+        assert_eq!(
+            partial.complete_with(&Chalk),
+            Completed {
+                bracket: Bracket { decisions: 0 },
+                filled: vec![33, 1]
+            }
+        );
+
+        // A model that always likes the higher team slot.
+        let underdogs = Favorites(|team: u8, opponent: u8| if team > opponent { 0.9 } else { 0.1 });
+        let completed = partial.complete_with(&underdogs);
+        assert_eq!(completed.filled, vec![33, 1]);
+        assert_eq!(completed.bracket.decision(33), Some(1));
+        assert_eq!(completed.bracket.champion(), 96);
+        // Picks that were made stay as they were.
+        assert_eq!(completed.bracket.decision(32), Some(0));
+    }
+}
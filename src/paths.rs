@@ -0,0 +1,93 @@
+use crate::contest::{for_each_ranking, BracketContest};
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::Bracket;
+
+/// In how many of the remaining scenarios an entry finishes first, in the
+/// top 3, and in the paid places. Ties share the better place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PathsToVictory {
+    pub wins: u64,
+    pub top_three: u64,
+    pub cashes: u64,
+}
+
+impl PathsToVictory {
+    /// `count` as a share of the `scenarios` it was counted from: the
+    /// probability when every remaining scenario is equally likely.
+    pub fn share(count: u64, scenarios: u64) -> f64 {
+        if scenarios == 0 {
+            0.0
+        } else {
+            count as f64 / scenarios as f64
+        }
+    }
+}
+
+impl TournamentView {
+    /// [`PathsToVictory`] for each bracket, with `paid_places` finishing
+    /// positions paying out, and the number of scenarios they were counted
+    /// over. Every remaining scenario is scored.
+    pub fn paths_to_victory(
+        &self,
+        brackets: &[Bracket],
+        paid_places: usize,
+    ) -> (Vec<PathsToVictory>, u64) {
+        self.paths_to_victory_with(brackets, paid_places, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::paths_to_victory`] under `scoring`.
+    pub fn paths_to_victory_with(
+        &self,
+        brackets: &[Bracket],
+        paid_places: usize,
+        scoring: &ScoringSystem,
+    ) -> (Vec<PathsToVictory>, u64) {
+        let contest = BracketContest::new(self, brackets, scoring);
+        let mut paths = vec![PathsToVictory::default(); brackets.len()];
+        let scenarios = for_each_ranking(&contest, |ranks| {
+            for (path, &rank) in paths.iter_mut().zip(ranks) {
+                path.wins += (rank == 0) as u64;
+                path.top_three += (rank < 3) as u64;
+                path.cashes += (rank < paid_places) as u64;
+            }
+        });
+        (paths, scenarios)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn counts_scenarios_by_finish() {
+        // Both semifinals and the title game are left.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        });
+        let brackets = [
+            Bracket { decisions: 0 },
+            Bracket { decisions: 1 << 1 },
+            Bracket { decisions: 1 << 2 },
+        ];
+
+        let (paths, scenarios) = view.paths_to_victory(&brackets, 1);
+        assert_eq!(scenarios, 8);
+        for path in &paths {
+            assert_eq!(path.top_three, scenarios);
+            assert_eq!(path.cashes, path.wins);
+        }
+        assert_eq!(
+            paths.iter().map(|p| p.wins).collect::<Vec<_>>(),
+            vec![3, 3, 3]
+        );
+        assert_eq!(
+            PathsToVictory::share(paths[0].wins, scenarios),
+            paths[0].wins as f64 / 8.0
+        );
+    }
+}
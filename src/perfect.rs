@@ -0,0 +1,82 @@
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Tournament};
+
+/// The pool's last perfect brackets going down together: each entry (as an
+/// index) with the game that busted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastPerfectBusted {
+    pub busted: Vec<(usize, u8)>,
+}
+
+impl TournamentView {
+    /// Entries (as indices) with every decided game right. Vacated games have
+    /// no right answer and don't count against anyone.
+    pub fn perfect_entries(&self, brackets: &[Bracket]) -> Vec<usize> {
+        (0..brackets.len())
+            .filter(|&entry| self.first_miss(&brackets[entry]).is_none())
+            .collect()
+    }
+
+    /// The first game `bracket` got wrong, in the order games are played
+    /// (highest slot first).
+    fn first_miss(&self, bracket: &Bracket) -> Option<u8> {
+        let picks = bracket.decision_team_slots();
+        (1..=63u8).rev().find(|&slot| {
+            let winner = self.team_slots()[slot as usize];
+            winner.is_some() && winner != picks[slot as usize]
+        })
+    }
+}
+
+/// Whether going from `before` to `after` busted the last perfect brackets
+/// in the pool. `None` if some entry is still perfect, or none was before.
+pub fn last_perfect_busted(
+    brackets: &[Bracket],
+    before: &Tournament,
+    after: &Tournament,
+) -> Option<LastPerfectBusted> {
+    let (before, after) = (TournamentView::new(before), TournamentView::new(after));
+    let perfect = before.perfect_entries(brackets);
+    if perfect.is_empty() || !after.perfect_entries(brackets).is_empty() {
+        return None;
+    }
+    let busted = perfect
+        .into_iter()
+        .filter_map(|entry| Some((entry, after.first_miss(&brackets[entry])?)))
+        .collect();
+    Some(LastPerfectBusted { busted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_busting_game() {
+        let brackets = [Bracket::from_decisions(0), Bracket::from_decisions(1 << 40)];
+        let start = Tournament::new(0, 0);
+        let mut first = start.clone();
+        first.set_decision(63, 0);
+        let view = TournamentView::new(&first);
+        assert_eq!(view.perfect_entries(&brackets), vec![0, 1]);
+
+        // Game 40 goes to the odd team: only entry 1 survives.
+        let mut second = first.clone();
+        second.set_decision(40, 1);
+        assert_eq!(
+            TournamentView::new(&second).perfect_entries(&brackets),
+            vec![1]
+        );
+        assert_eq!(last_perfect_busted(&brackets, &first, &second), None);
+
+        let mut third = second.clone();
+        third.set_decision(33, 1);
+        assert_eq!(
+            last_perfect_busted(&brackets, &second, &third),
+            Some(LastPerfectBusted {
+                busted: vec![(1, 33)]
+            })
+        );
+        assert_eq!(last_perfect_busted(&brackets, &third, &third), None);
+    }
+}
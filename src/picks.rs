@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::field::Field;
+use crate::partial::{MissingPicks, PartialBracket, Resolved};
+use crate::{Bracket, Decisions};
+
+/// How far a pick takes a team: the round it's picked to reach, or
+/// `Champion` for winning it all. `Second` is the second round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Round {
+    Second = 1,
+    Sweet16,
+    EliteEight,
+    FinalFour,
+    Championship,
+    Champion,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickError {
+    /// No team in the field goes by this name.
+    UnknownTeam(String),
+    /// `slot` already has `picked` winning it, so `wanted` can't.
+    Conflict {
+        slot: u8,
+        picked: String,
+        wanted: String,
+    },
+    /// Games without a pick, when a full bracket was asked for.
+    Incomplete { missing: usize },
+}
+
+impl fmt::Display for PickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickError::UnknownTeam(name) => write!(f, "no team named {:?}", name),
+            PickError::Conflict {
+                slot,
+                picked,
+                wanted,
+            } => write!(
+                f,
+                "{} is already picked to win game {}, so {} can't be",
+                picked, slot, wanted
+            ),
+            PickError::Incomplete { missing } => write!(f, "{} games have no pick", missing),
+        }
+    }
+}
+
+impl std::error::Error for PickError {}
+
+/// Fills in a bracket by team name. Picking a team to reach a round also
+/// picks it to win every game on the way there.
+#[derive(Debug, Clone)]
+pub struct BracketBuilder<'a> {
+    field: &'a Field,
+    // The picked winner of each game slot.
+    winners: [Option<u8>; 64],
+}
+
+impl<'a> BracketBuilder<'a> {
+    pub fn new(field: &'a Field) -> BracketBuilder<'a> {
+        BracketBuilder {
+            field,
+            winners: [None; 64],
+        }
+    }
+
+    /// Picks the team named `name` (as [`Field::find`] matches it, aliases
+    /// included) to reach
+    /// `round`. Fails without changing anything if another team was already
+    /// picked to win one of the games.
+    pub fn pick(&mut self, name: &str, round: Round) -> Result<(), PickError> {
+        let team = self
+            .field
+            .find(name)
+            .ok_or_else(|| PickError::UnknownTeam(name.to_string()))?;
+        let games = (1..=round as u8).map(|wins| team.slot >> wins);
+        for slot in games.clone() {
+            if let Some(picked) = self.winners[slot as usize].filter(|&p| p != team.slot) {
+                return Err(PickError::Conflict {
+                    slot,
+                    picked: self.field.team(picked).name.clone(),
+                    wanted: team.name.clone(),
+                });
+            }
+        }
+        for slot in games {
+            self.winners[slot as usize] = Some(team.slot);
+        }
+        Ok(())
+    }
+
+    /// The picks so far.
+    pub fn partial(&self) -> PartialBracket {
+        let mut partial = PartialBracket {
+            decisions: 0,
+            mask: 0,
+        };
+        for slot in 1..=63u8 {
+            if let Some(team) = self.winners[slot as usize] {
+                // The team won `wins` games to get here; the side it came
+                // from is the bit just below them.
+                let wins = slot.leading_zeros() - 1;
+                partial.set_decision(slot, (team >> (wins - 1)) & 1);
+            }
+        }
+        partial
+    }
+
+    /// The picks so far with the pool's `policy` applied to the rest, as
+    /// when an entry is imported, and the slots it filled.
+    pub fn resolve(&self, policy: MissingPicks) -> Resolved {
+        self.partial().resolve(policy)
+    }
+
+    pub fn build(&self) -> Result<Bracket, PickError> {
+        let partial = self.partial();
+        partial.to_bracket().ok_or(PickError::Incomplete {
+            missing: partial.missing().len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::standard_field;
+
+    #[test]
+    fn picks_fill_in_the_path() {
+        let field = standard_field();
+        let mut builder = BracketBuilder::new(&field);
+        builder.pick("West 16", Round::Champion).unwrap();
+        let partial = builder.partial();
+        assert_eq!(partial.missing().len(), 63 - 6);
+        let resolved = builder.resolve(MissingPicks::Chalk);
+        assert_eq!(resolved.filled.len(), 63 - 6);
+        assert_eq!(resolved.picks.to_bracket().map(|b| b.champion()), Some(81));
+        assert_eq!(field.winner(&partial, 1).map(|t| t.slot), Some(81));
+        assert_eq!(field.winner(&partial, 40).map(|t| t.slot), Some(81));
+
+        builder.pick("midwest 2", Round::FinalFour).unwrap();
+        let mut renamed = field.clone();
+        renamed.set_name(96, "Connecticut");
+        let mut alias_builder = BracketBuilder::new(&renamed);
+        alias_builder.pick("UConn", Round::Sweet16).unwrap();
+        assert_eq!(
+            renamed.winner(&alias_builder.partial(), 24).map(|t| t.slot),
+            Some(96)
+        );
+        assert_eq!(builder.pick("Midwest 1", Round::EliteEight), Ok(()));
+        assert_eq!(
+            builder.pick("Midwest 1", Round::FinalFour),
+            Err(PickError::Conflict {
+                slot: 7,
+                picked: "Midwest 2".to_string(),
+                wanted: "Midwest 1".to_string()
+            })
+        );
+        assert_eq!(
+            builder.pick("Nowhere", Round::Second),
+            Err(PickError::UnknownTeam("Nowhere".to_string()))
+        );
+        assert!(matches!(builder.build(), Err(PickError::Incomplete { .. })));
+    }
+
+    #[test]
+    fn builds_a_full_bracket() {
+        let field = standard_field();
+        let mut builder = BracketBuilder::new(&field);
+        // The top team of every game, a round at a time.
+        let rounds = [
+            Round::Second,
+            Round::Sweet16,
+            Round::EliteEight,
+            Round::FinalFour,
+            Round::Championship,
+            Round::Champion,
+        ];
+        for round in rounds {
+            for slot in (64..128).step_by(1 << round as u8) {
+                builder.pick(&field.team(slot).name, round).unwrap();
+            }
+        }
+        assert_eq!(builder.build(), Ok(Bracket::from_decisions(0)));
+    }
+}
@@ -0,0 +1,500 @@
+use std::fmt;
+
+use crate::contest::{best_finishes, for_each_ranking, BracketContest};
+use crate::field::{Field, Team};
+use crate::paths::PathsToVictory;
+use crate::results::{GameResult, ResultError};
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{standings_ranks, BestFinishes, Bracket, Score, Tournament, ENUMERATION_LIMIT};
+
+/// A bracket and the person who filled it out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub owner: String,
+    pub bracket: Bracket,
+    pub status: EntryStatus,
+    /// The commissioner's adjustments to the bracket's score, oldest first.
+    pub ledger: Vec<Adjustment>,
+}
+
+/// Points added to an entry's score, or taken off when negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adjustment {
+    pub points: i64,
+    pub note: String,
+}
+
+impl Entry {
+    /// Whether the entry is still in the standings.
+    pub fn is_active(&self) -> bool {
+        self.status == EntryStatus::Active
+    }
+
+    /// The ledger's total.
+    pub fn adjustment(&self) -> i64 {
+        self.ledger
+            .iter()
+            .fold(0, |total, a| total.saturating_add(a.points))
+    }
+
+    /// `score` with the ledger applied, never going below zero.
+    pub fn adjusted(&self, score: Score) -> Score {
+        score.saturating_add_signed(self.adjustment())
+    }
+}
+
+/// Whether an entry still competes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    Active,
+    /// Removed by the commissioner; kept on record but out of the standings.
+    Disqualified,
+    /// Pulled out by its owner; out of the standings the same way.
+    Withdrawn,
+}
+
+/// A commissioner's action, as kept in [`Pool::log`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolAction {
+    Disqualified {
+        owner: String,
+        reason: String,
+    },
+    Withdrawn {
+        owner: String,
+        reason: String,
+    },
+    /// `points` added to the entry's score, or taken off when negative.
+    Adjusted {
+        owner: String,
+        points: i64,
+        reason: String,
+    },
+}
+
+/// Why a commissioner's action was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolError {
+    /// No entry by this owner.
+    UnknownOwner(String),
+    /// The entry is already out of the standings.
+    Inactive(String),
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::UnknownOwner(owner) => write!(f, "no entry for {}", owner),
+            PoolError::Inactive(owner) => write!(f, "{} is out of the standings", owner),
+        }
+    }
+}
+
+impl std::error::Error for PoolError {}
+
+/// One row of a pool's leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub owner: String,
+    /// The ledger included.
+    pub score: Score,
+    /// The ledger's total, for showing beside the score.
+    pub adjustment: i64,
+    /// Zero-based, ties sharing the better place.
+    pub rank: usize,
+    /// Points still possible, as [`Bracket::max_possible_points`] with the
+    /// adjustment applied.
+    pub max_possible: Score,
+    /// The team slot picked to win it all.
+    pub champion: u8,
+}
+
+impl Standing {
+    /// The champion pick as a team of `field`.
+    pub fn champion_team<'f>(&self, field: &'f Field) -> &'f Team {
+        field.team(self.champion)
+    }
+}
+
+/// A pool's entries, the results so far and how picks are scored, so
+/// standings and odds come back by owner instead of by bracket.
+///
+/// Disqualified and withdrawn entries are left out of the leaderboard,
+/// best finishes and payout odds, and each entry's ledger counts in all
+/// three.
+#[derive(Debug, Clone)]
+pub struct Pool {
+    entries: Vec<Entry>,
+    tournament: Tournament,
+    scoring: ScoringSystem,
+    log: Vec<PoolAction>,
+}
+
+impl Pool {
+    pub fn new(tournament: Tournament, scoring: ScoringSystem) -> Pool {
+        Pool {
+            entries: vec![],
+            tournament,
+            scoring,
+            log: vec![],
+        }
+    }
+
+    /// Adds `owner`'s entry, replacing the picks of any earlier one by the
+    /// same name.
+    pub fn insert(&mut self, owner: &str, bracket: Bracket) {
+        match self.entries.iter_mut().find(|e| e.owner == owner) {
+            Some(entry) => entry.bracket = bracket,
+            None => self.entries.push(Entry {
+                owner: owner.to_string(),
+                bracket,
+                status: EntryStatus::Active,
+                ledger: vec![],
+            }),
+        }
+    }
+
+    /// Entries in the order they were added, active or not.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Entries still in the standings, in the order they were added.
+    pub fn active_entries(&self) -> Vec<&Entry> {
+        self.entries.iter().filter(|e| e.is_active()).collect()
+    }
+
+    pub fn bracket(&self, owner: &str) -> Option<&Bracket> {
+        self.entries
+            .iter()
+            .find(|e| e.owner == owner)
+            .map(|e| &e.bracket)
+    }
+
+    /// `owner`'s adjustments, oldest first.
+    pub fn ledger(&self, owner: &str) -> Option<&[Adjustment]> {
+        self.entries
+            .iter()
+            .find(|e| e.owner == owner)
+            .map(|e| e.ledger.as_slice())
+    }
+
+    pub fn tournament(&self) -> &Tournament {
+        &self.tournament
+    }
+
+    pub fn scoring(&self) -> &ScoringSystem {
+        &self.scoring
+    }
+
+    /// The commissioner's actions, oldest first.
+    pub fn log(&self) -> &[PoolAction] {
+        &self.log
+    }
+
+    /// Records a result, as [`Tournament::record_result`].
+    pub fn record_result(&mut self, slot: u8, winner: u8) -> Result<GameResult, ResultError> {
+        self.tournament.record_result(slot, winner)
+    }
+
+    /// Records a no-contest, as [`Tournament::record_no_contest`].
+    pub fn record_no_contest(&mut self, slot: u8) -> Result<(), ResultError> {
+        self.tournament.record_no_contest(slot)
+    }
+
+    /// Takes `owner`'s entry out of the standings for `reason`.
+    pub fn disqualify(&mut self, owner: &str, reason: &str) -> Result<(), PoolError> {
+        self.remove(owner, EntryStatus::Disqualified)?;
+        self.log.push(PoolAction::Disqualified {
+            owner: owner.to_string(),
+            reason: reason.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Takes `owner`'s entry out of the standings at their request.
+    pub fn withdraw(&mut self, owner: &str, reason: &str) -> Result<(), PoolError> {
+        self.remove(owner, EntryStatus::Withdrawn)?;
+        self.log.push(PoolAction::Withdrawn {
+            owner: owner.to_string(),
+            reason: reason.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Adds `points` to `owner`'s score, or takes them off when negative,
+    /// for `reason`, as a new line of the entry's ledger. To take one back,
+    /// add the opposite.
+    pub fn adjust(&mut self, owner: &str, points: i64, reason: &str) -> Result<(), PoolError> {
+        self.active_entry(owner)?.ledger.push(Adjustment {
+            points,
+            note: reason.to_string(),
+        });
+        self.log.push(PoolAction::Adjusted {
+            owner: owner.to_string(),
+            points,
+            reason: reason.to_string(),
+        });
+        Ok(())
+    }
+
+    fn active_entry(&mut self, owner: &str) -> Result<&mut Entry, PoolError> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.owner == owner)
+            .ok_or_else(|| PoolError::UnknownOwner(owner.to_string()))?;
+        if !entry.is_active() {
+            return Err(PoolError::Inactive(owner.to_string()));
+        }
+        Ok(entry)
+    }
+
+    fn remove(&mut self, owner: &str, status: EntryStatus) -> Result<(), PoolError> {
+        self.active_entry(owner)?.status = status;
+        Ok(())
+    }
+
+    fn brackets(&self) -> Vec<Bracket> {
+        self.active_entries().iter().map(|e| e.bracket).collect()
+    }
+
+    /// A contest over the active entries, adjustments applied.
+    pub(crate) fn contest<'a>(&self, view: &'a TournamentView) -> BracketContest<'a> {
+        let mut contest = BracketContest::new(view, &self.brackets(), &self.scoring);
+        for (i, entry) in self.active_entries().iter().enumerate() {
+            contest.adjust(i, entry.adjustment());
+        }
+        contest
+    }
+
+    /// Standings, best first; tied entries keep the order they were added in.
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        let active = self.active_entries();
+        let scores: Vec<Score> = active
+            .iter()
+            .map(|e| e.adjusted(e.bracket.score_with(&self.tournament, &self.scoring)))
+            .collect();
+        let mut standings: Vec<Standing> = active
+            .iter()
+            .zip(standings_ranks(&scores))
+            .zip(&scores)
+            .map(|((entry, rank), &score)| Standing {
+                owner: entry.owner.clone(),
+                score,
+                adjustment: entry.adjustment(),
+                rank,
+                max_possible: entry.adjusted(
+                    entry
+                        .bracket
+                        .max_possible_points(&self.tournament, &self.scoring),
+                ),
+                champion: entry.bracket.champion(),
+            })
+            .collect();
+        standings.sort_by_key(|s| s.rank);
+        standings
+    }
+
+    /// Each active owner's best possible zero-based finish, or `None` when
+    /// the entry can't reach the top `places`. In entry order. Every
+    /// scenario is scored, so this is `None` while more than
+    /// [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn best_finishes(&self, places: usize) -> Option<Vec<(&str, Option<usize>)>> {
+        let view = TournamentView::new(&self.tournament);
+        if !view.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let active = self.active_entries();
+        let brackets = self.brackets();
+        // The search keys finishes by picks, so entries sharing picks but
+        // not adjustments are ranked one by one instead.
+        let shared = active.iter().enumerate().any(|(i, a)| {
+            active[..i]
+                .iter()
+                .any(|b| b.bracket == a.bracket && b.adjustment() != a.adjustment())
+        });
+        let finishes: Vec<Option<usize>> = if shared {
+            best_finishes(&self.contest(&view), places)
+        } else {
+            let mut deltas = ScoreDeltaTable::for_view_with(&view, &brackets, &self.scoring);
+            for (i, entry) in active.iter().enumerate() {
+                deltas.adjust(i, entry.adjustment());
+            }
+            let found = BestFinishes::for_deltas(&brackets, &view, &deltas, places);
+            brackets
+                .iter()
+                .map(|b| found.possible_finishes.get(b).copied())
+                .collect()
+        };
+        let finishes = active
+            .iter()
+            .zip(finishes)
+            .map(|(e, finish)| (e.owner.as_str(), finish))
+            .collect();
+        Some(finishes)
+    }
+
+    /// Each active owner's share of the remaining scenarios that finish in
+    /// the top `paid_places`, every scenario counting the same. In entry
+    /// order. Every scenario is scored, so this is `None` while more than
+    /// [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn payout_odds(&self, paid_places: usize) -> Option<Vec<(&str, f64)>> {
+        let view = TournamentView::new(&self.tournament);
+        if !view.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let active = self.active_entries();
+        let mut cashes = vec![0; active.len()];
+        let scenarios = for_each_ranking(&self.contest(&view), |ranks| {
+            for (count, &rank) in cashes.iter_mut().zip(ranks) {
+                *count += (rank < paid_places) as u64;
+            }
+        });
+        let odds = active
+            .iter()
+            .zip(cashes)
+            .map(|(e, count)| (e.owner.as_str(), PathsToVictory::share(count, scenarios)))
+            .collect();
+        Some(odds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn reports_by_owner() {
+        // Only the title game is left: team 64 against team 96.
+        let tournament = Tournament::new(0, COMPLETE_MASK & !(1 << 1));
+        let mut pool = Pool::new(tournament, ScoringSystem::doubling());
+        pool.insert("Dave", Bracket::from_decisions(1 << 1));
+        pool.insert("Sarah", Bracket::from_decisions(1 << 40 | 1 << 1));
+        pool.insert("Dave", Bracket::from_decisions(0));
+        assert_eq!(pool.entries().len(), 2);
+        assert_eq!(pool.bracket("Dave"), Some(&Bracket::from_decisions(0)));
+
+        let leaderboard = pool.leaderboard();
+        assert_eq!(leaderboard[0].owner, "Dave");
+        assert_eq!(leaderboard[0].max_possible, leaderboard[0].score + 32);
+        assert_eq!(leaderboard[1].owner, "Sarah");
+        assert_eq!(leaderboard[1].rank, 1);
+
+        // Sarah is a first-round game behind but picked the other champion.
+        assert_eq!(
+            pool.best_finishes(1),
+            Some(vec![("Dave", Some(0)), ("Sarah", Some(0))])
+        );
+        assert_eq!(
+            pool.payout_odds(1),
+            Some(vec![("Dave", 0.5), ("Sarah", 0.5)])
+        );
+
+        pool.record_result(1, 96).unwrap();
+        assert_eq!(pool.leaderboard()[0].owner, "Sarah");
+        assert_eq!(
+            pool.best_finishes(1),
+            Some(vec![("Dave", None), ("Sarah", Some(0))])
+        );
+        assert_eq!(
+            pool.best_finishes(2),
+            Some(vec![("Dave", Some(1)), ("Sarah", Some(0))])
+        );
+    }
+
+    #[test]
+    fn commissioner_actions_flow_through_the_standings() {
+        // Only the title game is left: team 64 against team 96.
+        let tournament = Tournament::new(0, COMPLETE_MASK & !(1 << 1));
+        let mut pool = Pool::new(tournament, ScoringSystem::doubling());
+        pool.insert("Dave", Bracket::from_decisions(0));
+        pool.insert("Sarah", Bracket::from_decisions(1 << 40 | 1 << 1));
+        pool.insert("Mike", Bracket::from_decisions(1 << 1));
+
+        pool.disqualify("Mike", "paid after tip-off").unwrap();
+        assert_eq!(
+            pool.disqualify("Mike", "twice"),
+            Err(PoolError::Inactive("Mike".to_string()))
+        );
+        assert_eq!(
+            pool.adjust("Bob", 1, "no entry"),
+            Err(PoolError::UnknownOwner("Bob".to_string()))
+        );
+        assert_eq!(pool.entries().len(), 3);
+        assert_eq!(pool.leaderboard().len(), 2);
+        assert_eq!(
+            pool.payout_odds(1),
+            Some(vec![("Dave", 0.5), ("Sarah", 0.5)])
+        );
+
+        // Enough to put Sarah past anything Dave can still score.
+        pool.adjust("Sarah", 50, "tiebreaker pick").unwrap();
+        let leaderboard = pool.leaderboard();
+        assert_eq!(leaderboard[0].owner, "Sarah");
+        assert_eq!(leaderboard[0].score, leaderboard[1].score + 35);
+        assert_eq!(
+            pool.best_finishes(1),
+            Some(vec![("Dave", None), ("Sarah", Some(0))])
+        );
+        assert_eq!(
+            pool.payout_odds(1),
+            Some(vec![("Dave", 0.0), ("Sarah", 1.0)])
+        );
+
+        // Same picks as Dave but a point behind, so always a place behind.
+        pool.insert("Ann", Bracket::from_decisions(0));
+        pool.adjust("Ann", -1, "late swap").unwrap();
+        assert_eq!(
+            pool.best_finishes(3),
+            Some(vec![
+                ("Dave", Some(1)),
+                ("Sarah", Some(0)),
+                ("Ann", Some(2))
+            ])
+        );
+
+        pool.withdraw("Ann", "asked out").unwrap();
+        assert_eq!(pool.leaderboard().len(), 2);
+        assert_eq!(pool.log().len(), 4);
+        assert_eq!(
+            Pool::new(Tournament::new(0, 0), ScoringSystem::standard()).best_finishes(1),
+            None
+        );
+        assert_eq!(
+            pool.log()[1],
+            PoolAction::Adjusted {
+                owner: "Sarah".to_string(),
+                points: 50,
+                reason: "tiebreaker pick".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn ledger_lines_add_up() {
+        let tournament = Tournament::new(0, COMPLETE_MASK & !(1 << 1));
+        let mut pool = Pool::new(tournament.clone(), ScoringSystem::standard());
+        let chalk = Bracket::from_decisions(0);
+        pool.insert("Dave", chalk);
+        pool.adjust("Dave", 5, "bonus question").unwrap();
+        pool.adjust("Dave", -2, "late swap").unwrap();
+
+        let ledger = pool.ledger("Dave").unwrap();
+        assert_eq!(ledger.len(), 2);
+        assert_eq!(ledger[1].note, "late swap");
+        assert_eq!(pool.entries()[0].adjustment(), 3);
+        let standing = &pool.leaderboard()[0];
+        assert_eq!(standing.adjustment, 3);
+        assert_eq!(standing.score, chalk.score(&tournament) + 3);
+        assert_eq!(pool.ledger("Bob"), None);
+
+        // Taken back by adding the opposite, which stays on the ledger.
+        pool.adjust("Dave", -3, "bonus question voided").unwrap();
+        assert_eq!(pool.leaderboard()[0].score, chalk.score(&tournament));
+        assert_eq!(pool.ledger("Dave").unwrap().len(), 3);
+    }
+}
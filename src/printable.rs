@@ -0,0 +1,215 @@
+use std::fmt;
+
+use crate::field::Field;
+use crate::{round_name, round_num_for_slot, Bracket, Decisions, Tournament};
+
+/// Why a printable bracket couldn't be read. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrintableError {
+    /// No team in the field goes by this name.
+    UnknownTeam { line: usize, name: String },
+    /// The team can't win game `slot`: it isn't the pick from either game
+    /// feeding it.
+    NotPlaying { line: usize, slot: u8, name: String },
+    /// A full bracket has 63 picks.
+    PickCount(usize),
+}
+
+impl fmt::Display for PrintableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrintableError::UnknownTeam { line, name } => {
+                write!(f, "line {}: no team named {:?}", line, name)
+            }
+            PrintableError::NotPlaying { line, slot, name } => {
+                write!(f, "line {}: {} isn't playing in game {}", line, name, slot)
+            }
+            PrintableError::PickCount(count) => {
+                write!(f, "bracket has {} picks, not 63", count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrintableError {}
+
+impl Field {
+    /// `bracket` as text: each round's winners under the round's name, top
+    /// of the bracket first, as "(seed) Name".
+    pub fn printable(&self, bracket: &Bracket) -> String {
+        let picks = bracket.decision_team_slots();
+        let mut text = String::new();
+        for slot in bracket_order() {
+            if slot.is_power_of_two() {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(round_name(round_num_for_slot(slot)));
+                text.push('\n');
+            }
+            if let Some(team) = picks[slot as usize] {
+                let team = self.team(team);
+                text.push_str(&format!("({}) {}\n", team.seed, team.name));
+            }
+        }
+        text
+    }
+
+    /// Reads a bracket from a printable dump like [`Field::printable`]'s:
+    /// one winner per line, first-round games first and top of the bracket
+    /// first within a round. Round headings and blank lines are skipped, and
+    /// a leading seed ("(1) Gonzaga", "1. Gonzaga", "1 Gonzaga") is ignored.
+    /// Names are matched as [`Field::find`] matches them, aliases included.
+    pub fn parse_printable(&self, text: &str) -> Result<Bracket, PrintableError> {
+        let mut bracket = Bracket::from_decisions(0);
+        let count = self.read_winners(text, &mut bracket)?;
+        if count != 63 {
+            return Err(PrintableError::PickCount(count));
+        }
+        Ok(bracket)
+    }
+
+    /// Reads results so far in the same format as
+    /// [`Field::parse_printable`]: the winners of the games played, in the
+    /// same order, stopping wherever the list ends.
+    pub fn parse_results(&self, text: &str) -> Result<Tournament, PrintableError> {
+        let mut tournament = Tournament::new(0, 0);
+        let count = self.read_winners(text, &mut tournament)?;
+        if count > 63 {
+            return Err(PrintableError::PickCount(count));
+        }
+        Ok(tournament)
+    }
+
+    /// Sets a decision in `decisions` for each winner listed in `text`,
+    /// returning how many were listed.
+    fn read_winners<D: Decisions>(
+        &self,
+        text: &str,
+        decisions: &mut D,
+    ) -> Result<usize, PrintableError> {
+        let mut winners = [0u8; 128];
+        for (team, winner) in winners.iter_mut().enumerate().skip(64) {
+            *winner = team as u8;
+        }
+        let mut games = bracket_order();
+        let mut count = 0;
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (1..=6).any(|r| line.eq_ignore_ascii_case(round_name(r))) {
+                continue;
+            }
+            count += 1;
+            let name = strip_seed(line);
+            let team = self.find(name).ok_or_else(|| PrintableError::UnknownTeam {
+                line: i + 1,
+                name: name.to_string(),
+            })?;
+            let Some(slot) = games.next() else {
+                continue;
+            };
+            let sides = [winners[slot as usize * 2], winners[slot as usize * 2 + 1]];
+            let side = sides.iter().position(|&s| s == team.slot).ok_or_else(|| {
+                PrintableError::NotPlaying {
+                    line: i + 1,
+                    slot,
+                    name: team.name.clone(),
+                }
+            })?;
+            decisions.set_decision(slot, side as u8);
+            winners[slot as usize] = team.slot;
+        }
+        Ok(count)
+    }
+}
+
+/// Game slots round by round, top of the bracket first within each round.
+fn bracket_order() -> impl Iterator<Item = u8> {
+    (1..=6u8).rev().flat_map(|depth| {
+        let first = 1u8 << (depth - 1);
+        first..first * 2
+    })
+}
+
+fn strip_seed(line: &str) -> &str {
+    let rest = line.trim_start_matches('(');
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return line;
+    }
+    let after = rest[digits..].trim_start_matches([')', '.']);
+    if after.starts_with(char::is_whitespace) {
+        after.trim_start()
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_slot_mask;
+    use crate::testing::{chalk_bracket, random_brackets, standard_field};
+
+    #[test]
+    fn round_trips_printable_brackets() {
+        let field = standard_field();
+        let chalk = chalk_bracket();
+        let text = field.printable(&chalk);
+        assert!(text.starts_with("First Round\n(1) East 1\n(8) East 8\n"));
+        assert!(text.ends_with("Championship\n(1) East 1\n"));
+        assert_eq!(field.parse_printable(&text), Ok(chalk));
+
+        for bracket in random_brackets(3, 5) {
+            assert_eq!(
+                field.parse_printable(&field.printable(&bracket)),
+                Ok(bracket)
+            );
+        }
+        let bare: String = text
+            .lines()
+            .map(|line| line.replace("(", "").replace(")", "."))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(field.parse_printable(&bare), Ok(chalk));
+
+        let mut renamed = field.clone();
+        renamed.set_name(64, "Connecticut");
+        let aliased = renamed.printable(&chalk).replace("Connecticut", "UConn");
+        assert_eq!(renamed.parse_printable(&aliased), Ok(chalk));
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        let field = standard_field();
+        let text = field.printable(&chalk_bracket());
+
+        let unknown = text.replacen("(8) East 8", "Nobody", 1);
+        assert_eq!(
+            field.parse_printable(&unknown),
+            Err(PrintableError::UnknownTeam {
+                line: 3,
+                name: "Nobody".to_string()
+            })
+        );
+        let wrong = text.replacen("(8) East 8", "(1) East 1", 1);
+        assert_eq!(
+            field.parse_printable(&wrong),
+            Err(PrintableError::NotPlaying {
+                line: 3,
+                slot: 33,
+                name: "East 1".to_string()
+            })
+        );
+        let short: String = text.lines().skip(2).collect::<Vec<_>>().join("\n");
+        assert!(field.parse_printable(&short).is_err());
+
+        // A heading and the first round's 32 winners.
+        let first_round: String = text.lines().take(33).collect::<Vec<_>>().join("\n");
+        assert_eq!(
+            field.parse_results(&first_round),
+            Ok(Tournament::new(0, round_slot_mask(1)))
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{round_slot_mask, Bracket};
+
+/// A side prize for the most points in one round (1..=6), split evenly
+/// between entries that tie.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundPrize {
+    pub round: u8,
+    pub amount: f64,
+}
+
+/// Who has won each prize so far, and each entry's total.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundPrizeResults {
+    /// Per prize, in the order given: the winning entries (as indices), or
+    /// none while the round still has games to play.
+    pub winners: Vec<Vec<usize>>,
+    /// Per entry, in the order given: winnings from every decided prize.
+    pub winnings: Vec<f64>,
+}
+
+impl TournamentView {
+    /// Awards each prize whose round is finished, from the entries' points in
+    /// that round. Rounds with games left pay nothing yet.
+    pub fn round_prizes(&self, brackets: &[Bracket], prizes: &[RoundPrize]) -> RoundPrizeResults {
+        self.round_prizes_with(brackets, prizes, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::round_prizes`] under `scoring`.
+    pub fn round_prizes_with(
+        &self,
+        brackets: &[Bracket],
+        prizes: &[RoundPrize],
+        scoring: &ScoringSystem,
+    ) -> RoundPrizeResults {
+        let points: Vec<_> = brackets
+            .iter()
+            .map(|b| self.round_points_with(b, scoring))
+            .collect();
+        let mut winnings = vec![0.0; brackets.len()];
+        let winners = prizes
+            .iter()
+            .map(|prize| {
+                let round = prize.round as usize;
+                if self.decided_in_round(prize.round) != round_slot_mask(prize.round) {
+                    return vec![];
+                }
+                let best = points.iter().map(|p| p[round]).max().unwrap_or(0);
+                let winners: Vec<usize> = (0..brackets.len())
+                    .filter(|&e| points[e][round] == best)
+                    .collect();
+                for &entry in &winners {
+                    winnings[entry] += prize.amount / winners.len() as f64;
+                }
+                winners
+            })
+            .collect();
+        RoundPrizeResults { winners, winnings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tournament;
+
+    #[test]
+    fn pays_finished_rounds_and_splits_ties() {
+        // First round done, all won by the top team.
+        let view = TournamentView::new(&Tournament::new(0, round_slot_mask(1)));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 40),
+        ];
+        let prizes = [
+            RoundPrize {
+                round: 1,
+                amount: 20.0,
+            },
+            RoundPrize {
+                round: 2,
+                amount: 10.0,
+            },
+        ];
+
+        let results = view.round_prizes(&brackets, &prizes);
+        assert_eq!(results.winners, vec![vec![0, 1], vec![]]);
+        assert_eq!(results.winnings, vec![10.0, 10.0, 0.0]);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::paths::PathsToVictory;
+use crate::rng::Rng;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::simulate::{play, WinProbability};
+use crate::view::TournamentView;
+use crate::{Bracket, Score};
+
+/// The pool as it's likely to stand once the current round is over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundProjection {
+    /// The round being played, in the scoring format's numbering.
+    pub round: u8,
+    /// Per bracket, the average score at the end of the round.
+    pub expected_scores: Vec<f64>,
+    /// Per bracket, the chance of leading (alone or tied) then.
+    pub leads: Vec<f64>,
+}
+
+impl TournamentView {
+    /// Projects the leaderboard to the end of the earliest round with games
+    /// left, from `samples` draws of that round's games from `model`; later
+    /// rounds don't count yet. `None` once every game is decided. The same
+    /// `seed` gives the same projection.
+    pub fn round_projection<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+        samples: u64,
+        seed: u64,
+        scoring: &ScoringSystem,
+    ) -> Option<RoundProjection> {
+        let round = self
+            .undecided()
+            .iter()
+            .filter_map(|&slot| scoring.format.round(slot))
+            .min()?;
+        let games: Vec<u8> = self
+            .undecided()
+            .iter()
+            .copied()
+            .filter(|&slot| scoring.format.round(slot) == Some(round))
+            .collect();
+
+        let deltas = ScoreDeltaTable::for_view_with(self, brackets, scoring);
+        let mut rng = Rng::new(seed);
+        let mut team_slots = self.team_slots().to_vec();
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+        // Wide enough that no run of saturated scores can overflow it.
+        let mut totals = vec![0u128; brackets.len()];
+        let mut leads = vec![0u64; brackets.len()];
+        for _ in 0..samples {
+            // The later games stay empty, so they score nothing.
+            play(&games, model, &mut rng, &mut team_slots);
+            let ranks = deltas.ranks(&team_slots, &mut scores);
+            for ((total, lead), (&score, rank)) in totals
+                .iter_mut()
+                .zip(&mut leads)
+                .zip(scores.iter().zip(ranks))
+            {
+                *total += u128::from(score);
+                *lead += (rank == 0) as u64;
+            }
+        }
+
+        Some(RoundProjection {
+            round,
+            expected_scores: totals
+                .into_iter()
+                .map(|total| total as f64 / samples.max(1) as f64)
+                .collect(),
+            leads: leads
+                .into_iter()
+                .map(|count| PathsToVictory::share(count, samples))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{round_slot_mask, Decisions, Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn projects_to_the_end_of_the_round() {
+        // Through the Sweet 16: the Elite Eight is being played.
+        let through = COMPLETE_MASK & !0xFE;
+        let view = TournamentView::new(&Tournament::new(0, through));
+        let chalk = Bracket::from_decisions(0);
+        // Chalk but for the second side of every Elite Eight game.
+        let upsets = Bracket::from_decisions(round_slot_mask(4));
+        let brackets = [chalk, upsets];
+        // The lower team slot wins three times in four.
+        let model = |a: u8, b: u8| if a < b { 0.75 } else { 0.25 };
+        let scoring = ScoringSystem::standard();
+
+        let projection = view
+            .round_projection(&brackets, &model, 20_000, 9, &scoring)
+            .unwrap();
+        assert_eq!(projection.round, 4);
+        // Each Elite Eight pick comes in three times in four for chalk and
+        // once in four for the other; nothing later counts.
+        let played = chalk.score(&Tournament::new(0, through));
+        let pick = |bracket: &Bracket, slot: u8| {
+            let team = bracket.decision_team_slots()[slot as usize].unwrap();
+            scoring.points(slot, team, None) as f64
+        };
+        let expected = |bracket: &Bracket, chance: f64| {
+            (4..8).map(|slot| chance * pick(bracket, slot)).sum::<f64>()
+        };
+        assert!(
+            (projection.expected_scores[0] - played as f64 - expected(&chalk, 0.75)).abs() < 0.5
+        );
+        let played = upsets.score(&Tournament::new(0, through));
+        assert!(
+            (projection.expected_scores[1] - played as f64 - expected(&upsets, 0.25)).abs() < 0.5
+        );
+        assert!(projection.leads[0] > projection.leads[1]);
+        assert_eq!(
+            Some(projection),
+            view.round_projection(&brackets, &model, 20_000, 9, &scoring)
+        );
+
+        // Scores at the ceiling still average to it.
+        let huge = ScoringSystem {
+            multipliers: [Score::MAX; 7],
+            ..scoring
+        };
+        let projection = view
+            .round_projection(&brackets, &model, 10, 9, &huge)
+            .unwrap();
+        assert_eq!(projection.expected_scores[0], Score::MAX as f64);
+
+        let done = TournamentView::new(&Tournament::new(0, COMPLETE_MASK));
+        assert_eq!(
+            done.round_projection(&brackets, &model, 10, 9, &scoring),
+            None
+        );
+    }
+}
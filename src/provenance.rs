@@ -0,0 +1,141 @@
+use crate::format::BracketFormat;
+use crate::scoring::ScoringSystem;
+use crate::Tournament;
+
+/// How a result was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Every remaining scenario was looked at.
+    Exact,
+    /// Drawn from `samples` random scenarios, reproducible from `seed`.
+    Sampled { samples: u64, seed: u64 },
+    /// Enumerated where time allowed and sampled elsewhere, as by
+    /// `TournamentView::best_finishes_within`.
+    Mixed {
+        exact_subtrees: usize,
+        sampled_subtrees: usize,
+    },
+}
+
+/// What a computed result can be trusted for: how it was made, the odds
+/// model behind it if any, and the tournament state it describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub method: Method,
+    /// A name for the win-probability model, or `None` when every scenario
+    /// counted the same.
+    pub model: Option<String>,
+    /// [`Tournament::state_hash`] of the state the result was computed from.
+    pub state: u64,
+}
+
+impl Provenance {
+    pub fn new(tournament: &Tournament, method: Method, model: Option<&str>) -> Provenance {
+        Provenance {
+            method,
+            model: model.map(str::to_owned),
+            state: tournament.state_hash(),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.method == Method::Exact
+    }
+
+    /// False once a result has been recorded or undone since.
+    pub fn is_current(&self, tournament: &Tournament) -> bool {
+        self.state == tournament.state_hash()
+    }
+}
+
+/// A computed result along with its [`Provenance`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotated<T> {
+    pub value: T,
+    pub provenance: Provenance,
+}
+
+impl Tournament {
+    /// A hash of the recorded results that stays the same across builds and
+    /// platforms (64-bit FNV-1a over the decision, mask and vacated bits), so
+    /// it can be stored or sent along with results.
+    pub fn state_hash(&self) -> u64 {
+        fnv1a(&[self.decisions, self.mask, self.vacated])
+    }
+
+    /// A key for results that depend on both the tournament state and how
+    /// picks are scored, like standings and finish distributions. Recording
+    /// or undoing a result, or changing the scoring, gives a new key.
+    pub fn cache_key(&self, scoring: &ScoringSystem) -> u64 {
+        fnv1a(&[self.state_hash(), scoring.config_hash()])
+    }
+}
+
+impl ScoringSystem {
+    /// A hash of every setting, stable in the same way as
+    /// [`Tournament::state_hash`].
+    pub fn config_hash(&self) -> u64 {
+        let mut words = vec![self.seed_bonus as u64, self.upset_bonus as u64];
+        words.extend(self.round_values);
+        words.extend(self.multipliers);
+        // Left out for the standard format so existing hashes hold.
+        if self.format != BracketFormat::standard() {
+            words.push(self.format.rounds() as u64);
+            words.extend((64..=127).map(|team| self.format.seed(team).unwrap_or(0) as u64));
+        }
+        fnv1a(&words)
+    }
+}
+
+pub(crate) fn fnv1a(words: &[u64]) -> u64 {
+    words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goes_stale_when_results_change() {
+        let mut tournament = Tournament::new(0, 0);
+        let provenance = Provenance::new(
+            &tournament,
+            Method::Sampled {
+                samples: 1000,
+                seed: 7,
+            },
+            Some("seed ratings"),
+        );
+        assert!(!provenance.is_exact());
+        assert!(provenance.is_current(&tournament));
+        assert_eq!(tournament.state_hash(), Tournament::new(0, 0).state_hash());
+
+        tournament.record_result(63, 126).unwrap();
+        assert!(!provenance.is_current(&tournament));
+        tournament.undo_result(63).unwrap();
+        assert!(provenance.is_current(&tournament));
+    }
+
+    #[test]
+    fn cache_keys_cover_results_and_scoring() {
+        let mut tournament = Tournament::new(0, 0);
+        let standard = ScoringSystem::standard();
+        let key = tournament.cache_key(&standard);
+        assert_eq!(key, tournament.clone().cache_key(&ScoringSystem::default()));
+        assert_ne!(key, tournament.cache_key(&ScoringSystem::doubling()));
+
+        let mut no_seeds = standard;
+        no_seeds.seed_bonus = false;
+        assert_ne!(key, tournament.cache_key(&no_seeds));
+
+        tournament.record_result(40, 80).unwrap();
+        assert_ne!(key, tournament.cache_key(&standard));
+        // Pinned so a change to the hash shows up as a broken cache format.
+        assert_eq!(Tournament::new(0, 0).state_hash(), 0x81D2_3FD7_003C_2305);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::simulate::WinProbability;
+use crate::view::TournamentView;
+use crate::{Bracket, Score};
+
+/// Finishes over the scenarios likely enough to look at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrunedFinishes {
+    /// Best zero-based finish of each bracket reaching the top places in a
+    /// scenario that was kept.
+    pub finishes: HashMap<Bracket, usize>,
+    /// Per bracket in the order given: the chance of finishing first (ties
+    /// sharing it) over the kept scenarios. Low by at most
+    /// `truncated_mass`.
+    pub wins: Vec<f64>,
+    /// The total probability of the scenarios dropped.
+    pub truncated_mass: f64,
+    pub scenarios: u64,
+}
+
+struct Walk<'a, M: ?Sized> {
+    brackets: &'a [Bracket],
+    deltas: ScoreDeltaTable,
+    model: &'a M,
+    places: usize,
+    epsilon: f64,
+    result: PrunedFinishes,
+    scores: Vec<Score>,
+}
+
+impl TournamentView {
+    /// Every remaining scenario weighted by `model`, except that a game's
+    /// branch is dropped once the chance of reaching it falls below
+    /// `epsilon`, along with every scenario under it. An `epsilon` of 0.0
+    /// keeps everything the model gives a chance.
+    pub fn best_finishes_pruned<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        places: usize,
+        model: &M,
+        epsilon: f64,
+    ) -> PrunedFinishes {
+        self.best_finishes_pruned_with(brackets, places, model, epsilon, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::best_finishes_pruned`] under `scoring`.
+    pub fn best_finishes_pruned_with<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        places: usize,
+        model: &M,
+        epsilon: f64,
+        scoring: &ScoringSystem,
+    ) -> PrunedFinishes {
+        let mut walk = Walk {
+            brackets,
+            deltas: ScoreDeltaTable::for_view_with(self, brackets, scoring),
+            model,
+            places,
+            epsilon,
+            result: PrunedFinishes {
+                finishes: HashMap::new(),
+                wins: vec![0.0; brackets.len()],
+                truncated_mass: 0.0,
+                scenarios: 0,
+            },
+            scores: Vec::with_capacity(brackets.len()),
+        };
+        let mut team_slots = self.team_slots().to_vec();
+        walk.fill(&mut team_slots, self.undecided(), 1.0);
+        walk.result
+    }
+}
+
+impl<M: WinProbability + ?Sized> Walk<'_, M> {
+    // `undecided` lists children before parents.
+    fn fill(&mut self, team_slots: &mut [Option<u8>], undecided: &[u8], probability: f64) {
+        let Some((&slot, rest)) = undecided.split_first() else {
+            self.record(team_slots, probability);
+            return;
+        };
+        let slot = slot as usize;
+        let branches = match (team_slots[slot * 2], team_slots[slot * 2 + 1]) {
+            (Some(a), Some(b)) => {
+                let p = self.model.win_probability(a, b).clamp(0.0, 1.0);
+                [(Some(a), p), (Some(b), 1.0 - p)]
+            }
+            // A walkover has one branch.
+            (a, b) => [(a.or(b), 1.0), (None, 0.0)],
+        };
+        for (winner, p) in branches {
+            let reached = probability * p;
+            if reached == 0.0 {
+                continue;
+            }
+            if reached < self.epsilon {
+                self.result.truncated_mass += reached;
+                continue;
+            }
+            team_slots[slot] = winner;
+            self.fill(team_slots, rest, reached);
+        }
+        team_slots[slot] = None;
+    }
+
+    fn record(&mut self, team_slots: &[Option<u8>], probability: f64) {
+        self.result.scenarios += 1;
+        let ranks = self.deltas.ranks(team_slots, &mut self.scores);
+        for (i, rank) in ranks.into_iter().enumerate() {
+            if rank == 0 {
+                self.result.wins[i] += probability;
+            }
+            if rank < self.places {
+                let best = self.result.finishes.entry(self.brackets[i]).or_insert(rank);
+                *best = rank.min(*best);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BestFinishes, Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn drops_unlikely_subtrees() {
+        // Both semifinals and the title game are left.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2 | 1 << 1),
+        ];
+        // The lower team slot wins nine times in ten.
+        let model = |a: u8, b: u8| if a < b { 0.9 } else { 0.1 };
+
+        let full = view.best_finishes_pruned(&brackets, 5, &model, 0.0);
+        assert_eq!(full.scenarios, 8);
+        assert_eq!(full.truncated_mass, 0.0);
+        // Someone finishes first in every scenario.
+        assert!(full.wins.iter().sum::<f64>() >= 1.0 - 1e-12);
+        assert_eq!(
+            full.finishes,
+            BestFinishes::for_view(&brackets, &view).possible_finishes
+        );
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        assert_eq!(
+            view.best_finishes_pruned_with(&brackets, 2, &model, 0.0, &upsets)
+                .finishes,
+            BestFinishes::for_view_with(&brackets, &view, &upsets, 2).possible_finishes
+        );
+
+        // Both semifinals going to the higher slot is a 1% branch.
+        let pruned = view.best_finishes_pruned(&brackets, 5, &model, 0.05);
+        assert!(pruned.scenarios < 8);
+        assert!(pruned.truncated_mass > 0.0);
+        for (kept, all) in pruned.wins.iter().zip(&full.wins) {
+            assert!(kept <= all && all - kept <= pruned.truncated_mass + 1e-12);
+        }
+    }
+}
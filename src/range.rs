@@ -0,0 +1,182 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Score, Tournament};
+
+/// Where a bracket's score stands and where it can still end up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreRange {
+    /// Points it ends with however the remaining games go.
+    pub minimum: Score,
+    pub current: Score,
+    /// Points if every pick that's still alive comes true.
+    pub maximum: Score,
+}
+
+impl Bracket {
+    /// The bracket's score range under `scoring`, from which of its picks
+    /// for the undecided games are still alive. Picks are always consistent
+    /// with each other, so the maximum is reached by the scenario where every
+    /// live pick wins. With an upset bonus each live pick is counted against
+    /// the best seed that can still meet it, so the maximum is a bound that
+    /// may fall short of being reached.
+    pub fn score_range(&self, view: &TournamentView, scoring: &ScoringSystem) -> ScoreRange {
+        let current = view.score_with(self, scoring);
+        let picks = self.decision_team_slots();
+
+        let (mut minimum, mut maximum) = (current, current);
+        for &slot in view.undecided() {
+            let Some(team) = picks[slot as usize] else {
+                continue;
+            };
+            if !view.can_reach(team, slot) {
+                continue;
+            }
+            let points = scoring.points(slot, team, best_opponent(view, slot, team, scoring));
+            maximum = maximum.saturating_add(points);
+            // Guaranteed only once vacated games have left no one else.
+            if view.contenders(slot).len() == 1 {
+                minimum = minimum.saturating_add(points);
+            }
+        }
+
+        ScoreRange {
+            minimum,
+            current,
+            maximum,
+        }
+    }
+}
+
+impl Bracket {
+    /// Points possible remaining: the score if every pick that's still alive
+    /// comes true, under `scoring`. This is [`ScoreRange::maximum`], so with
+    /// an upset bonus each live pick is scored against the best seed that can
+    /// still reach it.
+    pub fn max_possible_points(&self, tournament: &Tournament, scoring: &ScoringSystem) -> Score {
+        self.score_range(&TournamentView::new(tournament), scoring)
+            .maximum
+    }
+}
+
+/// The team that can still meet `team` in `slot` and is worth the biggest
+/// upset bonus to beat: the best seed coming out of the other side. `None`
+/// when no one can, as after a vacated game.
+fn best_opponent(view: &TournamentView, slot: u8, team: u8, scoring: &ScoringSystem) -> Option<u8> {
+    // `team` comes up through one child; its opponent through the other.
+    let mut below = team;
+    while below / 2 != slot {
+        below /= 2;
+    }
+    let other = below ^ 1;
+    let arrivals = if other >= 64 {
+        vec![other]
+    } else {
+        view.contenders(other)
+    };
+    arrivals
+        .into_iter()
+        .min_by_key(|&t| scoring.format.seed(t).unwrap_or(u8::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{points_for, round_slot_mask, Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn range_counts_only_live_picks() {
+        // Everything but the title game is in; chalk (top teams) won out.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1),
+            vacated: 0,
+        });
+
+        let chalk = Bracket { decisions: 0 };
+        let range = chalk.score_range(&view, &ScoringSystem::standard());
+        assert_eq!(range.minimum, range.current);
+        assert_eq!(range.maximum, range.current + points_for(6, 1));
+
+        // Picked the loser of slot 2 to win it all: no upside left.
+        let busted = Bracket { decisions: 1 << 2 };
+        let range = busted.score_range(&view, &ScoringSystem::standard());
+        assert_eq!(range.minimum, range.current);
+        assert_eq!(range.maximum, range.current);
+    }
+
+    #[test]
+    fn walkover_picks_are_guaranteed() {
+        // The bottom semifinal was vacated, so the top one's winner takes
+        // the title.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1),
+            vacated: 1 << 3,
+        });
+
+        let chalk = Bracket { decisions: 0 };
+        let range = chalk.score_range(&view, &ScoringSystem::standard());
+        assert_eq!(range.minimum, range.current + points_for(6, 1));
+        assert_eq!(range.maximum, range.minimum);
+    }
+
+    #[test]
+    fn max_possible_points_matches_the_range() {
+        // The Final Four and title game are left.
+        let tournament = Tournament::new(0, COMPLETE_MASK & !0b1110);
+        let view = TournamentView::new(&tournament);
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(0b1110),
+            Bracket::from_decisions(1 << 40),
+        ];
+        for bracket in &brackets {
+            assert_eq!(
+                bracket.max_possible_points(&tournament, &ScoringSystem::standard()),
+                bracket
+                    .score_range(&view, &ScoringSystem::standard())
+                    .maximum
+            );
+        }
+
+        let doubling = ScoringSystem::doubling();
+        let chalk = &brackets[0];
+        assert_eq!(
+            chalk.max_possible_points(&tournament, &doubling),
+            chalk.score_with(&tournament, &doubling) + 16 + 16 + 32
+        );
+        assert_eq!(
+            chalk.score_range(&view, &doubling).maximum,
+            chalk.max_possible_points(&tournament, &doubling)
+        );
+    }
+
+    #[test]
+    fn upset_bonus_counts_the_best_seed_left_to_beat() {
+        // The first two rounds are in but for slot 17, where the 5 seed (68)
+        // meets the 4 (70). The 16 seed (65) won slot 16 and waits in slot 8.
+        let tournament = Tournament::new(
+            1 << 32,
+            (round_slot_mask(1) | round_slot_mask(2)) & !(1 << 17),
+        );
+        let view = TournamentView::new(&tournament);
+        // Only the first three rounds count.
+        let scoring = ScoringSystem {
+            upset_bonus: true,
+            multipliers: [0, 1, 1, 1, 0, 0, 0],
+            ..ScoringSystem::doubling()
+        };
+        // The 16 seed through slot 8, and the 12 seed (69), long out, in
+        // slot 17: that game is the entry's to lose either way.
+        let bracket = Bracket::from_decisions(1 << 32 | 1 << 34);
+
+        let mut best = 0;
+        view.for_each_scenario(None, |team_slots| {
+            best = best.max(bracket.points_for_decisions(team_slots, &scoring));
+        });
+        let range = bracket.score_range(&view, &scoring);
+        // Beating the 4 seed rather than the 5 is a line more.
+        assert_eq!(range.maximum, best);
+        assert_eq!(bracket.max_possible_points(&tournament, &scoring), best);
+    }
+}
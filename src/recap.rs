@@ -0,0 +1,271 @@
+use std::fmt::Write;
+
+use crate::contest::for_each_ranking;
+use crate::field::{Field, Team};
+use crate::paths::PathsToVictory;
+use crate::pool::{Pool, Standing};
+use crate::results::{GameResult, UpsetRecorded};
+use crate::view::TournamentView;
+use crate::{round_name, round_num_for_slot, standings_ranks, Decisions, Score, Tournament};
+
+// Win chances are only given when every scenario can be scored quickly.
+const ENUMERATION_LIMIT: u128 = 1 << 20;
+
+/// A leaderboard row and where the entry stood at the last snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecapStanding {
+    pub standing: Standing,
+    pub previous_rank: usize,
+}
+
+impl RecapStanding {
+    /// Places gained since the last snapshot, negative for a drop.
+    pub fn places_moved(&self) -> isize {
+        self.previous_rank as isize - self.standing.rank as isize
+    }
+}
+
+/// A team knocked out since the last snapshot that entries had as their
+/// champion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EliminatedFavorite {
+    pub result: GameResult,
+    pub seed: u8,
+    pub champion_picks: usize,
+}
+
+impl EliminatedFavorite {
+    /// The eliminated team as a team of `field`.
+    pub fn team<'f>(&self, field: &'f Field) -> &'f Team {
+        self.result
+            .loser_team(field)
+            .expect("eliminated teams lost a game")
+    }
+}
+
+/// What happened in a pool since the last snapshot, for a weekly email or
+/// page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recap {
+    /// Best first, as [`Pool::leaderboard`].
+    pub standings: Vec<RecapStanding>,
+    /// Games decided since the last snapshot, earliest round first.
+    pub results: Vec<GameResult>,
+    pub upsets: Vec<UpsetRecorded>,
+    /// Most champion picks first.
+    pub eliminated: Vec<EliminatedFavorite>,
+    /// Each owner's chance of finishing first (alone or tied), every
+    /// scenario counting the same, in leaderboard order. `None` while there
+    /// are too many scenarios to score.
+    pub win_odds: Option<Vec<(String, f64)>>,
+}
+
+impl Pool {
+    /// The recap since the latest of `history`, snapshots of the results
+    /// oldest first, or since the start with no history. Upsets are wins by
+    /// a team seeded at least `upset_threshold` lines worse.
+    pub fn recap(&self, history: &[Tournament], upset_threshold: u8) -> Recap {
+        let start = Tournament::new(0, 0);
+        let previous = history.last().unwrap_or(&start);
+        let active = self.active_entries();
+        let brackets: Vec<_> = active.iter().map(|e| e.bracket).collect();
+
+        // Adjustments count at both ends, so moves come from the games.
+        let previous_scores: Vec<Score> = active
+            .iter()
+            .map(|e| e.adjusted(e.bracket.score_with(previous, self.scoring())))
+            .collect();
+        let previous_ranks = standings_ranks(&previous_scores);
+        let leaderboard = self.leaderboard();
+        // The active entry behind each leaderboard row.
+        let order: Vec<usize> = leaderboard
+            .iter()
+            .map(|standing| {
+                active
+                    .iter()
+                    .position(|e| e.owner == standing.owner)
+                    .expect("leaderboard rows are entries")
+            })
+            .collect();
+        let standings = leaderboard
+            .into_iter()
+            .zip(&order)
+            .map(|(standing, &entry)| RecapStanding {
+                standing,
+                previous_rank: previous_ranks[entry],
+            })
+            .collect();
+
+        let view = TournamentView::new(self.tournament());
+        let team_slots = view.team_slots();
+        let new_games = self.tournament().mask() & !previous.mask();
+        let results: Vec<GameResult> = (1..64u8)
+            .rev()
+            .filter(|&slot| new_games & (1 << slot) != 0)
+            .filter_map(|slot| {
+                let winner = team_slots[slot as usize]?;
+                let sides = [
+                    team_slots[slot as usize * 2],
+                    team_slots[slot as usize * 2 + 1],
+                ];
+                let loser = sides.into_iter().flatten().find(|&team| team != winner);
+                Some(GameResult {
+                    slot,
+                    winner,
+                    loser,
+                })
+            })
+            .collect();
+
+        let upsets = results
+            .iter()
+            .filter_map(|r| r.upset(upset_threshold, &brackets, &self.scoring().format))
+            .collect();
+        let mut eliminated: Vec<EliminatedFavorite> = results
+            .iter()
+            .filter_map(|&result| {
+                let loser = result.loser?;
+                let champion_picks = brackets.iter().filter(|b| b.champion() == loser).count();
+                (champion_picks > 0).then_some(EliminatedFavorite {
+                    result,
+                    seed: self.scoring().format.seed(loser).unwrap_or(0),
+                    champion_picks,
+                })
+            })
+            .collect();
+        eliminated.sort_by_key(|e| std::cmp::Reverse(e.champion_picks));
+
+        let win_odds = self.tournament().is_enumerable(ENUMERATION_LIMIT).then(|| {
+            let mut wins = vec![0; active.len()];
+            let scenarios = for_each_ranking(&self.contest(&view), |ranks| {
+                for (count, &rank) in wins.iter_mut().zip(ranks) {
+                    *count += (rank == 0) as u64;
+                }
+            });
+            order
+                .iter()
+                .map(|&entry| {
+                    (
+                        active[entry].owner.clone(),
+                        PathsToVictory::share(wins[entry], scenarios),
+                    )
+                })
+                .collect()
+        });
+
+        Recap {
+            standings,
+            results,
+            upsets,
+            eliminated,
+            win_odds,
+        }
+    }
+}
+
+impl Recap {
+    /// The recap as Markdown, naming teams from `field`.
+    pub fn markdown(&self, field: &Field) -> String {
+        let name = |team: &Team| format!("({}) {}", team.seed, team.name);
+        let mut out =
+            String::from("## Standings\n\n| Rank | Owner | Score | Move |\n|---|---|---|---|\n");
+        for row in &self.standings {
+            let moved = match row.places_moved() {
+                0 => "-".to_string(),
+                places => format!("{:+}", places),
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                row.standing.rank + 1,
+                row.standing.owner,
+                row.standing.score,
+                moved
+            );
+        }
+
+        if !self.upsets.is_empty() {
+            out.push_str("\n## Upsets\n\n");
+            for upset in &self.upsets {
+                let result = upset.result;
+                let _ = writeln!(
+                    out,
+                    "- {} over {} in the {}: {} called it, {} busted",
+                    name(result.winner_team(field)),
+                    result.loser_team(field).map_or_else(String::new, name),
+                    round_name(round_num_for_slot(result.slot)),
+                    upset.called_it,
+                    upset.busted
+                );
+            }
+        }
+
+        if !self.eliminated.is_empty() {
+            out.push_str("\n## Champions eliminated\n\n");
+            for favorite in &self.eliminated {
+                let _ = writeln!(
+                    out,
+                    "- {}, picked to win it all by {}",
+                    name(favorite.team(field)),
+                    favorite.champion_picks
+                );
+            }
+        }
+
+        if let Some(odds) = &self.win_odds {
+            out.push_str("\n## Chances of winning\n\n");
+            for (owner, share) in odds {
+                let _ = writeln!(out, "- {}: {:.1}%", owner, share * 100.0);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ScoringSystem;
+    use crate::testing::standard_field;
+    use crate::{round_slot_mask, Bracket};
+
+    #[test]
+    fn recaps_the_week_since_the_last_snapshot() {
+        // Last week the first round went chalk.
+        let last_week = Tournament::new(0, round_slot_mask(1));
+        let mut pool = Pool::new(last_week.clone(), ScoringSystem::standard());
+        // Dave has the 1 seed in team slot 64 winning it all, Sarah the 8
+        // seed it meets in the second round.
+        pool.insert("Dave", Bracket::from_decisions(0));
+        pool.insert("Sarah", Bracket::from_decisions(1 << 16));
+        // This week the 8 seed wins that game.
+        pool.record_result(16, 66).unwrap();
+
+        let recap = pool.recap(&[Tournament::new(0, 0), last_week], 5);
+        assert_eq!(recap.results.len(), 1);
+        assert_eq!(recap.results[0].loser, Some(64));
+        assert_eq!(recap.upsets.len(), 1);
+        assert_eq!(recap.eliminated.len(), 1);
+        assert_eq!(recap.eliminated[0].champion_picks, 1);
+        assert_eq!(recap.standings[0].standing.owner, "Sarah");
+        assert_eq!(recap.standings[0].previous_rank, 0);
+        assert_eq!(recap.standings[1].places_moved(), -1);
+        assert_eq!(recap.win_odds, None);
+
+        let field = standard_field();
+        assert_eq!(recap.results[0].winner_team(&field).name, "East 8");
+        assert_eq!(recap.eliminated[0].team(&field).name, "East 1");
+        assert_eq!(
+            recap.standings[1].standing.champion_team(&field).name,
+            "East 1"
+        );
+        assert_eq!(
+            recap.standings[0].standing.champion_team(&field).name,
+            "East 8"
+        );
+        let markdown = recap.markdown(&field);
+        assert!(markdown.contains("| 1 | Sarah |"));
+        assert!(markdown.contains("- (8) East 8 over (1) East 1 in the Second Round: 1 called it"));
+        assert!(!markdown.contains("Chances of winning"));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{round_num_for_slot, Bracket, Decisions, Score};
+
+/// Correct picks out of the games decided so far in one round, e.g. 27/32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RoundRecord {
+    pub correct: u8,
+    pub decided: u8,
+}
+
+impl TournamentView {
+    /// `bracket`'s record in each round, indexed by round number (1..=6).
+    /// Vacated games have no right answer and aren't counted.
+    pub fn round_records(&self, bracket: &Bracket) -> [RoundRecord; 7] {
+        let mut records = [RoundRecord::default(); 7];
+        for (round, record) in records.iter_mut().enumerate().skip(1) {
+            record.decided =
+                (self.decided_in_round(round as u8) & !self.vacated()).count_ones() as u8;
+        }
+
+        let bracket_team_slots = bracket.decision_team_slots();
+        for (slot, (t, b)) in self
+            .team_slots()
+            .iter()
+            .zip(bracket_team_slots.iter())
+            .enumerate()
+        {
+            if t.is_some() && t == b {
+                records[round_num_for_slot(slot as u8) as usize].correct += 1;
+            }
+        }
+        records
+    }
+
+    /// `bracket`'s points in each round, indexed by round number (1..=6).
+    pub fn round_points(&self, bracket: &Bracket) -> [Score; 7] {
+        self.round_points_with(bracket, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::round_points`] under `scoring`.
+    pub fn round_points_with(&self, bracket: &Bracket, scoring: &ScoringSystem) -> [Score; 7] {
+        let mut points: [Score; 7] = [0; 7];
+        let bracket_team_slots = bracket.decision_team_slots();
+        for (slot, (t, b)) in self
+            .team_slots()
+            .iter()
+            .zip(bracket_team_slots.iter())
+            .enumerate()
+        {
+            if let (Some(team), true) = (t, t == b) {
+                let round = round_num_for_slot(slot as u8) as usize;
+                let earned = scoring.points_in(slot as u8, *team, self.team_slots());
+                points[round] = points[round].saturating_add(earned);
+            }
+        }
+        points
+    }
+
+    /// For each round, the brackets (as indices) with the most correct picks
+    /// in that round. Rounds with no decided games have no leaders.
+    pub fn round_leaders(&self, brackets: &[Bracket]) -> [Vec<usize>; 7] {
+        let records: Vec<[RoundRecord; 7]> =
+            brackets.iter().map(|b| self.round_records(b)).collect();
+        let mut leaders: [Vec<usize>; 7] = Default::default();
+        for (round, round_leaders) in leaders.iter_mut().enumerate().skip(1) {
+            let best = records.iter().map(|r| r[round].correct).max().unwrap_or(0);
+            if records.first().is_some_and(|r| r[round].decided > 0) {
+                *round_leaders = (0..brackets.len())
+                    .filter(|&entry| records[entry][round].correct == best)
+                    .collect();
+            }
+        }
+        leaders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tournament;
+
+    #[test]
+    fn first_round_records() {
+        // First round done, favorites (even teams) won all but game 33.
+        let view = TournamentView::new(&Tournament {
+            decisions: 1 << 33,
+            mask: 0xFFFF_FFFF_0000_0000,
+            vacated: 0,
+        });
+        let chalk = Bracket { decisions: 0 };
+        let upset = Bracket { decisions: 1 << 33 };
+
+        let records = view.round_records(&chalk);
+        assert_eq!(
+            records[1],
+            RoundRecord {
+                correct: 31,
+                decided: 32
+            }
+        );
+        assert_eq!(
+            records[2],
+            RoundRecord {
+                correct: 0,
+                decided: 0
+            }
+        );
+
+        let leaders = view.round_leaders(&[chalk, upset]);
+        assert_eq!(leaders[1], vec![1]);
+        assert!(leaders[2].is_empty());
+    }
+
+    #[test]
+    fn round_points_add_up_to_the_score() {
+        let view = TournamentView::new(&Tournament::new(0b1010 << 4, crate::COMPLETE_MASK));
+        let bracket = Bracket::from_decisions(0b0110 << 4);
+        let points = view.round_points(&bracket);
+        assert_eq!(points[0], 0);
+        assert_eq!(points.iter().sum::<Score>(), view.score(&bracket));
+
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        let points = view.round_points_with(&bracket, &upsets);
+        assert_eq!(
+            points.iter().sum::<Score>(),
+            view.score_with(&bracket, &upsets)
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use crate::scoring::ScoringSystem;
+use crate::{Bracket, Decisions, Score, Tournament};
+
+/// One region's 16-team sub-bracket: the 15 games under Elite Eight slot
+/// `4 + region`, renumbered 1..=15 with the regional final as 1, in the same
+/// layout as the full bracket. Teams keep their full-bracket team slots, and
+/// games keep their full-bracket round for scoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RegionView {
+    region: u8,
+    decisions: u16,
+    mask: u16,
+    vacated: u16,
+}
+
+impl RegionView {
+    fn new<D: Decisions + ?Sized>(d: &D, region: u8) -> RegionView {
+        assert!(region < 4, "no region {}", region);
+        let local_bits = |bits: u64| {
+            (1..16u8).fold(0u16, |acc, local| {
+                let global = global_slot(region, local);
+                acc | ((((bits >> global) & 1) as u16) << local)
+            })
+        };
+        RegionView {
+            region,
+            decisions: local_bits(d.decisions()),
+            mask: local_bits(d.mask()),
+            vacated: local_bits(d.vacated()),
+        }
+    }
+
+    pub fn region(&self) -> u8 {
+        self.region
+    }
+
+    /// The full-bracket game slot of local slot `local` (1..=15).
+    pub fn global_slot(&self, local: u8) -> u8 {
+        global_slot(self.region, local)
+    }
+
+    /// The winning team (a full-bracket team slot) of each local slot, with
+    /// undecided and vacated games empty.
+    pub fn team_slots(&self) -> [Option<u8>; 16] {
+        let first_team = 64 + 16 * self.region;
+        let mut team_slots = [None; 16];
+        for local in (1..16usize).rev() {
+            let position = 1u16 << local;
+            if self.mask & position == 0 || self.vacated & position != 0 {
+                continue;
+            }
+            let child = local * 2 + (self.decisions & position != 0) as usize;
+            team_slots[local] = if child >= 16 {
+                Some(first_team + (child - 16) as u8)
+            } else {
+                team_slots[child]
+            };
+        }
+        team_slots
+    }
+
+    /// Points `picks` (another region view, usually of a bracket) scores in
+    /// this region's games.
+    pub fn score(&self, picks: &RegionView) -> Score {
+        self.score_with(picks, &ScoringSystem::standard())
+    }
+
+    pub fn score_with(&self, picks: &RegionView, scoring: &ScoringSystem) -> Score {
+        assert_eq!(self.region, picks.region, "views of different regions");
+        let first_team = 64 + 16 * self.region;
+        let team_slots = self.team_slots();
+        let team_in = |child: usize| {
+            if child >= 16 {
+                Some(first_team + (child - 16) as u8)
+            } else {
+                team_slots[child]
+            }
+        };
+        team_slots
+            .iter()
+            .zip(picks.team_slots().iter())
+            .enumerate()
+            .fold(0, |acc, (local, (t, p))| match (t, p) {
+                (Some(t), Some(p)) if t == p => {
+                    let loser = [team_in(local * 2), team_in(local * 2 + 1)]
+                        .into_iter()
+                        .flatten()
+                        .find(|team| team != t);
+                    acc.saturating_add(scoring.points(self.global_slot(local as u8), *t, loser))
+                }
+                _ => acc,
+            })
+    }
+
+    /// Local slots where the two views have a different team, latest round
+    /// last.
+    pub fn diff(&self, other: &RegionView) -> Vec<u8> {
+        let (ours, theirs) = (self.team_slots(), other.team_slots());
+        (1..16u8)
+            .rev()
+            .filter(|&local| ours[local as usize] != theirs[local as usize])
+            .collect()
+    }
+}
+
+fn global_slot(region: u8, local: u8) -> u8 {
+    assert!((1..16).contains(&local), "no game in local slot {}", local);
+    let depth = 7 - local.leading_zeros() as u8;
+    (4 + region) * (1 << depth) + (local - (1 << depth))
+}
+
+impl Tournament {
+    /// Results in one region (0..=3, top of the bracket first).
+    pub fn region_view(&self, region: u8) -> RegionView {
+        RegionView::new(self, region)
+    }
+}
+
+impl Bracket {
+    /// Picks in one region (0..=3, top of the bracket first).
+    pub fn region_view(&self, region: u8) -> RegionView {
+        RegionView::new(self, region)
+    }
+}
+
+/// Each entry's points in one part of the bracket, and who scored the most.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionAward {
+    pub scores: Vec<Score>,
+    /// Entries (as indices) tied for the most points. Empty until some game
+    /// in the part has been decided.
+    pub winners: Vec<usize>,
+}
+
+/// Best-region awards: one per region (0..=3), then the Final Four games
+/// (slots 1..=3) as a fifth part.
+pub fn region_awards(tournament: &Tournament, brackets: &[Bracket]) -> [RegionAward; 5] {
+    region_awards_with(tournament, brackets, &ScoringSystem::standard())
+}
+
+/// [`region_awards`] under `scoring`.
+pub fn region_awards_with(
+    tournament: &Tournament,
+    brackets: &[Bracket],
+    scoring: &ScoringSystem,
+) -> [RegionAward; 5] {
+    let results = tournament.decision_team_slots();
+    std::array::from_fn(|part| {
+        let (scores, decided): (Vec<Score>, bool) = if part < 4 {
+            let view = tournament.region_view(part as u8);
+            let scores = brackets
+                .iter()
+                .map(|b| view.score_with(&b.region_view(part as u8), scoring))
+                .collect();
+            (scores, view.mask != 0)
+        } else {
+            let scores = brackets
+                .iter()
+                .map(|b| {
+                    let picks = b.decision_team_slots();
+                    (1..4u8)
+                        .filter_map(|slot| {
+                            let team = results[slot as usize]?;
+                            (picks[slot as usize] == Some(team))
+                                .then(|| scoring.points_in(slot, team, &results))
+                        })
+                        .fold(0, Score::saturating_add)
+                })
+                .collect();
+            (scores, tournament.mask() & 0b1110 != 0)
+        };
+
+        let best = scores.iter().copied().max().unwrap_or(0);
+        let winners = if decided {
+            (0..scores.len()).filter(|&e| scores[e] == best).collect()
+        } else {
+            vec![]
+        };
+        RegionAward { scores, winners }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{points_for_pick, COMPLETE_MASK};
+
+    #[test]
+    fn maps_local_slots() {
+        assert_eq!(global_slot(0, 1), 4);
+        assert_eq!(global_slot(3, 1), 7);
+        assert_eq!(global_slot(1, 2), 10);
+        assert_eq!(global_slot(1, 15), 47);
+        assert_eq!(global_slot(3, 8), 56);
+    }
+
+    #[test]
+    fn regions_add_up_to_the_full_score() {
+        let tournament = Tournament::new(0, COMPLETE_MASK);
+        let bracket = Bracket::from_decisions(0b1010_0110_0000);
+
+        let regional: Score = (0..4)
+            .map(|r| tournament.region_view(r).score(&bracket.region_view(r)))
+            .sum();
+        let (results, picks) = (
+            tournament.decision_team_slots(),
+            bracket.decision_team_slots(),
+        );
+        let final_four: Score = (1..4)
+            .filter(|&slot| results[slot] == picks[slot])
+            .map(|slot| points_for_pick(slot as u8, results[slot].unwrap()))
+            .sum();
+        assert_eq!(regional + final_four, bracket.score(&tournament));
+
+        let view = tournament.region_view(1);
+        assert_eq!(view.team_slots()[1], Some(80));
+        assert_eq!(view.diff(&bracket.region_view(1)), vec![3, 1]);
+    }
+
+    #[test]
+    fn regional_upsets_earn_their_bonus() {
+        let decisions = 0b1010_0110_0000 | 0xF0F0_0000_0000_0000;
+        let tournament = Tournament::new(decisions, COMPLETE_MASK);
+        let bracket = Bracket::from_decisions(decisions);
+        let scoring = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+
+        let regional: Score = (0..4)
+            .map(|r| {
+                tournament
+                    .region_view(r)
+                    .score_with(&bracket.region_view(r), &scoring)
+            })
+            .sum();
+        let results = tournament.decision_team_slots();
+        let final_four: Score = (1..4u8)
+            .map(|slot| scoring.points_in(slot, results[slot as usize].unwrap(), &results))
+            .sum();
+        assert_eq!(
+            regional + final_four,
+            bracket.score_with(&tournament, &scoring)
+        );
+    }
+
+    #[test]
+    fn awards_each_region_and_the_final_four() {
+        // Only region 0's first-round games are in, all won by the top team.
+        let tournament = Tournament::new(0, 0xFF << 32);
+        let brackets = [Bracket::from_decisions(0), Bracket::from_decisions(1 << 32)];
+
+        let awards = region_awards(&tournament, &brackets);
+        assert_eq!(awards[0].winners, vec![0]);
+        assert!(awards[0].scores[0] > awards[0].scores[1]);
+        for award in &awards[1..] {
+            assert!(award.winners.is_empty());
+            assert_eq!(award.scores, vec![0, 0]);
+        }
+
+        // The second entry's 16 seed (65) earns its upset bonus.
+        let tournament = Tournament::new(1 << 32, 0xFF << 32);
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        let awards = region_awards_with(&tournament, &brackets, &upsets);
+        assert_eq!(awards[0].scores, vec![7, 7 + 1 + 15]);
+        assert_eq!(awards[0].winners, vec![1]);
+    }
+}
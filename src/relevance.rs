@@ -0,0 +1,131 @@
+use std::collections::BTreeSet;
+
+use crate::pool::Pool;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, ENUMERATION_LIMIT};
+
+impl Bracket {
+    /// Undecided games whose outcome changes which places this bracket can
+    /// still finish in among `pool` under `scoring`, latest round last.
+    /// Games the bracket's possible finishes don't depend on are left out.
+    /// Every remaining scenario is scored, so this is `None` while more than
+    /// [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn relevant_games(
+        &self,
+        pool: &[Bracket],
+        view: &TournamentView,
+        scoring: &ScoringSystem,
+    ) -> Option<Vec<u8>> {
+        if !view.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        // This bracket goes last, so it's scored from the same table.
+        let mut brackets = pool.to_vec();
+        brackets.push(*self);
+        let deltas = ScoreDeltaTable::for_view_with(view, &brackets, scoring);
+        Some(relevant_games(&deltas, pool.len(), view))
+    }
+}
+
+impl Pool {
+    /// [`Bracket::relevant_games`] for `owner`'s entry against the other
+    /// active entries, adjustments applied. `None` when `owner` has no
+    /// active entry or too many scenarios are left.
+    pub fn relevant_games(&self, owner: &str) -> Option<Vec<u8>> {
+        let view = TournamentView::new(self.tournament());
+        if !view.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let mut entries = self.active_entries();
+        let entry = entries.iter().position(|e| e.owner == owner)?;
+        let entry = entries.remove(entry);
+        entries.push(entry);
+        let brackets: Vec<Bracket> = entries.iter().map(|e| e.bracket).collect();
+        let mut deltas = ScoreDeltaTable::for_view_with(&view, &brackets, self.scoring());
+        for (i, e) in entries.iter().enumerate() {
+            deltas.adjust(i, e.adjustment());
+        }
+        Some(relevant_games(&deltas, entries.len() - 1, &view))
+    }
+}
+
+/// The undecided games with two outcomes that leave bracket `entry`, the
+/// last in `deltas`, different sets of possible ranks.
+fn relevant_games(deltas: &ScoreDeltaTable, entry: usize, view: &TournamentView) -> Vec<u8> {
+    view.undecided()
+        .iter()
+        .copied()
+        .filter(|&slot| {
+            let finishes_0 = possible_ranks(deltas, entry, view, (slot, 0));
+            let finishes_1 = possible_ranks(deltas, entry, view, (slot, 1));
+            // A walkover has only one outcome and can't matter.
+            !finishes_0.is_empty() && !finishes_1.is_empty() && finishes_0 != finishes_1
+        })
+        .collect()
+}
+
+/// Every zero-based rank bracket `entry` reaches in some remaining scenario
+/// with `outcome`, counting the brackets before it that score strictly higher.
+fn possible_ranks(
+    deltas: &ScoreDeltaTable,
+    entry: usize,
+    view: &TournamentView,
+    outcome: (u8, u8),
+) -> BTreeSet<usize> {
+    let mut ranks = BTreeSet::new();
+    view.for_each_scenario(Some(outcome), |team_slots| {
+        let points = deltas.score(entry, team_slots);
+        let rank = (0..entry)
+            .filter(|&i| deltas.score(i, team_slots) > points)
+            .count();
+        ranks.insert(rank);
+    });
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn only_games_that_move_the_entry_matter() {
+        // Both semifinals and the title game are left.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        });
+        // Same picks everywhere except the semifinal in slot 3.
+        let pool = [Bracket { decisions: 0 }, Bracket { decisions: 1 << 3 }];
+
+        let scoring = ScoringSystem::standard();
+        assert_eq!(
+            pool[0].relevant_games(&pool, &view, &scoring),
+            Some(vec![3])
+        );
+        assert_eq!(
+            pool[0].relevant_games(&pool[..1], &view, &scoring),
+            Some(vec![])
+        );
+
+        let everything = TournamentView::new(&Tournament::new(0, 0));
+        assert_eq!(pool[0].relevant_games(&pool, &everything, &scoring), None);
+    }
+
+    #[test]
+    fn pool_entries_by_owner() {
+        let tournament = Tournament::new(0, COMPLETE_MASK & !0b1110);
+        let mut pool = Pool::new(tournament, ScoringSystem::standard());
+        pool.insert("ann", Bracket::from_decisions(0));
+        pool.insert("bob", Bracket::from_decisions(1 << 3));
+        assert_eq!(pool.relevant_games("ann"), Some(vec![3]));
+        assert_eq!(pool.relevant_games("cat"), None);
+
+        // Far enough ahead that no game can catch bob up.
+        pool.adjust("ann", 100, "bonus").unwrap();
+        assert_eq!(pool.relevant_games("ann"), Some(vec![]));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::view::TournamentView;
+use crate::{round_slot_mask, Tournament};
+
+impl Tournament {
+    /// Undecided game slots, latest round last.
+    pub fn undecided_games(&self) -> Vec<u8> {
+        (1..=63u8)
+            .rev()
+            .filter(|&slot| self.mask & (1 << slot) == 0)
+            .collect()
+    }
+
+    /// Undecided games in each round, indexed by round number (1..=6).
+    pub fn undecided_by_round(&self) -> [u32; 7] {
+        let mut counts = [0; 7];
+        for (round, count) in counts.iter_mut().enumerate().skip(1) {
+            *count = (round_slot_mask(round as u8) & !self.mask).count_ones();
+        }
+        counts
+    }
+
+    /// How many ways the undecided games can still play out: the number of
+    /// scenarios an exhaustive enumeration visits. A game with a vacated side
+    /// is a walkover and only has one outcome. At most 2^63, so it always
+    /// fits.
+    pub fn remaining_scenarios(&self) -> u128 {
+        1 << TournamentView::new(self).open_games().len()
+    }
+
+    /// Whether enumerating every remaining scenario is at most `limit` of
+    /// them, for choosing between exhaustive and sampled calculations.
+    pub fn is_enumerable(&self, limit: u128) -> bool {
+        self.remaining_scenarios() <= limit
+    }
+}
+
+impl TournamentView {
+    /// [`Tournament::is_enumerable`] for the viewed tournament.
+    pub fn is_enumerable(&self, limit: u128) -> bool {
+        1 << self.open_games().len() <= limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn counts_match_enumeration() {
+        let tournament = Tournament {
+            decisions: 0,
+            mask: round_slot_mask(1) | round_slot_mask(2),
+            vacated: 0,
+        };
+        assert_eq!(tournament.undecided_games().len(), 15);
+        assert_eq!(tournament.undecided_by_round(), [0, 0, 0, 8, 4, 2, 1]);
+        assert_eq!(tournament.remaining_scenarios(), 1 << 15);
+
+        let mut visited = 0u128;
+        TournamentView::new(&tournament).for_each_scenario(None, |_| visited += 1);
+        assert_eq!(visited, tournament.remaining_scenarios());
+
+        let empty = Tournament {
+            decisions: 0,
+            mask: 0,
+            vacated: 0,
+        };
+        assert_eq!(empty.remaining_scenarios(), 1 << 63);
+    }
+
+    #[test]
+    fn walkovers_have_one_outcome() {
+        // The bottom semifinal was vacated.
+        let tournament = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1) & !(1 << 2),
+            vacated: 1 << 3,
+        };
+        assert_eq!(tournament.remaining_scenarios(), 2);
+        assert!(tournament.is_enumerable(2));
+        assert!(!tournament.is_enumerable(1));
+    }
+}
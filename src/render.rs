@@ -0,0 +1,138 @@
+use crate::field::Field;
+use crate::view::TournamentView;
+use crate::{round_num_for_slot, Bracket, Decisions, Tournament};
+
+/// How picks are marked against results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marks {
+    /// `+` for a correct pick, `x` for a wrong one.
+    Ascii,
+    /// `✓` and `✗`.
+    Unicode,
+}
+
+impl Marks {
+    fn correct(self) -> char {
+        match self {
+            Marks::Ascii => '+',
+            Marks::Unicode => '✓',
+        }
+    }
+
+    fn wrong(self) -> char {
+        match self {
+            Marks::Ascii => 'x',
+            Marks::Unicode => '✗',
+        }
+    }
+}
+
+/// Characters of a team's name shown in each column.
+const NAME_WIDTH: usize = 16;
+
+impl Field {
+    /// The tournament as a tree lying on its side: the teams in the first
+    /// column, and each round's winners in the next, level with the middle
+    /// of the games they won.
+    pub fn render_tournament(&self, tournament: &Tournament) -> String {
+        self.render_tree(&tournament.decision_team_slots(), |_| ' ')
+    }
+
+    /// The bracket's picks as a tree like [`Field::render_tournament`]'s,
+    /// each decided game's pick marked right or wrong against `tournament`.
+    pub fn render_bracket(
+        &self,
+        bracket: &Bracket,
+        tournament: &Tournament,
+        marks: Marks,
+    ) -> String {
+        let view = TournamentView::new(tournament);
+        let picks = bracket.decision_team_slots();
+        self.render_tree(&picks, |slot| {
+            if !view.is_decided(slot) {
+                ' '
+            } else if view.team_slots()[slot as usize] == picks[slot as usize] {
+                marks.correct()
+            } else {
+                marks.wrong()
+            }
+        })
+    }
+
+    fn render_tree<F: Fn(u8) -> char>(&self, winners: &[Option<u8>], mark: F) -> String {
+        // Leaf `i` sits on row 2i, and a game on the row halfway between its
+        // first and last leaves.
+        let mut rows = vec![vec![String::new(); 7]; 127];
+        for team in 64..128u8 {
+            rows[(team as usize - 64) * 2][0] = self.cell(Some(team), ' ');
+        }
+        for slot in 1..64u8 {
+            let round = round_num_for_slot(slot);
+            let first = ((slot as usize) << round) - 64;
+            let last = first + (1 << round) - 1;
+            rows[first + last][round as usize] = self.cell(winners[slot as usize], mark(slot));
+        }
+
+        let blank = " ".repeat(NAME_WIDTH + 5);
+        let mut text = String::new();
+        for row in rows {
+            let line: Vec<&str> = row
+                .iter()
+                .map(|cell| {
+                    if cell.is_empty() {
+                        blank.as_str()
+                    } else {
+                        cell.as_str()
+                    }
+                })
+                .collect();
+            text.push_str(line.concat().trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// "seed name mark", padded to the column width.
+    fn cell(&self, team: Option<u8>, mark: char) -> String {
+        let (seed, name) = match team {
+            Some(team) => {
+                let team = self.team(team);
+                (
+                    team.seed.to_string(),
+                    team.name.chars().take(NAME_WIDTH).collect(),
+                )
+            }
+            None => (String::new(), "-".to_string()),
+        };
+        format!("{:>2} {:<width$}{} ", seed, name, mark, width = NAME_WIDTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_slot_mask;
+    use crate::testing::standard_field;
+
+    #[test]
+    fn draws_winners_between_their_games() {
+        let field = standard_field();
+        let tournament = Tournament::new(1 << 33, round_slot_mask(1));
+        let text = field.render_tournament(&tournament);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 127);
+        assert_eq!(lines[0].trim(), "1 East 1");
+        assert!(lines[1].ends_with("1 East 1"));
+        // Game 33 went to the 9 seed.
+        assert!(lines[5].ends_with("9 East 9"));
+        // Undecided games are drawn as a dash.
+        assert!(lines[3].trim_end().ends_with('-'));
+
+        let picks = Bracket::from_decisions(0);
+        let marked = field.render_bracket(&picks, &tournament, Marks::Unicode);
+        let lines: Vec<&str> = marked.lines().collect();
+        assert!(lines[1].ends_with("East 1          ✓"));
+        assert!(lines[5].ends_with("East 8          ✗"));
+        assert!(lines[3].ends_with("East 1"));
+    }
+}
@@ -0,0 +1,369 @@
+use std::fmt;
+
+use crate::field::{Field, Team};
+use crate::format::BracketFormat;
+use crate::{Bracket, Decisions, Tournament};
+
+/// A game as recorded: `winner` beat `loser` (team slots, 64..=127) in
+/// `slot`. Against a vacated side there's no `loser`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    pub slot: u8,
+    pub winner: u8,
+    pub loser: Option<u8>,
+}
+
+impl GameResult {
+    /// The winner as a team of `field`.
+    pub fn winner_team<'f>(&self, field: &'f Field) -> &'f Team {
+        field.team(self.winner)
+    }
+
+    /// The loser as a team of `field`, unless it was a walkover.
+    pub fn loser_team<'f>(&self, field: &'f Field) -> Option<&'f Team> {
+        self.loser.map(|loser| field.team(loser))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultError {
+    /// Not a game slot (1..=63).
+    NoGame {
+        slot: u8,
+    },
+    /// A game feeding this one hasn't been decided.
+    NotReady {
+        slot: u8,
+        feeder: u8,
+    },
+    /// `team` isn't one of the two teams in the game.
+    NotPlaying {
+        slot: u8,
+        team: u8,
+    },
+    /// Undo it first to change it.
+    AlreadyDecided {
+        slot: u8,
+    },
+    NotDecided {
+        slot: u8,
+    },
+    /// The game this one feeds has been decided, so undoing this one would
+    /// leave it without a team.
+    LaterGameDecided {
+        slot: u8,
+        later: u8,
+    },
+    /// The game was a no-contest; take it back with
+    /// [`Tournament::undo_no_contest`].
+    NoContest {
+        slot: u8,
+    },
+    /// The game was played; take it back with [`Tournament::undo_result`].
+    Played {
+        slot: u8,
+    },
+}
+
+impl fmt::Display for ResultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ResultError::NoGame { slot } => write!(f, "no game in slot {}", slot),
+            ResultError::NotReady { slot, feeder } => {
+                write!(f, "game {} feeds game {} and is undecided", feeder, slot)
+            }
+            ResultError::NotPlaying { slot, team } => {
+                write!(f, "team {} isn't playing in game {}", team, slot)
+            }
+            ResultError::AlreadyDecided { slot } => write!(f, "game {} is already decided", slot),
+            ResultError::NotDecided { slot } => write!(f, "game {} hasn't been decided", slot),
+            ResultError::LaterGameDecided { slot, later } => {
+                write!(
+                    f,
+                    "game {} is decided and needs the winner of game {}",
+                    later, slot
+                )
+            }
+            ResultError::NoContest { slot } => write!(f, "game {} was a no-contest", slot),
+            ResultError::Played { slot } => write!(f, "game {} was played", slot),
+        }
+    }
+}
+
+impl std::error::Error for ResultError {}
+
+impl Tournament {
+    /// The two sides of the game in `slot`, once both are known. A vacated
+    /// game upstream leaves its side empty.
+    fn sides(&self, slot: u8) -> Result<[Option<u8>; 2], ResultError> {
+        if !(1..=63).contains(&slot) {
+            return Err(ResultError::NoGame { slot });
+        }
+        let team_slots = self.decision_team_slots();
+        let side = |child: u8| {
+            if child >= 64 {
+                Ok(Some(child))
+            } else if self.decision(child).is_none() {
+                Err(ResultError::NotReady {
+                    slot,
+                    feeder: child,
+                })
+            } else {
+                Ok(team_slots[child as usize])
+            }
+        };
+        Ok([side(slot * 2)?, side(slot * 2 + 1)?])
+    }
+
+    /// Records `winner` (a team slot) winning the game in `slot`, checking
+    /// that it's one of the two teams playing there.
+    pub fn record_result(&mut self, slot: u8, winner: u8) -> Result<GameResult, ResultError> {
+        let sides = self.sides(slot)?;
+        if self.decision(slot).is_some() {
+            return Err(ResultError::AlreadyDecided { slot });
+        }
+        let side = sides
+            .iter()
+            .position(|&team| team == Some(winner))
+            .ok_or(ResultError::NotPlaying { slot, team: winner })?;
+        self.set_decision(slot, side as u8);
+        Ok(GameResult {
+            slot,
+            winner,
+            loser: sides[1 - side],
+        })
+    }
+
+    /// Takes back the result in `slot`, as long as the game it feeds is
+    /// still undecided. Returns the result that was removed.
+    pub fn undo_result(&mut self, slot: u8) -> Result<GameResult, ResultError> {
+        let sides = self.sides(slot)?;
+        let Some(side) = self.decision(slot) else {
+            return Err(ResultError::NotDecided { slot });
+        };
+        if self.vacated & (1 << slot) != 0 {
+            return Err(ResultError::NoContest { slot });
+        }
+        self.clear(slot)?;
+        Ok(GameResult {
+            slot,
+            winner: sides[side as usize].ok_or(ResultError::NotDecided { slot })?,
+            loser: sides[1 - side as usize],
+        })
+    }
+
+    /// Records the game in `slot` as a no-contest: decided, but with no
+    /// winner to advance, so the game it feeds goes to the other side as a
+    /// walkover. Both sides must be known, as for a result.
+    pub fn record_no_contest(&mut self, slot: u8) -> Result<(), ResultError> {
+        self.sides(slot)?;
+        if self.decision(slot).is_some() {
+            return Err(ResultError::AlreadyDecided { slot });
+        }
+        self.set_decision(slot, 0);
+        self.vacated |= 1 << slot;
+        Ok(())
+    }
+
+    /// Takes back a no-contest in `slot`, as long as the game it feeds is
+    /// still undecided.
+    pub fn undo_no_contest(&mut self, slot: u8) -> Result<(), ResultError> {
+        self.sides(slot)?;
+        if self.decision(slot).is_none() {
+            return Err(ResultError::NotDecided { slot });
+        }
+        if self.vacated & (1 << slot) == 0 {
+            return Err(ResultError::Played { slot });
+        }
+        self.clear(slot)
+    }
+
+    fn clear(&mut self, slot: u8) -> Result<(), ResultError> {
+        if slot > 1 && self.decision(slot / 2).is_some() {
+            return Err(ResultError::LaterGameDecided {
+                slot,
+                later: slot / 2,
+            });
+        }
+        let position = 1u64 << slot;
+        self.decisions &= !position;
+        self.mask &= !position;
+        self.vacated &= !position;
+        Ok(())
+    }
+}
+
+/// A result where the winner was seeded at least some number of lines
+/// worse than the team it beat, e.g. a 12 over a 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpsetRecorded {
+    pub result: GameResult,
+    pub winner_seed: u8,
+    pub loser_seed: u8,
+    /// Entries that picked the winner to win this game.
+    pub called_it: usize,
+    /// Entries that picked the loser to win this game.
+    pub busted: usize,
+}
+
+impl UpsetRecorded {
+    pub fn seed_differential(&self) -> u8 {
+        self.winner_seed - self.loser_seed
+    }
+}
+
+impl GameResult {
+    /// The upset alert for this result, if the winner's seed in `format`
+    /// is at least `threshold` (minimum 1) worse than the loser's, counting
+    /// how entries among `brackets` picked the game.
+    pub fn upset(
+        &self,
+        threshold: u8,
+        brackets: &[Bracket],
+        format: &BracketFormat,
+    ) -> Option<UpsetRecorded> {
+        let loser = self.loser?;
+        let (winner_seed, loser_seed) = (format.seed(self.winner)?, format.seed(loser)?);
+        if winner_seed < loser_seed.saturating_add(threshold.max(1)) {
+            return None;
+        }
+        let picks: Vec<Option<u8>> = brackets
+            .iter()
+            .map(|b| b.decision_team_slots()[self.slot as usize])
+            .collect();
+        Some(UpsetRecorded {
+            result: *self,
+            winner_seed,
+            loser_seed,
+            called_it: picks.iter().filter(|&&p| p == Some(self.winner)).count(),
+            busted: picks.iter().filter(|&&p| p == Some(loser)).count(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_undoes_results() {
+        let mut tournament = Tournament::new(0, 0);
+        assert_eq!(
+            tournament.record_result(32, 65),
+            Ok(GameResult {
+                slot: 32,
+                winner: 65,
+                loser: Some(64)
+            })
+        );
+        assert_eq!(tournament.decision(32), Some(1));
+        assert_eq!(
+            tournament.record_result(32, 64),
+            Err(ResultError::AlreadyDecided { slot: 32 })
+        );
+        assert_eq!(
+            tournament.record_result(16, 65),
+            Err(ResultError::NotReady {
+                slot: 16,
+                feeder: 33
+            })
+        );
+
+        tournament.record_result(33, 66).unwrap();
+        assert_eq!(
+            tournament.record_result(16, 64),
+            Err(ResultError::NotPlaying { slot: 16, team: 64 })
+        );
+        tournament.record_result(16, 66).unwrap();
+        assert_eq!(
+            tournament.undo_result(32),
+            Err(ResultError::LaterGameDecided {
+                slot: 32,
+                later: 16
+            })
+        );
+
+        let undone = tournament.undo_result(16).unwrap();
+        assert_eq!((undone.winner, undone.loser), (66, Some(65)));
+        assert_eq!(tournament.decision(16), None);
+        assert_eq!(
+            tournament.undo_result(16),
+            Err(ResultError::NotDecided { slot: 16 })
+        );
+        assert_eq!(
+            tournament.record_result(0, 64),
+            Err(ResultError::NoGame { slot: 0 })
+        );
+    }
+
+    #[test]
+    fn no_contests_leave_a_walkover() {
+        let mut tournament = Tournament::new(0, 0);
+        tournament.record_result(32, 64).unwrap();
+        assert_eq!(tournament.record_no_contest(33), Ok(()));
+        assert_eq!(
+            tournament.record_no_contest(33),
+            Err(ResultError::AlreadyDecided { slot: 33 })
+        );
+        assert_eq!(tournament.decision_team_slots()[33], None);
+
+        // The winner of slot 32 advances with no one to beat.
+        assert_eq!(
+            tournament.record_result(16, 64),
+            Ok(GameResult {
+                slot: 16,
+                winner: 64,
+                loser: None
+            })
+        );
+        assert_eq!(
+            tournament.undo_no_contest(33),
+            Err(ResultError::LaterGameDecided {
+                slot: 33,
+                later: 16
+            })
+        );
+        tournament.undo_result(16).unwrap();
+        assert_eq!(
+            tournament.undo_result(33),
+            Err(ResultError::NoContest { slot: 33 })
+        );
+        assert_eq!(
+            tournament.undo_no_contest(32),
+            Err(ResultError::Played { slot: 32 })
+        );
+        tournament.undo_no_contest(33).unwrap();
+        assert_eq!(tournament.decision(33), None);
+        assert_eq!(tournament.vacated(), 0);
+
+        // Once undone, the game can be played.
+        tournament.record_result(33, 67).unwrap();
+        assert_eq!(tournament.record_result(16, 67).unwrap().loser, Some(64));
+    }
+
+    #[test]
+    fn flags_upsets_past_the_threshold() {
+        let mut tournament = Tournament::new(0, 0);
+        let brackets = [Bracket::from_decisions(0), Bracket::from_decisions(1 << 34)];
+        let format = BracketFormat::standard();
+
+        // 12 seed (69) over 5 seed (68).
+        let result = tournament.record_result(34, 69).unwrap();
+        let upset = result.upset(5, &brackets, &format).unwrap();
+        assert_eq!((upset.winner_seed, upset.loser_seed), (12, 5));
+        assert_eq!(upset.seed_differential(), 7);
+        assert_eq!((upset.called_it, upset.busted), (1, 1));
+        assert_eq!(result.upset(8, &brackets, &format), None);
+        assert_eq!(result.upset(u8::MAX, &brackets, &format), None);
+
+        // A 1 seed winning is never an upset.
+        let chalk = tournament.record_result(32, 64).unwrap();
+        assert_eq!(chalk.upset(0, &brackets, &format), None);
+
+        // Seeds come from the format: here 68 is the 12 and 69 the 5.
+        let mut order = crate::SEED_ORDER;
+        order.swap(4, 5);
+        let swapped = BracketFormat::new(6, vec![order.to_vec(); 4]).unwrap();
+        assert_eq!(result.upset(1, &brackets, &swapped), None);
+    }
+}
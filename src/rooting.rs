@@ -0,0 +1,150 @@
+use crate::paths::PathsToVictory;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Score, ENUMERATION_LIMIT};
+
+/// Which way one undecided game should go for an entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootingInterest {
+    pub slot: u8,
+    /// The side to root for: the winner coming from child `slot * 2 + side`.
+    pub side: u8,
+    /// The team on that side, once it's known.
+    pub team: Option<u8>,
+    /// The entry's best zero-based finish with each side winning, indexed by
+    /// side.
+    pub best_finish: [usize; 2],
+    /// Share of scenarios the entry wins (alone or tied) with each side
+    /// winning, every scenario counting the same.
+    pub win_share: [f64; 2],
+}
+
+impl RootingInterest {
+    /// Places of best finish gained by the preferred side winning.
+    pub fn places_gained(&self) -> usize {
+        self.best_finish[1 - self.side as usize] - self.best_finish[self.side as usize]
+    }
+}
+
+impl TournamentView {
+    /// The games still to be played that matter to bracket `entry` among
+    /// `brackets`, most important first: by places of best finish at stake,
+    /// then by the change in winning share. Games where neither changes are
+    /// left out.
+    ///
+    /// Every remaining scenario is scored, so this is `None` while more
+    /// than [`crate::ENUMERATION_LIMIT`] are left.
+    pub fn rooting_guide(
+        &self,
+        brackets: &[Bracket],
+        entry: usize,
+    ) -> Option<Vec<RootingInterest>> {
+        self.rooting_guide_with(brackets, entry, &ScoringSystem::standard())
+    }
+
+    /// [`TournamentView::rooting_guide`] under `scoring`.
+    pub fn rooting_guide_with(
+        &self,
+        brackets: &[Bracket],
+        entry: usize,
+        scoring: &ScoringSystem,
+    ) -> Option<Vec<RootingInterest>> {
+        if !self.is_enumerable(ENUMERATION_LIMIT) {
+            return None;
+        }
+        let games = self.open_games();
+        let deltas = ScoreDeltaTable::for_view_with(self, brackets, scoring);
+        let mut best = vec![[usize::MAX; 2]; games.len()];
+        let mut wins = vec![[0u64; 2]; games.len()];
+        let mut scenarios = vec![[0u64; 2]; games.len()];
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+
+        self.for_each_scenario(None, |team_slots| {
+            let rank = deltas.ranks(team_slots, &mut scores)[entry];
+            for (k, &slot) in games.iter().enumerate() {
+                let side =
+                    (team_slots[slot as usize] == team_slots[slot as usize * 2 + 1]) as usize;
+                best[k][side] = best[k][side].min(rank);
+                wins[k][side] += (rank == 0) as u64;
+                scenarios[k][side] += 1;
+            }
+        });
+
+        let mut guide: Vec<RootingInterest> = games
+            .iter()
+            .enumerate()
+            .filter_map(|(k, &slot)| {
+                let win_share =
+                    [0, 1].map(|side| PathsToVictory::share(wins[k][side], scenarios[k][side]));
+                if best[k][0] == best[k][1] && win_share[0] == win_share[1] {
+                    return None;
+                }
+                let side = if best[k][0] != best[k][1] {
+                    (best[k][1] < best[k][0]) as u8
+                } else {
+                    (win_share[1] > win_share[0]) as u8
+                };
+                Some(RootingInterest {
+                    slot,
+                    side,
+                    team: self.team_slots()[slot as usize * 2 + side as usize],
+                    best_finish: best[k],
+                    win_share,
+                })
+            })
+            .collect();
+
+        guide.sort_by(|a, b| {
+            let swing = |r: &RootingInterest| (r.win_share[0] - r.win_share[1]).abs();
+            b.places_gained()
+                .cmp(&a.places_gained())
+                .then(swing(b).total_cmp(&swing(a)))
+        });
+        Some(guide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn roots_for_the_picked_champion() {
+        // Both semifinals and the title game are left.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+
+        let guide = view.rooting_guide(&brackets, 0).unwrap();
+        assert_eq!(guide.len(), 3);
+        // Entry 0's champion losing its semifinal is the only way it can't
+        // win, so that game comes first.
+        assert_eq!((guide[0].slot, guide[0].side), (2, 0));
+        assert_eq!(guide[0].team, view.team_slots()[4]);
+        assert_eq!(guide[0].best_finish, [0, 1]);
+        assert_eq!(guide[0].places_gained(), 1);
+        // Then against entry 1's champion in the other semifinal.
+        assert_eq!((guide[1].slot, guide[1].side), (3, 1));
+        assert_eq!(guide[1].win_share, [0.25, 0.5]);
+        assert_eq!((guide[2].slot, guide[2].side, guide[2].team), (1, 0, None));
+
+        // When none of the games left score, none of them matter.
+        let early_rounds = ScoringSystem {
+            multipliers: [0, 1, 1, 1, 1, 0, 0],
+            ..ScoringSystem::standard()
+        };
+        assert!(view
+            .rooting_guide_with(&brackets, 0, &early_rounds)
+            .unwrap()
+            .is_empty());
+
+        // Too early to score every scenario.
+        let opening = TournamentView::new(&Tournament::new(0, 0));
+        assert_eq!(opening.rooting_guide(&brackets, 0), None);
+    }
+}
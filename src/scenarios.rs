@@ -0,0 +1,104 @@
+use crate::view::TournamentView;
+use crate::{Decisions, Tournament, COMPLETE_MASK};
+
+/// Every way the undecided games of a tournament can still play out, each
+/// as a completed [`Tournament`]. Made by [`Tournament::scenarios`].
+#[derive(Debug, Clone)]
+pub struct Scenarios {
+    base: Tournament,
+    open: Vec<u8>,
+    next: u128,
+    total: u128,
+}
+
+impl Tournament {
+    /// Iterates over every completed tournament the remaining games can
+    /// produce: one per combination of the games that can go either way,
+    /// with walkovers going to the only side they can. There are
+    /// [`Tournament::remaining_scenarios`] of them; a finished tournament
+    /// yields just itself.
+    pub fn scenarios(&self) -> Scenarios {
+        let view = TournamentView::new(self);
+        let mut base = self.clone();
+        for (slot, side) in view.walkovers() {
+            base.set_decision(slot, side);
+        }
+        let open = view.open_games();
+        Scenarios {
+            base,
+            total: 1 << open.len(),
+            open,
+            next: 0,
+        }
+    }
+}
+
+impl Iterator for Scenarios {
+    type Item = Tournament;
+
+    fn next(&mut self) -> Option<Tournament> {
+        if self.next == self.total {
+            return None;
+        }
+        let mut scenario = self.base.clone();
+        for (i, &slot) in self.open.iter().enumerate() {
+            scenario.set_decision(slot, ((self.next >> i) & 1) as u8);
+        }
+        debug_assert_eq!(scenario.mask(), COMPLETE_MASK);
+        self.next += 1;
+        Some(scenario)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = usize::try_from(self.total - self.next).ok();
+        (left.unwrap_or(usize::MAX), left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BestFinishes, Bracket};
+    use std::collections::HashSet;
+
+    #[test]
+    fn yields_each_completed_tournament_once() {
+        // Both semifinals and the title game are left.
+        let tournament = Tournament::new(0, COMPLETE_MASK & !0b1110);
+        let scenarios: Vec<Tournament> = tournament.scenarios().collect();
+        assert_eq!(scenarios.len() as u128, tournament.remaining_scenarios());
+        assert!(scenarios.iter().all(|s| s.mask() == COMPLETE_MASK));
+        let champions: HashSet<u8> = scenarios
+            .iter()
+            .map(|s| s.decision_team_slots()[1].unwrap())
+            .collect();
+        assert_eq!(champions, HashSet::from([64, 80, 96, 112]));
+
+        // Best finishes from the scenarios agree with the enumeration.
+        let brackets = [Bracket::from_decisions(0), Bracket::from_decisions(0b0110)];
+        let view = TournamentView::new(&tournament);
+        let expected = BestFinishes::for_view(&brackets, &view);
+        for bracket in &brackets {
+            let best = scenarios
+                .iter()
+                .map(|s| {
+                    let score = bracket.score(s);
+                    brackets.iter().filter(|b| b.score(s) > score).count()
+                })
+                .min();
+            assert_eq!(best, expected.possible_finishes.get(bracket).copied());
+        }
+    }
+
+    #[test]
+    fn walkovers_are_filled_in() {
+        let tournament = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b110,
+            vacated: 1 << 3,
+        };
+        let scenarios: Vec<Tournament> = tournament.scenarios().collect();
+        assert_eq!(scenarios.len(), 2);
+        assert_eq!(tournament.scenarios().size_hint(), (2, Some(2)));
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use crate::results::ResultError;
+use crate::{Decisions, Tournament};
+
+/// Schedule details for one game. Everything is optional since feeds fill
+/// them in as the bracket gets set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameInfo {
+    pub tip_time: Option<SystemTime>,
+    pub venue: Option<String>,
+    pub broadcast: Option<String>,
+}
+
+/// Game info keyed by game slot (1..=63).
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    games: HashMap<u8, GameInfo>,
+}
+
+impl Schedule {
+    pub fn new() -> Schedule {
+        Schedule::default()
+    }
+
+    /// Sets the info for the game in `slot`, replacing any already there.
+    pub fn insert(&mut self, slot: u8, info: GameInfo) -> Result<(), ResultError> {
+        if !(1..=63).contains(&slot) {
+            return Err(ResultError::NoGame { slot });
+        }
+        self.games.insert(slot, info);
+        Ok(())
+    }
+
+    pub fn game(&self, slot: u8) -> Option<&GameInfo> {
+        self.games.get(&slot)
+    }
+
+    /// Undecided games tipping off in `[now, now + window)`, soonest first.
+    pub fn upcoming(
+        &self,
+        tournament: &Tournament,
+        now: SystemTime,
+        window: Duration,
+    ) -> Vec<(u8, &GameInfo)> {
+        let end = now + window;
+        let mut games: Vec<(u8, &GameInfo)> = self
+            .games
+            .iter()
+            .filter(|(&slot, _)| tournament.mask() & (1 << slot) == 0)
+            .filter(|(_, info)| info.tip_time.is_some_and(|t| t >= now && t < end))
+            .map(|(&slot, info)| (slot, info))
+            .collect();
+        games.sort_by_key(|(slot, info)| (info.tip_time, *slot));
+        games
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upcoming_skips_decided_and_distant_games() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let hours = |h: u64| Duration::from_secs(h * 3600);
+        let at = |h| GameInfo {
+            tip_time: Some(now + hours(h)),
+            ..GameInfo::default()
+        };
+        let mut schedule = Schedule::new();
+        schedule.insert(32, at(1)).unwrap();
+        schedule.insert(16, at(3)).unwrap();
+        schedule.insert(17, at(2)).unwrap();
+        schedule.insert(8, at(30)).unwrap();
+        schedule.insert(9, GameInfo::default()).unwrap();
+        let tournament = Tournament {
+            decisions: 0,
+            mask: 1 << 32,
+            vacated: 0,
+        };
+
+        let slots: Vec<u8> = schedule
+            .upcoming(&tournament, now, hours(24))
+            .iter()
+            .map(|(slot, _)| *slot)
+            .collect();
+
+        assert_eq!(slots, vec![17, 16]);
+        assert_eq!(
+            schedule.insert(64, at(1)),
+            Err(ResultError::NoGame { slot: 64 })
+        );
+        assert_eq!(
+            schedule.insert(0, at(1)),
+            Err(ResultError::NoGame { slot: 0 })
+        );
+        assert_eq!(schedule.game(64), None);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{standings_ranks, Bracket, Decisions, Score};
+
+/// Per-bracket scoring for the games still to be played, laid out so a
+/// scenario is scored by table lookups instead of rebuilding each bracket's
+/// team slots: a bracket's score is its current points plus, for every
+/// undecided game, the pick's points when the scenario's winner matches.
+#[derive(Debug, Clone)]
+pub struct ScoreDeltaTable {
+    undecided: Vec<u8>,
+    base: Vec<Score>,
+    // Row per bracket, column per undecided game: the team picked to win it
+    // and what that pick is worth.
+    picks: Vec<(Option<u8>, Score)>,
+    scoring: ScoringSystem,
+}
+
+impl ScoreDeltaTable {
+    /// `tournament_team_slots` must have no team in any `undecided` slot.
+    pub fn new(
+        brackets: &[Bracket],
+        tournament_team_slots: &[Option<u8>],
+        undecided: &[u8],
+        scoring: &ScoringSystem,
+    ) -> ScoreDeltaTable {
+        let mut base = Vec::with_capacity(brackets.len());
+        let mut picks = Vec::with_capacity(brackets.len() * undecided.len());
+        for bracket in brackets {
+            base.push(bracket.points_for_decisions(tournament_team_slots, scoring));
+            let bracket_team_slots = bracket.decision_team_slots();
+            picks.extend(undecided.iter().map(|&slot| {
+                let pick = bracket_team_slots[slot as usize];
+                (
+                    pick,
+                    pick.map_or(0, |team| scoring.points(slot, team, None)),
+                )
+            }));
+        }
+
+        ScoreDeltaTable {
+            undecided: undecided.to_vec(),
+            base,
+            picks,
+            scoring: *scoring,
+        }
+    }
+
+    pub fn for_view(view: &TournamentView, brackets: &[Bracket]) -> ScoreDeltaTable {
+        ScoreDeltaTable::for_view_with(view, brackets, &ScoringSystem::standard())
+    }
+
+    pub fn for_view_with(
+        view: &TournamentView,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+    ) -> ScoreDeltaTable {
+        ScoreDeltaTable::new(brackets, view.team_slots(), view.undecided(), scoring)
+    }
+
+    /// Points bracket `bracket` (by index) scores in the scenario whose
+    /// winners are in `team_slots`.
+    pub fn score(&self, bracket: usize, team_slots: &[Option<u8>]) -> Score {
+        (0..self.undecided.len()).fold(self.base[bracket], |acc, column| {
+            acc.saturating_add(self.game_points(bracket, column, team_slots))
+        })
+    }
+
+    /// Every bracket's rank (ties sharing the better one) in the scenario
+    /// whose winners are in `team_slots`. `scores` is scratch space, left
+    /// holding the scores the ranks came from.
+    pub fn ranks(&self, team_slots: &[Option<u8>], scores: &mut Vec<Score>) -> Vec<usize> {
+        scores.clear();
+        scores.extend((0..self.base.len()).map(|i| self.score(i, team_slots)));
+        standings_ranks(scores)
+    }
+
+    /// Moves bracket `bracket`'s score by `points` in every scenario, as for
+    /// a commissioner's adjustment. Scores stop at zero.
+    pub fn adjust(&mut self, bracket: usize, points: i64) {
+        self.base[bracket] = self.base[bracket].saturating_add_signed(points);
+    }
+
+    /// Where `slot` is among the undecided games the table was built for.
+    pub fn column(&self, slot: u8) -> Option<usize> {
+        self.undecided.iter().position(|&s| s == slot)
+    }
+
+    /// Points bracket `bracket` gets from the undecided game in `column`
+    /// once `team_slots` has its winner, so a score can be built up one
+    /// game at a time.
+    pub fn game_points(&self, bracket: usize, column: usize, team_slots: &[Option<u8>]) -> Score {
+        let slot = self.undecided[column];
+        match self.picks[bracket * self.undecided.len() + column] {
+            (Some(team), points) if team_slots[slot as usize] == Some(team) => {
+                // An upset bonus depends on who the pick beat, which only
+                // the scenario knows.
+                if self.scoring.upset_bonus {
+                    self.scoring.points_in(slot, team, team_slots)
+                } else {
+                    points
+                }
+            }
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn lookups_match_full_scoring() {
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        });
+        let brackets = [
+            Bracket { decisions: 0 },
+            Bracket { decisions: 0b1010 },
+            Bracket {
+                decisions: COMPLETE_MASK,
+            },
+        ];
+        let table = ScoreDeltaTable::for_view(&view, &brackets);
+
+        view.for_each_scenario(None, |team_slots| {
+            for (i, b) in brackets.iter().enumerate() {
+                assert_eq!(
+                    table.score(i, team_slots),
+                    b.points_for_decisions(team_slots, &ScoringSystem::standard())
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn upset_bonus_uses_the_scenario_loser() {
+        // A 6 seed (72) came out of slot 4 to play a 1 seed in slot 2.
+        let view = TournamentView::new(&Tournament {
+            decisions: 1 << 4,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        });
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 0b1110 }];
+        let scoring = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        let table = ScoreDeltaTable::for_view_with(&view, &brackets, &scoring);
+
+        view.for_each_scenario(None, |team_slots| {
+            for (i, b) in brackets.iter().enumerate() {
+                assert_eq!(
+                    table.score(i, team_slots),
+                    b.points_for_decisions(team_slots, &scoring)
+                );
+            }
+        });
+    }
+}
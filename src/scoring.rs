@@ -0,0 +1,202 @@
+use crate::format::BracketFormat;
+use crate::{Score, POINTS_PER_ROUND};
+
+/// How a correct pick is scored. A pick in round `r` is worth
+/// `round_values[r]`, plus the winner's seed with `seed_bonus`, plus how many
+/// seed lines it beat with `upset_bonus`, all times `multipliers[r]`. Arrays
+/// are indexed by round number (1..=6); index 0 is unused. Rounds and seeds
+/// come from `format`, so round 1 is the event's first round and padding
+/// games score nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringSystem {
+    pub round_values: [Score; 7],
+    pub seed_bonus: bool,
+    /// The winner's seed minus the loser's, when the winner is the worse
+    /// seed. Needs the loser, so it only applies where the game's
+    /// opponents are known.
+    pub upset_bonus: bool,
+    pub multipliers: [Score; 7],
+    pub format: BracketFormat,
+}
+
+impl ScoringSystem {
+    /// Fibonacci round values (1, 2, 3, 5, 8, 13) plus the winner's seed: the
+    /// crate's built-in scoring.
+    pub fn standard() -> ScoringSystem {
+        ScoringSystem {
+            round_values: POINTS_PER_ROUND.map(Score::from),
+            seed_bonus: true,
+            upset_bonus: false,
+            multipliers: [1; 7],
+            format: BracketFormat::standard(),
+        }
+    }
+
+    /// 1, 2, 4, 8, 16, 32 with no bonuses.
+    pub fn doubling() -> ScoringSystem {
+        ScoringSystem {
+            round_values: [0, 1, 2, 4, 8, 16, 32],
+            seed_bonus: false,
+            upset_bonus: false,
+            multipliers: [1; 7],
+            format: BracketFormat::standard(),
+        }
+    }
+
+    /// Points for correctly picking `winner` to beat `loser` in `slot`. With
+    /// no `loser` there's no upset bonus.
+    pub fn points(&self, slot: u8, winner: u8, loser: Option<u8>) -> Score {
+        let Some(round) = self.format.round(slot) else {
+            return 0;
+        };
+        let round = round as usize;
+        let seed_of = |team| Score::from(self.format.seed(team).unwrap_or(0));
+        let seed = seed_of(winner);
+        let mut points = self.round_values[round];
+        if self.seed_bonus {
+            points = points.saturating_add(seed);
+        }
+        if let (true, Some(loser)) = (self.upset_bonus, loser) {
+            points = points.saturating_add(seed.saturating_sub(seed_of(loser)));
+        }
+        points.saturating_mul(self.multipliers[round])
+    }
+
+    /// Points for correctly picking the winner of `slot` in a tournament
+    /// whose winners are in `team_slots` (as laid out by
+    /// [`crate::Decisions::decision_team_slots`], optionally followed by the
+    /// team slots themselves).
+    pub fn points_in(&self, slot: u8, winner: u8, team_slots: &[Option<u8>]) -> Score {
+        let loser = if self.upset_bonus {
+            let team_in = |child: u8| {
+                if child >= 64 {
+                    Some(child)
+                } else {
+                    team_slots[child as usize]
+                }
+            };
+            [team_in(slot * 2), team_in(slot * 2 + 1)]
+                .into_iter()
+                .flatten()
+                .find(|&team| team != winner)
+        } else {
+            None
+        };
+        self.points(slot, winner, loser)
+    }
+
+    /// Points for correctly picking a `seed` seed to win a game in the
+    /// event's `round`, before any upset bonus. Rounds outside the format
+    /// score nothing.
+    pub fn points_for(&self, round: u8, seed: u8) -> Score {
+        if !(1..=self.format.rounds()).contains(&round) {
+            return 0;
+        }
+        let round = round as usize;
+        let mut points = self.round_values[round];
+        if self.seed_bonus {
+            points = points.saturating_add(Score::from(seed));
+        }
+        points.saturating_mul(self.multipliers[round])
+    }
+
+    /// The most a single correct pick in the event's `round` can score: the
+    /// worst seed in the field winning, over the best seed with
+    /// `upset_bonus`.
+    pub fn max_points_in_round(&self, round: u8) -> Score {
+        let seeds = (0..self.format.teams())
+            .filter_map(|index| self.format.seed(self.format.team_slot(index)));
+        let (best, worst) = seeds.fold((u8::MAX, 0), |(best, worst), seed| {
+            (best.min(seed), worst.max(seed))
+        });
+        let mut points = self.points_for(round, worst);
+        if self.upset_bonus && best <= worst && (1..=self.format.rounds()).contains(&round) {
+            let spread = Score::from(worst - best).saturating_mul(self.multipliers[round as usize]);
+            points = points.saturating_add(spread);
+        }
+        points
+    }
+}
+
+impl Default for ScoringSystem {
+    fn default() -> ScoringSystem {
+        ScoringSystem::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{points_for_pick, SEED_ORDER};
+
+    #[test]
+    fn presets_and_bonuses() {
+        let standard = ScoringSystem::standard();
+        for slot in 1..=63u8 {
+            assert_eq!(standard.points(slot, 65, None), points_for_pick(slot, 65));
+        }
+
+        let doubling = ScoringSystem::doubling();
+        assert_eq!(doubling.points(32, 65, Some(64)), 1);
+        assert_eq!(doubling.points(1, 64, None), 32);
+
+        // 16 seed (65) over 1 seed (64) in the first round.
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            multipliers: [0, 1, 1, 1, 1, 1, 2],
+            ..doubling
+        };
+        assert_eq!(upsets.points(32, 65, Some(64)), 1 + 15);
+        assert_eq!(upsets.points(32, 64, Some(65)), 1);
+        assert_eq!(upsets.points_in(32, 65, &[None; 64]), 1 + 15);
+        assert_eq!(upsets.points(1, 64, None), 64);
+    }
+
+    #[test]
+    fn formats_set_rounds_and_seeds() {
+        // An eight-team event: slot 4 is its first round, slot 8 padding.
+        let format = BracketFormat::single_region(3).unwrap();
+        let scoring = ScoringSystem {
+            format,
+            ..ScoringSystem::standard()
+        };
+        // The 8 seed (72) over the 1 seed (64).
+        assert_eq!(scoring.points(4, 72, Some(64)), 1 + 8);
+        assert_eq!(scoring.points(1, 64, None), 3 + 1);
+        assert_eq!(scoring.points(8, 64, None), 0);
+
+        let mut order = SEED_ORDER;
+        order.swap(0, 1);
+        let swapped = ScoringSystem {
+            format: BracketFormat::new(6, vec![order.to_vec(); 4]).unwrap(),
+            ..ScoringSystem::standard()
+        };
+        assert_eq!(swapped.points(32, 64, None), 1 + 16);
+        assert_eq!(swapped.points(32, 65, None), 1 + 1);
+    }
+
+    #[test]
+    fn round_probes() {
+        let standard = ScoringSystem::standard();
+        assert_eq!(standard.points_for(1, 1), crate::points_for(1, 1));
+        assert_eq!(standard.points_for(6, 2), crate::points_for(6, 2));
+        assert_eq!(standard.points_for(0, 1), 0);
+        assert_eq!(standard.max_points_in_round(3), 19);
+
+        let upsets = ScoringSystem {
+            upset_bonus: true,
+            ..ScoringSystem::doubling()
+        };
+        assert_eq!(upsets.points_for(6, 16), 32);
+        assert_eq!(upsets.max_points_in_round(1), 1 + 15);
+        assert_eq!(upsets.max_points_in_round(6), 32 + 15);
+
+        // An eight-team event has three rounds, seeded 1..=8.
+        let eight = ScoringSystem {
+            format: BracketFormat::single_region(3).unwrap(),
+            ..ScoringSystem::standard()
+        };
+        assert_eq!(eight.max_points_in_round(1), 1 + 8);
+        assert_eq!(eight.points_for(4, 1), 0);
+    }
+}
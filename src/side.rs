@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::pool::{Entry, Pool};
+use crate::view::TournamentView;
+use crate::{round_name, seed_for_slot, standings_ranks, Decisions, ROUNDS};
+
+/// A contest run on the side of a pool's main standings, from the same
+/// entries. Each entry gets a mark, and the best mark wins.
+pub trait SideContest {
+    fn name(&self) -> &str;
+
+    /// `entry`'s mark so far, or `None` to leave it out of the contest.
+    fn mark(&self, entry: &Entry, pool: &Pool) -> Option<u64>;
+
+    /// Whether the lowest mark wins instead of the highest.
+    fn lowest_wins(&self) -> bool {
+        false
+    }
+}
+
+/// One row of a side contest's standings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideStanding {
+    pub owner: String,
+    pub mark: u64,
+    /// Zero-based, ties sharing the better place.
+    pub rank: usize,
+}
+
+/// Upsets picked correctly: decided games won by the worse seed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UpsetsCalled;
+
+impl SideContest for UpsetsCalled {
+    fn name(&self) -> &str {
+        "Upsets called"
+    }
+
+    fn mark(&self, entry: &Entry, pool: &Pool) -> Option<u64> {
+        let view = TournamentView::new(pool.tournament());
+        let winners = view.team_slots();
+        let picks = entry.bracket.decision_team_slots();
+        let called = (1..64usize)
+            .filter(|&slot| {
+                let (Some(winner), Some(pick)) = (winners[slot], picks[slot]) else {
+                    return false;
+                };
+                let loser = if winners[slot * 2] == Some(winner) {
+                    winners[slot * 2 + 1]
+                } else {
+                    winners[slot * 2]
+                };
+                pick == winner
+                    && loser.is_some_and(|loser| seed_for_slot(winner) > seed_for_slot(loser))
+            })
+            .count();
+        Some(called as u64)
+    }
+}
+
+/// Most points in one round, under the pool's scoring.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundScore(u8);
+
+impl RoundScore {
+    /// The contest for `round`, or `None` unless it's 1..=6.
+    pub fn new(round: u8) -> Option<RoundScore> {
+        (1..=ROUNDS).contains(&round).then_some(RoundScore(round))
+    }
+
+    pub fn round(&self) -> u8 {
+        self.0
+    }
+}
+
+impl SideContest for RoundScore {
+    fn name(&self) -> &str {
+        round_name(self.0)
+    }
+
+    fn mark(&self, entry: &Entry, pool: &Pool) -> Option<u64> {
+        let breakdown = entry
+            .bracket
+            .score_breakdown(pool.tournament(), pool.scoring());
+        Some(breakdown.rounds[self.0 as usize])
+    }
+}
+
+/// Closest guess at a number, usually the title game's total points.
+/// Entries without a guess are left out.
+#[derive(Debug, Clone, Default)]
+pub struct Tiebreaker {
+    pub guesses: HashMap<String, u32>,
+    pub actual: u32,
+}
+
+impl SideContest for Tiebreaker {
+    fn name(&self) -> &str {
+        "Tiebreaker"
+    }
+
+    fn mark(&self, entry: &Entry, _: &Pool) -> Option<u64> {
+        let guess = self.guesses.get(&entry.owner)?;
+        Some(u64::from(guess.abs_diff(self.actual)))
+    }
+
+    fn lowest_wins(&self) -> bool {
+        true
+    }
+}
+
+impl Pool {
+    /// Standings in `contest`, best first; tied entries keep the order they
+    /// were added in.
+    pub fn side_standings(&self, contest: &dyn SideContest) -> Vec<SideStanding> {
+        let marked: Vec<(&Entry, u64)> = self
+            .active_entries()
+            .into_iter()
+            .filter_map(|entry| Some((entry, contest.mark(entry, self)?)))
+            .collect();
+        let keys: Vec<u64> = marked
+            .iter()
+            .map(|&(_, mark)| {
+                if contest.lowest_wins() {
+                    u64::MAX - mark
+                } else {
+                    mark
+                }
+            })
+            .collect();
+        let mut standings: Vec<SideStanding> = marked
+            .iter()
+            .zip(standings_ranks(&keys))
+            .map(|(&(entry, mark), rank)| SideStanding {
+                owner: entry.owner.clone(),
+                mark,
+                rank,
+            })
+            .collect();
+        standings.sort_by_key(|s| s.rank);
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ScoringSystem;
+    use crate::{round_slot_mask, Bracket, Tournament};
+
+    #[test]
+    fn ranks_side_contests() {
+        // The first round went chalk except game 33, where the 9 seed won.
+        let tournament = Tournament::new(1 << 33, round_slot_mask(1));
+        let mut pool = Pool::new(tournament, ScoringSystem::standard());
+        pool.insert("ann", Bracket::from_decisions(0));
+        pool.insert("bob", Bracket::from_decisions(1 << 33));
+        pool.insert("cat", Bracket::from_decisions(1 << 33 | 1 << 34));
+
+        let upsets = pool.side_standings(&UpsetsCalled);
+        let marks: Vec<(&str, u64, usize)> = upsets
+            .iter()
+            .map(|s| (s.owner.as_str(), s.mark, s.rank))
+            .collect();
+        assert_eq!(marks, vec![("bob", 1, 0), ("cat", 1, 0), ("ann", 0, 2)]);
+
+        let first_round = pool.side_standings(&RoundScore::new(1).unwrap());
+        assert_eq!(first_round[0].owner, "bob");
+        assert_eq!(first_round[1].rank, 1);
+        assert!(RoundScore::new(0).is_none());
+        assert!(RoundScore::new(7).is_none());
+
+        let tiebreaker = Tiebreaker {
+            guesses: [("ann", 140), ("cat", 151)]
+                .iter()
+                .map(|&(owner, guess)| (owner.to_string(), guess))
+                .collect(),
+            actual: 148,
+        };
+        let closest = pool.side_standings(&tiebreaker);
+        assert_eq!(closest.len(), 2);
+        assert_eq!((closest[0].owner.as_str(), closest[0].mark), ("cat", 3));
+        assert_eq!(tiebreaker.name(), "Tiebreaker");
+    }
+}
@@ -0,0 +1,227 @@
+use std::io::{self, Write};
+
+use crate::paths::PathsToVictory;
+use crate::rng::Rng;
+use crate::score_delta::ScoreDeltaTable;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Score};
+
+/// Win probabilities for matchups, e.g. from a ratings model.
+pub trait WinProbability {
+    /// The chance `team` beats `opponent` (both team slots, 64..=127).
+    fn win_probability(&self, team: u8, opponent: u8) -> f64;
+}
+
+impl<F: Fn(u8, u8) -> f64> WinProbability for F {
+    fn win_probability(&self, team: u8, opponent: u8) -> f64 {
+        self(team, opponent)
+    }
+}
+
+impl TournamentView {
+    /// Plays out the undecided games `samples` times with winners drawn from
+    /// `model`, counting [`PathsToVictory`] per bracket over the samples
+    /// instead of over every scenario. The same `seed` gives the same
+    /// counts, which turn into probabilities with [`PathsToVictory::share`].
+    pub fn simulate<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+        samples: u64,
+        paid_places: usize,
+        seed: u64,
+    ) -> Vec<PathsToVictory> {
+        self.simulate_with(
+            brackets,
+            model,
+            samples,
+            paid_places,
+            seed,
+            &ScoringSystem::standard(),
+        )
+    }
+
+    /// [`TournamentView::simulate`] under `scoring`.
+    pub fn simulate_with<M: WinProbability + ?Sized>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+        samples: u64,
+        paid_places: usize,
+        seed: u64,
+        scoring: &ScoringSystem,
+    ) -> Vec<PathsToVictory> {
+        let deltas = ScoreDeltaTable::for_view_with(self, brackets, scoring);
+        let mut rng = Rng::new(seed);
+        let mut paths = vec![PathsToVictory::default(); brackets.len()];
+        let mut team_slots = self.team_slots().to_vec();
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+
+        for _ in 0..samples {
+            self.play_out(model, &mut rng, &mut team_slots);
+            for (path, rank) in paths.iter_mut().zip(deltas.ranks(&team_slots, &mut scores)) {
+                path.wins += (rank == 0) as u64;
+                path.top_three += (rank < 3) as u64;
+                path.cashes += (rank < paid_places) as u64;
+            }
+        }
+        paths
+    }
+
+    /// Writes every sample of [`TournamentView::simulate_with`] to `out` as
+    /// CSV in long form, one row per sample and bracket:
+    /// `scenario,entry,score,rank` with zero-based sample numbers, bracket
+    /// indices and ranks (ties sharing the better one). Columns load
+    /// straight into a data frame. The same `seed` draws the same samples
+    /// as `simulate_with`. Rows are written as they're made, so buffer
+    /// `out` for big runs.
+    pub fn write_simulation_csv<M: WinProbability + ?Sized, W: Write>(
+        &self,
+        brackets: &[Bracket],
+        model: &M,
+        samples: u64,
+        seed: u64,
+        scoring: &ScoringSystem,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let deltas = ScoreDeltaTable::for_view_with(self, brackets, scoring);
+        let mut rng = Rng::new(seed);
+        let mut team_slots = self.team_slots().to_vec();
+        let mut scores: Vec<Score> = Vec::with_capacity(brackets.len());
+
+        writeln!(out, "scenario,entry,score,rank")?;
+        for scenario in 0..samples {
+            self.play_out(model, &mut rng, &mut team_slots);
+            let ranks = deltas.ranks(&team_slots, &mut scores);
+            for (entry, (score, rank)) in scores.iter().zip(ranks).enumerate() {
+                writeln!(out, "{},{},{},{}", scenario, entry, score, rank)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills every undecided game of `team_slots`, which starts as this
+    /// view's, with a winner drawn from `model`.
+    pub(crate) fn play_out<M: WinProbability + ?Sized>(
+        &self,
+        model: &M,
+        rng: &mut Rng,
+        team_slots: &mut [Option<u8>],
+    ) {
+        play(self.undecided(), model, rng, team_slots);
+    }
+}
+
+/// Fills `games`, listed children before parents, with winners drawn from
+/// `model`.
+pub(crate) fn play<M: WinProbability + ?Sized>(
+    games: &[u8],
+    model: &M,
+    rng: &mut Rng,
+    team_slots: &mut [Option<u8>],
+) {
+    // Children come before parents, so both sides are known.
+    for &slot in games {
+        let idx = slot as usize;
+        team_slots[idx] = match (team_slots[idx * 2], team_slots[idx * 2 + 1]) {
+            (Some(a), Some(b)) => {
+                if rng.next_f64() < model.win_probability(a, b) {
+                    Some(a)
+                } else {
+                    Some(b)
+                }
+            }
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    fn final_four() -> TournamentView {
+        TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !0b1110))
+    }
+
+    #[test]
+    fn certain_outcomes_are_deterministic() {
+        let view = final_four();
+        let brackets = [Bracket::from_decisions(0), Bracket::from_decisions(1 << 1)];
+        // The lower team slot always wins, which is the first bracket's
+        // title pick.
+        let favorites = |team: u8, opponent: u8| if team < opponent { 1.0 } else { 0.0 };
+
+        let paths = view.simulate(&brackets, &favorites, 100, 1, 7);
+        assert_eq!(paths[0].wins, 100);
+        assert_eq!(paths[1].wins, 0);
+        assert_eq!(paths[1].top_three, 100);
+
+        // Scoring only the first round, which is over, ties them every time.
+        let first_round = ScoringSystem {
+            multipliers: [0, 1, 0, 0, 0, 0, 0],
+            ..ScoringSystem::standard()
+        };
+        let paths = view.simulate_with(&brackets, &favorites, 100, 1, 7, &first_round);
+        assert_eq!(paths[1].wins, 100);
+    }
+
+    #[test]
+    fn coin_flips_approach_the_exhaustive_shares() {
+        let view = final_four();
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+        let (exact, scenarios) = view.paths_to_victory(&brackets, 1);
+
+        let samples = 20_000;
+        let simulated = view.simulate(&brackets, &|_, _| 0.5, samples, 1, 11);
+        for (sim, exact) in simulated.iter().zip(&exact) {
+            let expected = PathsToVictory::share(exact.wins, scenarios);
+            let estimate = PathsToVictory::share(sim.wins, samples);
+            assert!((expected - estimate).abs() < 0.02);
+        }
+        assert_eq!(
+            simulated,
+            view.simulate(&brackets, &|_, _| 0.5, samples, 1, 11)
+        );
+    }
+
+    #[test]
+    fn exports_every_sample() {
+        let view = final_four();
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            Bracket::from_decisions(1 << 2),
+        ];
+        let scoring = ScoringSystem::standard();
+        let mut csv = vec![];
+        view.write_simulation_csv(&brackets, &|_, _| 0.5, 50, 3, &scoring, &mut csv)
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("scenario,entry,score,rank"));
+
+        let rows: Vec<Vec<u64>> = lines
+            .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(rows.len(), 50 * 3);
+        assert_eq!(rows[4][..2], [1, 1]);
+        // The same draws as the summary, counted back from the ranks.
+        let paths = view.simulate_with(&brackets, &|_, _| 0.5, 50, 1, 3, &scoring);
+        for (entry, path) in paths.iter().enumerate() {
+            let wins = rows
+                .iter()
+                .filter(|row| row[1] == entry as u64 && row[3] == 0)
+                .count();
+            assert_eq!(wins as u64, path.wins);
+        }
+        let base = view.score(&brackets[0]);
+        assert!(rows.iter().all(|row| row[1] != 0 || row[2] >= base));
+    }
+}
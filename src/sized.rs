@@ -0,0 +1,287 @@
+use crate::format::BracketFormat;
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Score, Tournament, ROUNDS};
+
+/// Slot math for a bracket of `TEAMS` teams: 2, 4, 8, 16, 32 or 64.
+///
+/// The numbering is the 64-team one cut down: the title game is slot 1,
+/// games are slots `1..TEAMS` and the teams sit in slots `TEAMS..2 * TEAMS`.
+/// Those are the top slots of the full tree, so a smaller bracket carries
+/// over to it as the top of a [`BracketFormat`] with the same number of
+/// rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TournamentSize<const TEAMS: usize>;
+
+impl<const TEAMS: usize> TournamentSize<TEAMS> {
+    const VALID: () = assert!(
+        TEAMS.is_power_of_two() && TEAMS >= 2 && TEAMS <= 64,
+        "a bracket holds 2, 4, 8, 16, 32 or 64 teams"
+    );
+
+    pub const ROUNDS: u8 = {
+        let () = Self::VALID;
+        TEAMS.trailing_zeros() as u8
+    };
+
+    /// Every game slot decided, bit 0 reserved as in the full tree.
+    pub const COMPLETE_MASK: u64 = {
+        let () = Self::VALID;
+        ((1u128 << TEAMS) - 2) as u64
+    };
+
+    /// The round (1 = first, [`TournamentSize::ROUNDS`] = title game) of the
+    /// game in `slot`.
+    pub fn round_for_slot(slot: u8) -> u8 {
+        assert!(
+            (1..TEAMS).contains(&(slot as usize)),
+            "no game in slot {}",
+            slot
+        );
+        Self::ROUNDS + 1 - (u8::BITS - slot.leading_zeros()) as u8
+    }
+
+    /// Bits of the game slots played in `round`.
+    pub fn round_slot_mask(round: u8) -> u64 {
+        assert!((1..=Self::ROUNDS).contains(&round), "no round {}", round);
+        let lo = 1u64 << (Self::ROUNDS - round);
+        ((1u64 << lo) - 1) << lo
+    }
+
+    /// The draw: the NCAA's for 64 teams, one region seeded the same way
+    /// otherwise.
+    pub fn format() -> BracketFormat {
+        if TEAMS == 64 {
+            BracketFormat::standard()
+        } else {
+            // Every size the assertion allows is a valid region.
+            BracketFormat::single_region(Self::ROUNDS).unwrap()
+        }
+    }
+
+    /// The full tree's team slot (64..=127) for `team` (`TEAMS..2 * TEAMS`).
+    pub fn full_team_slot(team: u8) -> u8 {
+        assert!(
+            (TEAMS..2 * TEAMS).contains(&(team as usize)),
+            "no team slot {}",
+            team
+        );
+        team << (ROUNDS - Self::ROUNDS)
+    }
+
+    /// Standard scoring over this size's rounds and seeds, for scoring
+    /// [`SizedBracket::bracket`] in the full tree. Change its round values
+    /// or bonuses with struct update syntax; keep its format.
+    pub fn scoring() -> ScoringSystem {
+        ScoringSystem {
+            format: Self::format(),
+            ..ScoringSystem::standard()
+        }
+    }
+
+    pub fn seed(team: u8) -> u8 {
+        Self::format()
+            .seed(Self::full_team_slot(team))
+            .expect("every team slot has a seed")
+    }
+
+    /// Points for correctly picking `team` to win the game in `slot`, under
+    /// the standard points per round from this size's first round.
+    pub fn points_for_pick(slot: u8, team: u8) -> Score {
+        Self::scoring().points(slot, Self::full_team_slot(team), None)
+    }
+}
+
+/// Results so far in a `TEAMS`-team tournament.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SizedTournament<const TEAMS: usize> {
+    decisions: u64,
+    mask: u64,
+}
+
+impl<const TEAMS: usize> SizedTournament<TEAMS> {
+    /// Bits outside [`TournamentSize::COMPLETE_MASK`] are ignored.
+    pub fn new(decisions: u64, mask: u64) -> SizedTournament<TEAMS> {
+        let mask = mask & TournamentSize::<TEAMS>::COMPLETE_MASK;
+        SizedTournament {
+            decisions: decisions & mask,
+            mask,
+        }
+    }
+
+    pub fn decisions(&self) -> u64 {
+        self.decisions
+    }
+
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    pub fn decision(&self, slot: u8) -> Option<u8> {
+        let position = 1u64 << slot;
+        (self.mask & position != 0).then_some((self.decisions & position != 0) as u8)
+    }
+
+    /// Records the winner of the game in `slot` (`1..TEAMS`) as in
+    /// [`crate::Decisions::set_decision`].
+    pub fn set_decision(&mut self, slot: u8, winner: u8) {
+        let position = checked_bit::<TEAMS>(slot, winner);
+        self.decisions = (self.decisions & !position) | (position * winner as u64);
+        self.mask |= position;
+    }
+
+    /// The same results in the full tree, with the games below this size's
+    /// first round decided as padding, for the crate's views and searches.
+    pub fn tournament(&self) -> Tournament {
+        let mut tournament = Tournament::new(self.decisions, self.mask);
+        TournamentSize::<TEAMS>::format().apply(&mut tournament);
+        tournament
+    }
+}
+
+/// One entry's picks for every game of a `TEAMS`-team bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SizedBracket<const TEAMS: usize> {
+    decisions: u64,
+}
+
+impl<const TEAMS: usize> SizedBracket<TEAMS> {
+    /// Bits outside [`TournamentSize::COMPLETE_MASK`] are ignored.
+    pub fn from_decisions(decisions: u64) -> SizedBracket<TEAMS> {
+        SizedBracket {
+            decisions: decisions & TournamentSize::<TEAMS>::COMPLETE_MASK,
+        }
+    }
+
+    pub fn decisions(&self) -> u64 {
+        self.decisions
+    }
+
+    pub fn decision(&self, slot: u8) -> u8 {
+        checked_bit::<TEAMS>(slot, 0);
+        (self.decisions >> slot & 1) as u8
+    }
+
+    pub fn set_decision(&mut self, slot: u8, winner: u8) {
+        let position = checked_bit::<TEAMS>(slot, winner);
+        self.decisions = (self.decisions & !position) | (position * winner as u64);
+    }
+
+    /// The same picks in the full tree, to be scored with
+    /// [`TournamentSize::scoring`]: under the 64-team seeds and rounds they'd
+    /// get the wrong points. Padding is picked the way it's decided and
+    /// scores nothing.
+    pub fn bracket(&self) -> Bracket {
+        Bracket::from_decisions(self.decisions)
+    }
+
+    /// Points scored against `tournament` so far.
+    pub fn score(&self, tournament: &SizedTournament<TEAMS>) -> Score {
+        self.score_with(tournament, &TournamentSize::<TEAMS>::scoring())
+    }
+
+    /// Points scored against `tournament` so far under `scoring`, which
+    /// should carry this size's format.
+    pub fn score_with(
+        &self,
+        tournament: &SizedTournament<TEAMS>,
+        scoring: &ScoringSystem,
+    ) -> Score {
+        TournamentView::new(&tournament.tournament()).score_with(&self.bracket(), scoring)
+    }
+}
+
+fn checked_bit<const TEAMS: usize>(slot: u8, winner: u8) -> u64 {
+    assert!(
+        (1..TEAMS).contains(&(slot as usize)),
+        "no game in slot {}",
+        slot
+    );
+    assert!(winner <= 1, "winner must be 0 or 1, got {}", winner);
+    1u64 << slot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{points_for_pick, round_num_for_slot, BestFinishes, Decisions, COMPLETE_MASK};
+
+    type Sixteen = TournamentSize<16>;
+
+    #[test]
+    fn sizes_cut_the_full_tree_down() {
+        assert_eq!(Sixteen::ROUNDS, 4);
+        assert_eq!(Sixteen::COMPLETE_MASK, 0xFFFE);
+        assert_eq!(TournamentSize::<64>::COMPLETE_MASK, COMPLETE_MASK);
+        assert_eq!(TournamentSize::<8>::round_slot_mask(1), 0xF0);
+        assert_eq!(Sixteen::round_for_slot(8), 1);
+        assert_eq!(Sixteen::round_for_slot(1), 4);
+        assert_eq!(Sixteen::seed(16), 1);
+        assert_eq!(Sixteen::seed(17), 16);
+        assert_eq!(Sixteen::full_team_slot(17), 68);
+        assert_eq!(Sixteen::points_for_pick(8, 17), 1 + 16);
+        for slot in 1..64 {
+            assert_eq!(
+                TournamentSize::<64>::round_for_slot(slot),
+                round_num_for_slot(slot)
+            );
+            assert_eq!(
+                TournamentSize::<64>::points_for_pick(slot, 64 + slot),
+                points_for_pick(slot, 64 + slot)
+            );
+        }
+    }
+
+    #[test]
+    fn conference_pool_runs_through_the_full_tree() {
+        // The first round is in, chalk but for the 16 seed's upset.
+        let mut tournament = SizedTournament::<16>::new(0, Sixteen::round_slot_mask(1));
+        tournament.set_decision(8, 1);
+        assert_eq!(tournament.decision(8), Some(1));
+        assert_eq!(tournament.decision(4), None);
+
+        let chalk = SizedBracket::<16>::from_decisions(0);
+        let mut upset = chalk;
+        upset.set_decision(8, 1);
+        assert_eq!(
+            upset.score(&tournament),
+            chalk.score(&tournament) + Sixteen::points_for_pick(8, 17)
+        );
+
+        let full = tournament.tournament();
+        let view = TournamentView::new(&full);
+        assert_eq!(view.undecided(), &[7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(full.decision_team_slots()[8], Some(68));
+        let brackets = [chalk.bracket(), upset.bracket()];
+        let finishes =
+            BestFinishes::for_view_with(&brackets, &view, &Sixteen::scoring(), 5).possible_finishes;
+        assert_eq!(finishes[&brackets[1]], 0);
+    }
+
+    #[test]
+    fn seeds_come_from_the_size_not_the_full_tree() {
+        // The 16 seed (17) beat the 1 seed in slot 8 and the 8 seed (18) the
+        // 9 in slot 9; every later game went to the top side.
+        let mut tournament = SizedTournament::<16>::new(0, Sixteen::COMPLETE_MASK);
+        tournament.set_decision(8, 1);
+        // Both entries take the bottom side in slots 4 and 2 and miss every
+        // later game. Only `upset` calls slot 8; only `chalk` calls slot 9.
+        let chalk = SizedBracket::<16>::from_decisions(0b10100);
+        let upset = SizedBracket::<16>::from_decisions(0b11_0001_0100);
+        assert_eq!(
+            upset.score(&tournament),
+            chalk.score(&tournament) + Sixteen::points_for_pick(8, 17)
+                - Sixteen::points_for_pick(9, 18)
+        );
+
+        let view = TournamentView::new(&tournament.tournament());
+        let brackets = [chalk.bracket(), upset.bracket()];
+        let finishes =
+            BestFinishes::for_view_with(&brackets, &view, &Sixteen::scoring(), 2).possible_finishes;
+        assert_eq!((finishes[&brackets[0]], finishes[&brackets[1]]), (1, 0));
+        // In the full tree's seeds 17 and 18 sit in a 5 and a 6 seed's
+        // slots, which would put `chalk` ahead.
+        let full_tree = BestFinishes::for_view_places(&brackets, &view, 2).possible_finishes;
+        assert_eq!((full_tree[&brackets[0]], full_tree[&brackets[1]]), (0, 1));
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{
+    checked_decision_bit, points_for_team_slots, round_slot_mask, BestFinishes, Bracket, Decisions,
+    Score, Tournament, COMPLETE_MASK,
+};
+
+/// The round a bracket's picks start from. Second-chance pools start a
+/// fresh bracket once the field is down to sixteen or eight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BracketStage {
+    #[default]
+    Full,
+    Sweet16,
+    EliteEight,
+}
+
+impl BracketStage {
+    /// The first round (1..=6) picked at this stage.
+    pub fn first_round(self) -> u8 {
+        match self {
+            BracketStage::Full => 1,
+            BracketStage::Sweet16 => 3,
+            BracketStage::EliteEight => 4,
+        }
+    }
+
+    /// Bits of the game slots picked at this stage.
+    pub fn mask(self) -> u64 {
+        (self.first_round()..=6).fold(0, |mask, round| mask | round_slot_mask(round))
+    }
+}
+
+/// A bracket filled in at `stage`: picks for the games from then on, with
+/// the games before it taken as they went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StagedBracket {
+    stage: BracketStage,
+    decisions: u64,
+}
+
+impl Decisions for StagedBracket {
+    fn decisions(&self) -> u64 {
+        self.decisions
+    }
+
+    fn mask(&self) -> u64 {
+        self.stage.mask()
+    }
+
+    /// Panics on a slot before the bracket's stage.
+    fn set_decision(&mut self, slot: u8, winner: u8) {
+        let position = checked_decision_bit(slot, winner);
+        assert!(
+            self.stage.mask() & position != 0,
+            "slot {} is before the bracket's stage",
+            slot
+        );
+        self.decisions = (self.decisions & !position) | (position * winner as u64);
+    }
+}
+
+impl StagedBracket {
+    /// Bits outside the stage are ignored.
+    pub fn new(stage: BracketStage, decisions: u64) -> StagedBracket {
+        StagedBracket {
+            stage,
+            decisions: decisions & stage.mask(),
+        }
+    }
+
+    pub fn stage(&self) -> BracketStage {
+        self.stage
+    }
+
+    /// The full bracket the picks make once every game before the stage
+    /// is decided: those games as they went, then the picks.
+    pub fn full_bracket(&self, tournament: &Tournament) -> Option<Bracket> {
+        let before = COMPLETE_MASK & !self.stage.mask();
+        if tournament.mask() & before != before {
+            return None;
+        }
+        Some(Bracket::from_decisions(
+            (tournament.decisions() & before) | self.decisions,
+        ))
+    }
+
+    /// The team picked to win each game from the stage on, with the games
+    /// before it left empty. `None` until those games are decided.
+    pub fn picked_team_slots(&self, tournament: &Tournament) -> Option<[Option<u8>; 64]> {
+        let mut team_slots = self.full_bracket(tournament)?.decision_team_slots();
+        for (slot, team) in team_slots.iter_mut().enumerate() {
+            if self.stage.mask() & (1 << slot) == 0 {
+                *team = None;
+            }
+        }
+        Some(team_slots)
+    }
+
+    /// Points from the games picked at this stage. Nothing until the games
+    /// before it are decided.
+    pub fn score_with(&self, tournament: &Tournament, scoring: &ScoringSystem) -> Score {
+        let Some(picks) = self.picked_team_slots(tournament) else {
+            return 0;
+        };
+        let view = TournamentView::new(tournament);
+        points_for_team_slots(view.team_slots(), &picks, scoring)
+    }
+}
+
+impl Tournament {
+    /// Best finishes in the top `places` for a pool of brackets all filled
+    /// in at `stage`, or `None` until every game before it is decided. Full
+    /// and second-chance pools are computed separately, each with its own
+    /// call. Each bracket is extended with the games before the stage as
+    /// they went, which adds the same points to everyone and so leaves the
+    /// finishes as they would be scoring the stage alone.
+    pub fn staged_best_finishes(
+        &self,
+        stage: BracketStage,
+        brackets: &[StagedBracket],
+        places: usize,
+    ) -> Option<HashMap<StagedBracket, usize>> {
+        assert!(
+            brackets.iter().all(|b| b.stage == stage),
+            "brackets from another stage"
+        );
+        let full: Vec<Bracket> = brackets
+            .iter()
+            .map(|b| b.full_bracket(self))
+            .collect::<Option<_>>()?;
+        let view = TournamentView::new(self);
+        let best_finishes = BestFinishes::for_view_places(&full, &view, places);
+        Some(
+            brackets
+                .iter()
+                .zip(&full)
+                .filter_map(|(staged, b)| {
+                    let rank = best_finishes.possible_finishes.get(b)?;
+                    Some((*staged, *rank))
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_chance_brackets_score_from_their_stage() {
+        let stage = BracketStage::Sweet16;
+        assert_eq!(stage.mask(), 0xFFFE);
+        // Two rounds played, all to the top team.
+        let tournament = Tournament::new(0, round_slot_mask(1) | round_slot_mask(2));
+        let chalk = StagedBracket::new(stage, 1 << 40);
+        assert_eq!(chalk.decision(40), None);
+
+        let picks = chalk.picked_team_slots(&tournament).unwrap();
+        assert_eq!(picks[8], Some(64));
+        assert_eq!(picks[16], None);
+        assert_eq!(picks[1], Some(64));
+        assert_eq!(chalk.score_with(&tournament, &ScoringSystem::standard()), 0);
+        assert_eq!(chalk.full_bracket(&Tournament::new(0, 0)), None);
+
+        // The first Sweet 16 game goes to the top team as well.
+        let mut tournament = tournament;
+        tournament.set_decision(8, 0);
+        assert_eq!(
+            chalk.score_with(&tournament, &ScoringSystem::standard()),
+            crate::points_for_pick(8, 64)
+        );
+
+        let other = StagedBracket::new(stage, 1 << 8);
+        let finishes = tournament
+            .staged_best_finishes(stage, &[chalk, other], 5)
+            .unwrap();
+        assert_eq!(finishes[&chalk], 0);
+        // Other missed the first game and everything after it goes through
+        // a team that's out, so it can't catch chalk.
+        assert_eq!(finishes[&other], 1);
+        assert_eq!(
+            Tournament::new(0, 0).staged_best_finishes(stage, &[chalk], 1),
+            None
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::contest::{for_each_ranking, BracketContest};
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::Bracket;
+
+/// Where a bracket stands against the paid places over every remaining
+/// scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Finishes no worse than this zero-based place however the rest of the
+    /// tournament goes, and that place pays.
+    Clinched(usize),
+    /// Finishes in the money in some scenarios but not all.
+    Alive,
+    /// Out of the money in every scenario.
+    Eliminated,
+}
+
+/// Best and worst finish of every bracket in a pool, from one walk over the
+/// remaining scenarios.
+#[derive(Debug, Clone)]
+pub struct PoolAnalysis {
+    paid_places: usize,
+    finishes: HashMap<Bracket, (usize, usize)>,
+}
+
+impl PoolAnalysis {
+    /// Brackets are scored under `scoring`, as the pool scores them.
+    pub fn new(
+        view: &TournamentView,
+        brackets: &[Bracket],
+        scoring: &ScoringSystem,
+        paid_places: usize,
+    ) -> PoolAnalysis {
+        let contest = BracketContest::new(view, brackets, scoring);
+        let mut range = vec![(usize::MAX, 0); brackets.len()];
+        for_each_ranking(&contest, |ranks| {
+            for ((best, worst), &rank) in range.iter_mut().zip(ranks) {
+                *best = rank.min(*best);
+                *worst = rank.max(*worst);
+            }
+        });
+
+        PoolAnalysis {
+            paid_places,
+            finishes: brackets.iter().copied().zip(range).collect(),
+        }
+    }
+
+    /// `None` for a bracket that isn't in the pool.
+    pub fn status(&self, bracket: &Bracket) -> Option<Status> {
+        let &(best, worst) = self.finishes.get(bracket)?;
+        Some(if worst < self.paid_places {
+            Status::Clinched(worst)
+        } else if best < self.paid_places {
+            Status::Alive
+        } else {
+            Status::Eliminated
+        })
+    }
+
+    /// The best and worst zero-based finish of a bracket in the pool.
+    pub fn finish_range(&self, bracket: &Bracket) -> Option<(usize, usize)> {
+        self.finishes.get(bracket).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tournament, COMPLETE_MASK};
+
+    #[test]
+    fn sorts_brackets_by_what_is_still_possible() {
+        // Only the title game is left: team 64 against team 96.
+        let view = TournamentView::new(&Tournament::new(0, COMPLETE_MASK & !(1 << 1)));
+        let brackets = [
+            Bracket::from_decisions(0),
+            Bracket::from_decisions(1 << 1),
+            // Wrong in the first round and with a dead champion.
+            Bracket::from_decisions(0xFFFF_FFFF_0000_0006),
+        ];
+
+        let standard = ScoringSystem::standard();
+        let analysis = PoolAnalysis::new(&view, &brackets, &standard, 2);
+        assert_eq!(analysis.status(&brackets[0]), Some(Status::Clinched(1)));
+        assert_eq!(analysis.status(&brackets[1]), Some(Status::Clinched(1)));
+        assert_eq!(analysis.status(&brackets[2]), Some(Status::Eliminated));
+        assert_eq!(analysis.finish_range(&brackets[0]), Some((0, 1)));
+
+        let winner_only = PoolAnalysis::new(&view, &brackets, &standard, 1);
+        assert_eq!(winner_only.status(&brackets[0]), Some(Status::Alive));
+        assert_eq!(winner_only.status(&Bracket::from_decisions(1 << 40)), None);
+
+        // With only the title game counting, the busted bracket ties the
+        // one that missed it.
+        let title_only = ScoringSystem {
+            multipliers: [0, 0, 0, 0, 0, 0, 1],
+            ..standard
+        };
+        let analysis = PoolAnalysis::new(&view, &brackets, &title_only, 2);
+        assert_eq!(analysis.finish_range(&brackets[2]), Some((1, 1)));
+        assert_eq!(analysis.status(&brackets[2]), Some(Status::Clinched(1)));
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+
+use crate::{Bracket, Decisions, Score, Tournament};
+
+/// How a pool charges for changing picks after the tournament starts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SwapPenalty {
+    /// The same deduction however many picks change.
+    Flat(Score),
+    /// A deduction for every pick that ends up on a different team.
+    PerPick(Score),
+}
+
+/// A validated change of picks, kept as the log entry for the swap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LateSwap {
+    pub from: Bracket,
+    pub to: Bracket,
+    /// Slots whose picked team changed, latest round last.
+    pub changed: Vec<u8>,
+    pub penalty: Score,
+}
+
+impl LateSwap {
+    /// `score` with this swap's penalty taken off, never going below zero.
+    pub fn apply(&self, score: Score) -> Score {
+        score.saturating_sub(self.penalty)
+    }
+}
+
+/// A swap that touched games that have already been played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPicksChanged {
+    pub slots: Vec<u8>,
+}
+
+impl fmt::Display for LockedPicksChanged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "picks changed for decided games {:?}", self.slots)
+    }
+}
+
+impl std::error::Error for LockedPicksChanged {}
+
+impl Bracket {
+    /// Checks that `to` only differs from this bracket in games `tournament`
+    /// hasn't decided, and prices the swap under `penalty`.
+    pub fn late_swap(
+        &self,
+        to: &Bracket,
+        tournament: &Tournament,
+        penalty: SwapPenalty,
+    ) -> Result<LateSwap, LockedPicksChanged> {
+        let moved = (self.decisions ^ to.decisions) & self.mask();
+        let locked: Vec<u8> = (1..=63u8)
+            .filter(|&slot| moved & tournament.mask() & (1 << slot) != 0)
+            .collect();
+        if !locked.is_empty() {
+            return Err(LockedPicksChanged { slots: locked });
+        }
+
+        let (before, after) = (self.decision_team_slots(), to.decision_team_slots());
+        let changed: Vec<u8> = (1..=63u8)
+            .rev()
+            .filter(|&slot| before[slot as usize] != after[slot as usize])
+            .collect();
+        let penalty = match penalty {
+            _ if changed.is_empty() => 0,
+            SwapPenalty::Flat(points) => points,
+            SwapPenalty::PerPick(points) => points.saturating_mul(changed.len() as Score),
+        };
+
+        Ok(LateSwap {
+            from: *self,
+            to: *to,
+            changed,
+            penalty,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    fn final_four_left() -> Tournament {
+        Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !0b1110,
+            vacated: 0,
+        }
+    }
+
+    #[test]
+    fn swapping_undecided_picks() {
+        let from = Bracket { decisions: 0 };
+        // A different semifinal winner, who is also carried into the final.
+        let to = Bracket {
+            decisions: (1 << 1) | (1 << 3),
+        };
+
+        let swap = from
+            .late_swap(&to, &final_four_left(), SwapPenalty::PerPick(2))
+            .unwrap();
+
+        assert_eq!(swap.changed, vec![3, 1]);
+        assert_eq!(swap.penalty, 4);
+        assert_eq!(swap.apply(10), 6);
+        assert_eq!(swap.apply(3), 0);
+    }
+
+    #[test]
+    fn rejects_changes_to_decided_games() {
+        let from = Bracket { decisions: 0 };
+        let to = Bracket {
+            decisions: (1 << 3) | (1 << 40),
+        };
+
+        assert_eq!(
+            from.late_swap(&to, &final_four_left(), SwapPenalty::Flat(5)),
+            Err(LockedPicksChanged { slots: vec![40] })
+        );
+    }
+}
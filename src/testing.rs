@@ -0,0 +1,187 @@
+//! Helpers for tests in crates that depend on this one: known tournament
+//! states, reproducible random ones, and standings assertions with readable
+//! failures.
+
+use crate::field::{Field, FieldBuilder};
+use crate::rng::Rng;
+use crate::{
+    round_slot_mask, seed_for_slot, standings_ranks, Bracket, Decisions, Score, Tournament,
+    SEED_ORDER,
+};
+
+/// Every game through `round` (0..=6) won by the side from the lower slot,
+/// the rest to be played. With the standard seed layout that's the better
+/// seed whenever the seeds went to form.
+pub fn chalk_through(round: u8) -> Tournament {
+    let mask = (1..=round).fold(0, |mask, r| mask | round_slot_mask(r));
+    Tournament::new(0, mask)
+}
+
+/// The first `games` games (0..=63) in the order they're played, highest
+/// slot first, with winners drawn from `seed`. The same seed always gives
+/// the same state, and every state is consistent: a game is only decided
+/// once both games feeding it are.
+pub fn random_tournament(seed: u64, games: u8) -> Tournament {
+    let mut rng = Rng::new(seed);
+    let mut tournament = Tournament::new(0, 0);
+    for slot in (64 - games.min(63)..=63).rev() {
+        tournament.set_decision(slot, (rng.next_u64() & 1) as u8);
+    }
+    tournament
+}
+
+/// Every game through `round` decided at random from `seed`.
+pub fn random_through(seed: u64, round: u8) -> Tournament {
+    let games = (1..=round)
+        .map(|r| round_slot_mask(r).count_ones())
+        .sum::<u32>();
+    random_tournament(seed, games as u8)
+}
+
+/// `count` brackets with picks drawn from `seed`.
+pub fn random_brackets(seed: u64, count: usize) -> Vec<Bracket> {
+    let mut rng = Rng::new(seed);
+    (0..count)
+        .map(|_| Bracket::from_decisions(rng.next_u64()))
+        .collect()
+}
+
+/// The bracket with the better seed winning every game (the lower team slot
+/// on a tie of seeds).
+pub fn chalk_bracket() -> Bracket {
+    let mut winners = [0u8; 128];
+    for (team, winner) in winners.iter_mut().enumerate().skip(64) {
+        *winner = team as u8;
+    }
+    let mut bracket = Bracket::from_decisions(0);
+    for slot in (1..=63u8).rev() {
+        let (a, b) = (winners[slot as usize * 2], winners[slot as usize * 2 + 1]);
+        let side = (seed_for_slot(b) < seed_for_slot(a)) as u8;
+        bracket.set_decision(slot, side);
+        winners[slot as usize] = if side == 0 { a } else { b };
+    }
+    bracket
+}
+
+/// The teams of [`standard_field`], not yet built.
+pub fn standard_field_builder() -> FieldBuilder {
+    let mut builder = FieldBuilder::new();
+    for region in ["East", "West", "South", "Midwest"] {
+        builder.region(region);
+        for seed in SEED_ORDER {
+            builder.push(&format!("{} {}", region, seed), seed);
+        }
+    }
+    builder
+}
+
+/// A 64-team field in the standard seed layout, its regions East, West,
+/// South and Midwest and each team named for its region and seed, from
+/// "East 1" to "Midwest 16".
+pub fn standard_field() -> Field {
+    standard_field_builder().build().unwrap()
+}
+
+/// A pool of `entries` brackets for benchmarks, drawn from `seed`. Each
+/// game's pick follows `consensus` with probability `similarity` (0.0 to
+/// 1.0) and is a coin flip otherwise, so 1.0 gives identical entries and 0.0
+/// independent random ones.
+pub fn synthetic_pool(
+    entries: usize,
+    consensus: &Bracket,
+    similarity: f64,
+    seed: u64,
+) -> Vec<Bracket> {
+    let mut rng = Rng::new(seed);
+    (0..entries)
+        .map(|_| {
+            let mut bracket = *consensus;
+            for slot in 1..=63u8 {
+                if rng.next_f64() >= similarity {
+                    bracket.set_decision(slot, (rng.next_u64() & 1) as u8);
+                }
+            }
+            bracket
+        })
+        .collect()
+}
+
+/// Panics unless `brackets` have the zero-based `expected` ranks against
+/// `tournament` (ties sharing the better rank), listing each entry's score
+/// and rank.
+pub fn assert_standings(tournament: &Tournament, brackets: &[Bracket], expected: &[usize]) {
+    let scores: Vec<Score> = brackets.iter().map(|b| b.score(tournament)).collect();
+    let ranks = standings_ranks(&scores);
+    if ranks != expected {
+        let table: Vec<String> = scores
+            .iter()
+            .zip(&ranks)
+            .zip(expected)
+            .enumerate()
+            .map(|(entry, ((score, rank), want))| {
+                format!(
+                    "  entry {}: {} points, rank {} (expected {})",
+                    entry, score, rank, want
+                )
+            })
+            .collect();
+        panic!("standings differ:\n{}", table.join("\n"));
+    }
+}
+
+/// Panics unless the two tournaments record the same results.
+pub fn assert_same_results(actual: &Tournament, expected: &Tournament) {
+    let differing: Vec<u8> = (1..=63)
+        .filter(|&slot| actual.decision(slot) != expected.decision(slot))
+        .collect();
+    assert!(
+        differing.is_empty(),
+        "results differ in games {:?}",
+        differing
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn states_are_reproducible_and_consistent() {
+        assert_eq!(chalk_through(1).mask(), round_slot_mask(1));
+        assert_same_results(&random_tournament(3, 40), &random_tournament(3, 40));
+
+        let tournament = random_through(9, 2);
+        assert_eq!(tournament.mask(), round_slot_mask(1) | round_slot_mask(2));
+        assert!(tournament.validate_strict().is_ok());
+        assert_eq!(random_brackets(5, 4), random_brackets(5, 4));
+    }
+
+    #[test]
+    fn standings_assertion() {
+        let finished = chalk_through(6);
+        let brackets = [Bracket::from_decisions(1 << 1), Bracket::from_decisions(0)];
+        assert_standings(&finished, &brackets, &[1, 0]);
+        let caught = std::panic::catch_unwind(|| assert_standings(&finished, &brackets, &[0, 1]));
+        assert!(caught.is_err());
+    }
+
+    #[test]
+    fn synthetic_pools_follow_similarity() {
+        let chalk = chalk_bracket();
+        assert_eq!(chalk.champion(), 64);
+        // The 4 seed (from game 35) beats the 5 seed (from game 34).
+        assert_eq!(chalk.decision(34), Some(0));
+        assert_eq!(chalk.decision(17), Some(1));
+
+        assert!(synthetic_pool(5, &chalk, 1.0, 1)
+            .iter()
+            .all(|b| *b == chalk));
+        let agreement = |similarity| {
+            let pool = synthetic_pool(50, &chalk, similarity, 2);
+            pool.iter()
+                .map(|b| 63 - (b.decisions() ^ chalk.decisions()).count_ones())
+                .sum::<u32>()
+        };
+        assert!(agreement(0.8) > agreement(0.2));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{Bracket, Decisions, Score, Tournament, POINTS_PER_ROUND};
+
+/// Results or picks plus a third-place game between the two semifinal
+/// losers, which sits outside the 63-game tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WithThirdPlace<D> {
+    pub main: D,
+    third_place: Option<u8>,
+}
+
+impl<D: Decisions> WithThirdPlace<D> {
+    /// `main` with the third-place game undecided (or unpicked).
+    pub fn new(main: D) -> WithThirdPlace<D> {
+        WithThirdPlace {
+            main,
+            third_place: None,
+        }
+    }
+
+    /// Records (or picks) the third-place game: `0` when the loser of
+    /// semifinal slot 2 wins it, `1` for the loser of slot 3. Panics on a
+    /// winner other than 0 or 1.
+    pub fn set_third_place(&mut self, side: u8) {
+        assert!(side <= 1, "third place must be 0 or 1, got {}", side);
+        self.third_place = Some(side);
+    }
+
+    /// The winner as [`WithThirdPlace::set_third_place`] took it, or `None`
+    /// while the game is undecided.
+    pub fn third_place(&self) -> Option<u8> {
+        self.third_place
+    }
+
+    /// The team (64..=127) that finishes third, once its semifinal is
+    /// decided and so is the third-place game.
+    pub fn third_place_team(&self) -> Option<u8> {
+        let side = self.third_place?;
+        let team_slots = self.main.decision_team_slots();
+        let semifinal = 2 + side as usize;
+        let winner = team_slots[semifinal]?;
+        let (a, b) = (team_slots[semifinal * 2]?, team_slots[semifinal * 2 + 1]?);
+        Some(if winner == a { b } else { a })
+    }
+}
+
+/// Scoring for the third-place game: `points` for naming the team that
+/// finishes third, whichever semifinal its bracket had it losing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThirdPlaceGame {
+    pub points: Score,
+}
+
+impl Default for ThirdPlaceGame {
+    /// Worth the same as a correct semifinal pick, before the seed bonus.
+    fn default() -> ThirdPlaceGame {
+        ThirdPlaceGame {
+            points: POINTS_PER_ROUND[5] as Score,
+        }
+    }
+}
+
+impl ThirdPlaceGame {
+    pub fn points_for(
+        &self,
+        bracket: &WithThirdPlace<Bracket>,
+        tournament: &WithThirdPlace<Tournament>,
+    ) -> Score {
+        match (bracket.third_place_team(), tournament.third_place_team()) {
+            (Some(picked), Some(third)) if picked == third => self.points,
+            _ => 0,
+        }
+    }
+
+    /// The bracket's score including the third-place game.
+    pub fn score(
+        &self,
+        bracket: &WithThirdPlace<Bracket>,
+        tournament: &WithThirdPlace<Tournament>,
+    ) -> Score {
+        bracket
+            .main
+            .score(&tournament.main)
+            .saturating_add(self.points_for(bracket, tournament))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_MASK;
+
+    #[test]
+    fn scores_the_team_not_the_side() {
+        // Chalk: 64 beats 80 in semifinal 2, 96 beats 112 in semifinal 3.
+        let mut tournament = WithThirdPlace::new(Tournament::new(0, COMPLETE_MASK));
+        tournament.set_third_place(1);
+        assert_eq!(tournament.third_place_team(), Some(112));
+
+        let game = ThirdPlaceGame::default();
+        let mut chalk = WithThirdPlace::new(Bracket::from_decisions(0));
+        chalk.set_third_place(1);
+        assert_eq!(game.points_for(&chalk, &tournament), game.points);
+        assert_eq!(
+            game.score(&chalk, &tournament),
+            chalk.main.score(&tournament.main) + game.points
+        );
+
+        // Has 112 beating 96, so 96 is its semifinal-3 loser.
+        let mut upset = WithThirdPlace::new(Bracket::from_decisions(1 << 3));
+        upset.set_third_place(1);
+        assert_eq!(upset.third_place_team(), Some(96));
+        assert_eq!(game.points_for(&upset, &tournament), 0);
+
+        let undecided = WithThirdPlace::new(tournament.main);
+        assert_eq!(undecided.third_place(), None);
+        assert_eq!(game.points_for(&chalk, &undecided), 0);
+
+        let caught = std::panic::catch_unwind(|| {
+            WithThirdPlace::new(Bracket::from_decisions(0)).set_third_place(2)
+        });
+        assert!(caught.is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::scoring::ScoringSystem;
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Score};
+
+/// How picks for a vacated game are credited. Only the vacated game itself is
+/// affected; later games are scored on whoever actually played them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VacatedCredit {
+    /// Nobody won the game, so nobody scores it.
+    #[default]
+    NoCredit,
+    /// A pick of either team scheduled to play the game scores as if correct.
+    Participants,
+    /// Every pick for the game scores as if correct.
+    Everyone,
+}
+
+impl TournamentView {
+    /// The bracket's score under `scoring`, plus whatever `credit` gives
+    /// for its picks of vacated games. A credited pick of one of the two
+    /// teams scheduled to play scores as if it beat the other.
+    pub fn score_with_vacated(
+        &self,
+        bracket: &Bracket,
+        scoring: &ScoringSystem,
+        credit: VacatedCredit,
+    ) -> Score {
+        let bracket_team_slots = bracket.decision_team_slots();
+        let team_slots = self.team_slots();
+
+        let vacated_points = (1..64u8)
+            .filter(|&slot| self.vacated() & (1 << slot) != 0)
+            .filter_map(|slot| {
+                let pick = bracket_team_slots[slot as usize]?;
+                let sides = [
+                    team_slots[slot as usize * 2],
+                    team_slots[slot as usize * 2 + 1],
+                ];
+                let participant = sides.contains(&Some(pick));
+                let credited = match credit {
+                    VacatedCredit::NoCredit => false,
+                    VacatedCredit::Participants => participant,
+                    VacatedCredit::Everyone => true,
+                };
+                let loser = sides
+                    .into_iter()
+                    .flatten()
+                    .find(|&team| participant && team != pick);
+                credited.then(|| scoring.points(slot, pick, loser))
+            })
+            .fold(0, Score::saturating_add);
+
+        self.score_with(bracket, scoring)
+            .saturating_add(vacated_points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BestFinishes, Tournament, COMPLETE_MASK};
+
+    // Title game still to play; the semifinal in slot 2 was a no-contest.
+    fn no_contest_semifinal() -> Tournament {
+        Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !(1 << 1),
+            vacated: 1 << 2,
+        }
+    }
+
+    #[test]
+    fn vacated_game_has_no_winner() {
+        let view = TournamentView::new(&no_contest_semifinal());
+
+        assert_eq!(view.team_slots()[2], None);
+        assert_eq!(view.team_slots()[3], Some(96));
+        assert_eq!(view.undecided(), &[1]);
+    }
+
+    #[test]
+    fn credit_options() {
+        let view = TournamentView::new(&no_contest_semifinal());
+        // Picks team 64 to win slot 2; team 64 did play in it.
+        let bracket = Bracket { decisions: 0 };
+        let standard = ScoringSystem::standard();
+        let base = view.score(&bracket);
+        let semifinal = crate::points_for_pick(2, 64);
+
+        assert_eq!(
+            view.score_with_vacated(&bracket, &standard, VacatedCredit::NoCredit),
+            base
+        );
+        assert_eq!(
+            view.score_with_vacated(&bracket, &standard, VacatedCredit::Participants),
+            base + semifinal
+        );
+        assert_eq!(
+            view.score_with_vacated(&bracket, &standard, VacatedCredit::Everyone),
+            base + semifinal
+        );
+
+        let doubling = ScoringSystem::doubling();
+        assert_eq!(
+            view.score_with_vacated(&bracket, &doubling, VacatedCredit::Participants),
+            view.score_with(&bracket, &doubling) + doubling.points(2, 64, None)
+        );
+        // Picks team 72, which never reached slot 2, so only `Everyone` credits.
+        let outsider = Bracket { decisions: 1 << 4 };
+        assert_eq!(
+            view.score_with_vacated(&outsider, &doubling, VacatedCredit::Participants),
+            view.score_with(&outsider, &doubling)
+        );
+        assert_eq!(
+            view.score_with_vacated(&outsider, &doubling, VacatedCredit::Everyone),
+            view.score_with(&outsider, &doubling) + doubling.points(2, 72, None)
+        );
+    }
+
+    #[test]
+    fn enumeration_only_advances_the_walkover() {
+        let view = TournamentView::new(&no_contest_semifinal());
+        let brackets = [Bracket { decisions: 0 }, Bracket { decisions: 1 << 1 }];
+
+        let best_finishes = BestFinishes::for_view(&brackets, &view);
+
+        // Only the team from slot 3 can win the title, which only the second
+        // bracket picked.
+        assert_eq!(best_finishes.possible_finishes[&brackets[1]], 0);
+        assert_eq!(best_finishes.possible_finishes[&brackets[0]], 1);
+    }
+}
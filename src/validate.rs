@@ -0,0 +1,268 @@
+use std::fmt;
+
+use crate::error::MadnessError;
+use crate::view::TournamentView;
+use crate::{Bracket, Decisions, Tournament};
+
+/// What strict validation expected to find at a slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    /// The game is decided, so the game feeding it from `child` must be too.
+    ChildDecided { child: u8 },
+    /// The game is undecided, so it must not carry a decision bit.
+    NoDecision,
+    /// The game is vacated, so it must also be marked decided.
+    VacatedDecided,
+    /// The game has a winner, so it can't have advanced them from the vacated
+    /// game in `child`.
+    NotVacated { child: u8 },
+    /// Bit 0 is not a game and must be clear in both fields.
+    Reserved,
+}
+
+/// A single decisions/mask invariant violation, with the bits as found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub slot: u8,
+    pub expected: Expected,
+    pub decision_bit: bool,
+    pub mask_bit: bool,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = format!(
+            "decision={} mask={}",
+            self.decision_bit as u8, self.mask_bit as u8
+        );
+        match self.expected {
+            Expected::ChildDecided { child } => write!(
+                f,
+                "slot {} is decided but its child slot {} is not ({})",
+                self.slot, child, bits
+            ),
+            Expected::NoDecision => write!(
+                f,
+                "slot {} is undecided but has its decision bit set ({})",
+                self.slot, bits
+            ),
+            Expected::VacatedDecided => write!(
+                f,
+                "slot {} is vacated but not marked decided ({})",
+                self.slot, bits
+            ),
+            Expected::NotVacated { child } => write!(
+                f,
+                "slot {} advances the winner of vacated slot {} ({})",
+                self.slot, child, bits
+            ),
+            Expected::Reserved => write!(f, "reserved slot 0 is in use ({})", bits),
+        }
+    }
+}
+
+/// Every invariant violation in `d`, lowest slot first. An empty result means
+/// `decision_team_slots` will describe exactly the state the bits encode.
+pub fn diagnose<D: Decisions + ?Sized>(d: &D) -> Vec<Diagnostic> {
+    let (decisions, mask, vacated) = (d.decisions(), d.mask(), d.vacated());
+    let bit = |field: u64, slot: u8| field & (1 << slot) != 0;
+    let mut diagnostics = Vec::new();
+
+    if bit(decisions, 0) || bit(mask, 0) {
+        diagnostics.push(Diagnostic {
+            slot: 0,
+            expected: Expected::Reserved,
+            decision_bit: bit(decisions, 0),
+            mask_bit: bit(mask, 0),
+        });
+    }
+
+    for slot in 1..=63u8 {
+        let found = |expected| Diagnostic {
+            slot,
+            expected,
+            decision_bit: bit(decisions, slot),
+            mask_bit: bit(mask, slot),
+        };
+        if !bit(mask, slot) {
+            if bit(decisions, slot) {
+                diagnostics.push(found(Expected::NoDecision));
+            }
+            if bit(vacated, slot) {
+                diagnostics.push(found(Expected::VacatedDecided));
+            }
+            continue;
+        }
+        if slot < 32 {
+            for child in [slot * 2, slot * 2 + 1] {
+                if !bit(mask, child) {
+                    diagnostics.push(found(Expected::ChildDecided { child }));
+                }
+            }
+            let winner_from = slot * 2 + bit(decisions, slot) as u8;
+            if !bit(vacated, slot) && bit(vacated, winner_from) {
+                diagnostics.push(found(Expected::NotVacated { child: winner_from }));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+impl Tournament {
+    pub fn validate_strict(&self) -> Result<(), Vec<Diagnostic>> {
+        let diagnostics = diagnose(self);
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
+        }
+    }
+}
+
+impl Tournament {
+    /// Like [`Tournament::new`], but rejects bits [`Tournament::new`] would
+    /// quietly drop or misread: a set bit 0, a decision bit on an undecided
+    /// game, or a decided game above an undecided one.
+    pub fn try_new(decisions: u64, mask: u64) -> Result<Tournament, MadnessError> {
+        let tournament = Tournament {
+            decisions,
+            mask,
+            vacated: 0,
+        };
+        tournament
+            .validate_strict()
+            .map_err(MadnessError::InvalidTournament)?;
+        Ok(tournament)
+    }
+}
+
+impl Bracket {
+    /// Like [`Bracket::from_decisions`], but rejects a set bit 0 instead of
+    /// dropping it. Every other combination of bits is a consistent bracket.
+    pub fn try_new(decisions: u64) -> Result<Bracket, MadnessError> {
+        if decisions & 1 != 0 {
+            return Err(MadnessError::ReservedBit);
+        }
+        Ok(Bracket::from_decisions(decisions))
+    }
+}
+
+impl TournamentView {
+    /// Like [`TournamentView::new`], but refuses to derive slot tables from a
+    /// tournament that violates the decisions/mask invariant.
+    pub fn new_strict(tournament: &Tournament) -> Result<TournamentView, Vec<Diagnostic>> {
+        tournament.validate_strict()?;
+        Ok(TournamentView::new(tournament))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bracket, COMPLETE_MASK};
+
+    #[test]
+    fn consistent_states_pass() {
+        let tournament = Tournament {
+            decisions: 1 << 40,
+            mask: 0xFFFF_FFFF_0000_0000,
+            vacated: 0,
+        };
+        assert_eq!(tournament.validate_strict(), Ok(()));
+        let walkover = Tournament {
+            decisions: 1 << 1,
+            mask: COMPLETE_MASK,
+            vacated: 1 << 2,
+        };
+        assert_eq!(walkover.validate_strict(), Ok(()));
+        assert!(diagnose(&Bracket {
+            decisions: COMPLETE_MASK
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn reports_each_violation() {
+        let tournament = Tournament {
+            decisions: (1 << 5) | 1,
+            mask: (1 << 1) | (1 << 3),
+            vacated: 0,
+        };
+
+        let diagnostics = TournamentView::new_strict(&tournament).unwrap_err();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    slot: 0,
+                    expected: Expected::Reserved,
+                    decision_bit: true,
+                    mask_bit: false,
+                },
+                Diagnostic {
+                    slot: 1,
+                    expected: Expected::ChildDecided { child: 2 },
+                    decision_bit: false,
+                    mask_bit: true,
+                },
+                Diagnostic {
+                    slot: 3,
+                    expected: Expected::ChildDecided { child: 6 },
+                    decision_bit: false,
+                    mask_bit: true,
+                },
+                Diagnostic {
+                    slot: 3,
+                    expected: Expected::ChildDecided { child: 7 },
+                    decision_bit: false,
+                    mask_bit: true,
+                },
+                Diagnostic {
+                    slot: 5,
+                    expected: Expected::NoDecision,
+                    decision_bit: true,
+                    mask_bit: false,
+                },
+            ]
+        );
+        let vacated_side = Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK,
+            vacated: (1 << 2) | (1 << 5),
+        };
+        assert_eq!(
+            diagnose(&vacated_side),
+            vec![Diagnostic {
+                slot: 1,
+                expected: Expected::NotVacated { child: 2 },
+                decision_bit: false,
+                mask_bit: true,
+            }]
+        );
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "slot 1 is decided but its child slot 2 is not (decision=0 mask=1)"
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_impossible_states() {
+        assert_eq!(Bracket::try_new(1), Err(MadnessError::ReservedBit));
+        assert_eq!(
+            Bracket::try_new(1 << 7),
+            Ok(Bracket::from_decisions(1 << 7))
+        );
+
+        assert!(Tournament::try_new(0, COMPLETE_MASK).is_ok());
+        let err = Tournament::try_new(1 << 5, 1 << 1).unwrap_err();
+        let MadnessError::InvalidTournament(diagnostics) = &err else {
+            panic!("expected diagnostics, got {:?}", err);
+        };
+        assert_eq!(diagnostics.len(), 3);
+        assert!(err
+            .to_string()
+            .starts_with("invalid tournament: slot 1 is decided"));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::scoring::ScoringSystem;
+use crate::{
+    points_for_team_slots, round_num_for_slot, Bracket, Decisions, Score, Tournament, ROUNDS,
+};
+
+/// Everything derived from a single tournament state, computed once and
+/// shared by scoring and enumeration until the tournament changes again.
+#[derive(Debug, Clone)]
+pub struct TournamentView {
+    // Game slots 1..=63 followed by the 64 first-round teams in 64..=127, so
+    // the enumeration can read both children of any game directly.
+    team_slots: [Option<u8>; 128],
+    round_masks: [u64; ROUNDS as usize + 1],
+    undecided: Vec<u8>,
+    vacated: u64,
+}
+
+impl TournamentView {
+    pub fn new(tournament: &Tournament) -> TournamentView {
+        TournamentView::from_decisions(tournament)
+    }
+
+    /// The view of results held in any [`Decisions`] type.
+    pub fn from_decisions(tournament: &dyn Decisions) -> TournamentView {
+        let mut team_slots: [Option<u8>; 128] = [None; 128];
+        team_slots[..64].copy_from_slice(&tournament.decision_team_slots());
+        for (team, slot) in team_slots.iter_mut().enumerate().skip(64) {
+            *slot = Some(team as u8);
+        }
+
+        let mut round_masks = [0; ROUNDS as usize + 1];
+        for (round, mask) in round_masks.iter_mut().enumerate().skip(1) {
+            *mask = tournament.decided_mask_for_round(round as u8);
+        }
+
+        // Highest slot first: the order games are played, and the order the
+        // enumeration fills them in.
+        let undecided = (1..=63u8)
+            .rev()
+            .filter(|&slot| tournament.mask() & (1 << slot) == 0)
+            .collect();
+
+        TournamentView {
+            team_slots,
+            round_masks,
+            undecided,
+            vacated: tournament.vacated(),
+        }
+    }
+
+    pub fn team_slots(&self) -> &[Option<u8>] {
+        &self.team_slots
+    }
+
+    /// Decided games of `round`, as bits of their game slots.
+    pub fn decided_in_round(&self, round: u8) -> u64 {
+        self.round_masks[round as usize]
+    }
+
+    /// Undecided game slots, latest round last.
+    pub fn undecided(&self) -> &[u8] {
+        &self.undecided
+    }
+
+    /// Undecided games that can really go either way, latest round last.
+    /// The rest are walkovers: a vacated game upstream leaves one side with
+    /// no team, so there's only one outcome.
+    pub fn open_games(&self) -> Vec<u8> {
+        let empty = self.empty_slots();
+        self.undecided
+            .iter()
+            .copied()
+            .filter(|&slot| !empty[slot as usize * 2] && !empty[slot as usize * 2 + 1])
+            .collect()
+    }
+
+    /// The undecided games [`TournamentView::open_games`] leaves out, each
+    /// with the only side it can be won from, latest round last.
+    pub fn walkovers(&self) -> Vec<(u8, u8)> {
+        let empty = self.empty_slots();
+        self.undecided
+            .iter()
+            .copied()
+            .filter(|&slot| empty[slot as usize * 2] || empty[slot as usize * 2 + 1])
+            .map(|slot| {
+                // Same rule as the enumeration: side 0 unless only side 1
+                // has a team.
+                let side = (empty[slot as usize * 2] && !empty[slot as usize * 2 + 1]) as u8;
+                (slot, side)
+            })
+            .collect()
+    }
+
+    /// Whether each slot can end up with no team in it.
+    fn empty_slots(&self) -> [bool; 128] {
+        let mut empty = [false; 128];
+        for slot in (1..64usize).rev() {
+            empty[slot] = if self.is_decided(slot as u8) {
+                self.team_slots[slot].is_none()
+            } else {
+                empty[slot * 2] && empty[slot * 2 + 1]
+            };
+        }
+        empty
+    }
+
+    /// Decided games that produced no winner, as bits of their game slots.
+    pub fn vacated(&self) -> u64 {
+        self.vacated
+    }
+
+    pub fn is_decided(&self, slot: u8) -> bool {
+        self.decided_in_round(round_num_for_slot(slot)) & (1 << slot) != 0
+    }
+
+    /// Whether `team` (a team slot, 64..=127) can still win the game in
+    /// `slot`: it won every decided game on the way there, and the rest are
+    /// still to be played.
+    pub fn can_reach(&self, team: u8, slot: u8) -> bool {
+        let mut game = team / 2;
+        loop {
+            if self.is_decided(game) && self.team_slots[game as usize] != Some(team) {
+                return false;
+            }
+            if game <= slot {
+                return game == slot;
+            }
+            game /= 2;
+        }
+    }
+
+    /// Every team that can still win the game in `slot`, lowest team slot
+    /// first.
+    pub fn contenders(&self, slot: u8) -> Vec<u8> {
+        // A game in round r sits above 2^r first-round teams.
+        let round = round_num_for_slot(slot);
+        let first_team = (slot as usize) << round;
+        (first_team..first_team + (1 << round))
+            .map(|team| team as u8)
+            .filter(|&team| self.can_reach(team, slot))
+            .collect()
+    }
+
+    pub fn score(&self, bracket: &Bracket) -> Score {
+        self.score_with(bracket, &ScoringSystem::standard())
+    }
+
+    pub fn score_with(&self, bracket: &Bracket, scoring: &ScoringSystem) -> Score {
+        bracket.points_for_decisions(&self.team_slots, scoring)
+    }
+
+    /// Points for `picks` held in any [`Decisions`] type, under `scoring`.
+    /// Games without a pick score nothing.
+    pub fn score_picks(&self, picks: &dyn Decisions, scoring: &ScoringSystem) -> Score {
+        points_for_team_slots(&self.team_slots, &picks.decision_team_slots(), scoring)
+    }
+
+    pub fn score_batch(&self, brackets: &[Bracket]) -> Vec<Score> {
+        self.score_batch_with(brackets, &ScoringSystem::standard())
+    }
+
+    /// Every bracket's score under `scoring`, in bracket order.
+    pub fn score_batch_with(&self, brackets: &[Bracket], scoring: &ScoringSystem) -> Vec<Score> {
+        brackets
+            .iter()
+            .map(|b| self.score_with(b, scoring))
+            .collect()
+    }
+
+    /// Calls `f` with the completed team-slot table of every way the undecided
+    /// games can still play out. With `outcome` set to `(slot, side)`, only
+    /// scenarios where `slot` is won from child `slot * 2 + side` are visited.
+    pub fn for_each_scenario<F: FnMut(&[Option<u8>])>(&self, outcome: Option<(u8, u8)>, mut f: F) {
+        let mut team_slots = self.team_slots;
+        walk_scenarios(&mut team_slots, &self.undecided, outcome, &mut f);
+    }
+}
+
+fn walk_scenarios<F: FnMut(&[Option<u8>])>(
+    team_slots: &mut [Option<u8>; 128],
+    undecided: &[u8],
+    outcome: Option<(u8, u8)>,
+    f: &mut F,
+) {
+    let Some((&slot, rest)) = undecided.split_first() else {
+        f(team_slots);
+        return;
+    };
+    let idx = slot as usize;
+    let decision_0 = team_slots[idx * 2];
+    let decision_1 = team_slots[idx * 2 + 1];
+    // Same walkover rule as `BestFinishes::fill`: a side with no team can't
+    // win, unless neither side has one.
+    let sides = [
+        (0, decision_0, decision_0.is_some() || decision_1.is_none()),
+        (1, decision_1, decision_1.is_some()),
+    ];
+
+    for (side, team, possible) in sides {
+        let excluded = outcome.is_some_and(|(s, forced)| s == slot && forced != side);
+        if possible && !excluded {
+            team_slots[idx] = team;
+            walk_scenarios(team_slots, rest, outcome, f);
+        }
+    }
+    team_slots[idx] = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{round_slot_mask, COMPLETE_MASK};
+
+    #[test]
+    fn round_masks_partition_the_games() {
+        let all = (1..=ROUNDS).fold(0, |acc, r| {
+            assert_eq!(acc & round_slot_mask(r), 0);
+            acc | round_slot_mask(r)
+        });
+        assert_eq!(all, COMPLETE_MASK);
+        for slot in 1..=63u8 {
+            assert_ne!(round_slot_mask(round_num_for_slot(slot)) & (1 << slot), 0);
+        }
+    }
+
+    #[test]
+    fn first_round_complete() {
+        let first_round = round_slot_mask(1);
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: first_round,
+            vacated: 0,
+        });
+
+        assert_eq!(view.decided_in_round(1), first_round);
+        assert_eq!(view.decided_in_round(2), 0);
+        assert_eq!(view.undecided(), (1..32).rev().collect::<Vec<u8>>());
+        assert_eq!(view.team_slots()[32], Some(64));
+        assert_eq!(view.team_slots()[16], None);
+        assert_eq!(view.team_slots()[127], Some(127));
+    }
+
+    #[test]
+    fn scenarios_respect_forced_outcome() {
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: COMPLETE_MASK & !((1 << 1) | (1 << 2)),
+            vacated: 0,
+        });
+
+        let mut champions = vec![];
+        view.for_each_scenario(None, |slots| champions.push(slots[1]));
+        assert_eq!(champions.len(), 4);
+
+        let mut champions = vec![];
+        view.for_each_scenario(Some((2, 1)), |slots| champions.push(slots[1]));
+        assert_eq!(champions, vec![view.team_slots()[5], view.team_slots()[3]]);
+    }
+
+    #[test]
+    fn reach_follows_decided_games() {
+        // Round 1 is done (top teams won); everything after is open.
+        let view = TournamentView::new(&Tournament {
+            decisions: 0,
+            mask: round_slot_mask(1),
+            vacated: 0,
+        });
+
+        assert!(view.can_reach(64, 1));
+        assert!(view.can_reach(66, 16));
+        assert!(!view.can_reach(65, 32));
+        assert!(!view.can_reach(65, 1));
+        // Not in the subtree of slot 2.
+        assert!(!view.can_reach(126, 2));
+    }
+
+    #[test]
+    fn works_through_trait_objects() {
+        // Picks kept somewhere else, e.g. a database row.
+        struct Row {
+            picks: u64,
+        }
+
+        impl Decisions for Row {
+            fn decisions(&self) -> u64 {
+                self.picks
+            }
+
+            fn mask(&self) -> u64 {
+                COMPLETE_MASK
+            }
+
+            fn set_decision(&mut self, slot: u8, winner: u8) {
+                self.picks = (self.picks & !(1 << slot)) | (winner as u64) << slot;
+            }
+        }
+
+        let results: Box<dyn Decisions> = Box::new(Row { picks: 1 << 40 });
+        let view = TournamentView::from_decisions(results.as_ref());
+        assert!(view.undecided().is_empty());
+
+        let entries: Vec<Box<dyn Decisions>> = vec![
+            Box::new(Row { picks: 1 << 40 }),
+            Box::new(Bracket::from_decisions(0)),
+        ];
+        let scoring = ScoringSystem::standard();
+        let perfect = view.score_picks(entries[0].as_ref(), &scoring);
+        assert_eq!(perfect, view.score(&Bracket::from_decisions(1 << 40)));
+        assert!(view.score_picks(entries[1].as_ref(), &scoring) < perfect);
+    }
+}